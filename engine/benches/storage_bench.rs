@@ -0,0 +1,169 @@
+// Compares the sparse-set `World` against the previous layout, where every component type lived in a
+// `HashMap<Entity, T>` and queries walked all entities doing one hash lookup per component type.
+// Run with `cargo bench --package lumina_engine --bench storage_bench`.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use lumina_engine::{
+    logic::{
+        ecs::{
+            component::{component::Component, transform::Transform},
+            entity::entity::Entity,
+        },
+        scene::world::World,
+    },
+    math::vec3::Vec3,
+};
+
+const FISH_COUNT: usize = 500;
+const SEAWEED_COUNT: usize = 2000;
+const ITERATIONS: u32 = 1000;
+
+#[derive(Component)]
+struct Fish {
+    speed: f32,
+}
+
+#[derive(Component)]
+struct Seaweed {
+    current: f32,
+}
+
+struct HashMapWorld {
+    entities: Vec<Entity>,
+    components: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl HashMapWorld {
+    fn new() -> Self {
+        Self {
+            entities: Vec::new(),
+            components: HashMap::new(),
+        }
+    }
+
+    fn create_entity(&mut self) -> Entity {
+        let entity = Entity::new(self.entities.len() as u32 + 1, 0);
+        self.entities.push(entity);
+        entity
+    }
+
+    fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
+        self.components
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(HashMap::<Entity, T>::new()))
+            .downcast_mut::<HashMap<Entity, T>>()
+            .unwrap()
+            .insert(entity, component);
+    }
+
+    fn storage_ptr<T: Component>(&mut self) -> *mut HashMap<Entity, T> {
+        self.components
+            .get_mut(&TypeId::of::<T>())
+            .unwrap()
+            .downcast_mut::<HashMap<Entity, T>>()
+            .unwrap()
+    }
+
+    fn for_each_fish(&mut self, mut f: impl FnMut(&mut Transform, &mut Fish)) {
+        let transforms = self.storage_ptr::<Transform>();
+        let fish = self.storage_ptr::<Fish>();
+        for entity in &self.entities {
+            unsafe {
+                if let (Some(transform), Some(fish)) =
+                    ((*transforms).get_mut(entity), (*fish).get_mut(entity))
+                {
+                    f(transform, fish);
+                }
+            }
+        }
+    }
+}
+
+fn transform() -> Transform {
+    Transform {
+        position: Vec3::zero(),
+        ..Default::default()
+    }
+}
+
+fn populate_world() -> World {
+    let mut world = World::load();
+    for i in 0..SEAWEED_COUNT + FISH_COUNT {
+        let entity = world.create_entity();
+        world.add_component(entity, transform());
+        if i % 5 == 0 {
+            world.add_component(entity, Fish { speed: 0.07 });
+        } else {
+            world.add_component(entity, Seaweed { current: 0.0 });
+        }
+    }
+    world
+}
+
+fn populate_hash_map_world() -> HashMapWorld {
+    let mut world = HashMapWorld::new();
+    for i in 0..SEAWEED_COUNT + FISH_COUNT {
+        let entity = world.create_entity();
+        world.add_component(entity, transform());
+        if i % 5 == 0 {
+            world.add_component(entity, Fish { speed: 0.07 });
+        } else {
+            world.add_component(entity, Seaweed { current: 0.0 });
+        }
+    }
+    world
+}
+
+fn measure(mut f: impl FnMut()) -> Duration {
+    // Warm up caches before timing
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let world = populate_world();
+    let sparse_set = measure(|| {
+        for (_, (transform, fish)) in world.query::<(&mut Transform, &Fish)>().iter() {
+            transform.position.x -= fish.speed * 0.016;
+        }
+        black_box(&world);
+    });
+
+    let mut hash_map_world = populate_hash_map_world();
+    let hash_map = measure(|| {
+        hash_map_world.for_each_fish(|transform, fish| {
+            transform.position.x -= fish.speed * 0.016;
+        });
+        black_box(&hash_map_world);
+    });
+
+    let seaweed = measure(|| {
+        let current: f32 = world
+            .query::<(&Transform, &Seaweed)>()
+            .iter()
+            .map(|(_, (transform, seaweed))| transform.position.x + seaweed.current)
+            .sum();
+        black_box(current);
+    });
+
+    println!(
+        "{} entities, {} matching (&mut Transform, &Fish)",
+        SEAWEED_COUNT + FISH_COUNT,
+        world.query::<(&Fish,)>().iter().count()
+    );
+    println!("sparse set: {:?}/iter", sparse_set);
+    println!("hash map:   {:?}/iter", hash_map);
+    println!("sparse set (&Transform, &Seaweed): {:?}/iter", seaweed);
+}
@@ -1,5 +1,4 @@
 use flume::Sender;
-use include_assets::{include_dir, NamedArchive};
 
 use glutin::config::{Config, ConfigTemplateBuilder, GlConfig};
 use glutin::context::{
@@ -21,14 +20,16 @@ use winit::keyboard::{Key, NamedKey};
 use winit::window::{Window, WindowBuilder};
 
 use crate::engine_config;
-use crate::logic::engine_logic::run_logic_loop;
+use crate::logic::engine_logic::{init_scene, input_files_from_env, resolve_seed, run_logic_loop};
 use crate::logic::scene::scene::Scene;
 use crate::math::vec2::Vec2;
 use crate::render::renderer::Renderer;
 use crate::render::resource::resource_loader::ResourceLoader;
 use crate::render::resource::resource_manager::ResourceManager;
-use crate::render::resource::resource_provider::ResourceProvider;
+#[cfg(not(target_os = "android"))]
+use crate::shared::input::gamepad;
 use crate::shared::input::input_event::InputEvent;
+use crate::shared::input::input_recording::{InputRecord, InputRecording, InputReplay};
 
 pub fn gl_config_picker(configs: Box<dyn Iterator<Item = Config> + '_>) -> Config {
     configs
@@ -81,7 +82,7 @@ pub fn start_with_android_app(
 
 fn start_with_event_loop(
     event_loop: EventLoop<()>,
-    mut on_init: impl FnMut(&mut Scene, &mut ResourceManager) + Send + 'static,
+    on_init: impl FnMut(&mut Scene, &mut ResourceManager) + Send + 'static,
 ) {
     event_loop.set_control_flow(ControlFlow::Poll);
 
@@ -134,16 +135,38 @@ fn start_with_event_loop(
 
     // Delegate logic to separate thread
     let _logic_handle = std::thread::spawn(move || {
-        let mut scene = Scene::new();
-        let mut resource_manager = ResourceManager::new(resource_tx.clone());
-        resource_manager.attach_archive(NamedArchive::load(include_dir!("assets")));
-        resource_manager.load_default_meshes();
-        resource_manager.load_default_shaders();
-        on_init(&mut scene, &mut resource_manager);
-        scene.get_world_mut().insert_resource(resource_manager);
-        run_logic_loop(input_rx, render_tx, scene);
+        let (mut recorder, recording) = input_files_from_env();
+        let seed = resolve_seed(
+            recording
+                .as_ref()
+                .and_then(InputRecording::seed)
+                .or(engine_config::RNG_SEED),
+        );
+        if let Some(recorder) = &mut recorder {
+            recorder
+                .record(&InputRecord::Seed(seed))
+                .expect("Failed to record input");
+        }
+        let scene = init_scene(ResourceManager::new(resource_tx.clone()), seed, on_init);
+        let replay = recording.map(InputReplay::new);
+        run_logic_loop(input_rx, render_tx, scene, recorder, replay);
     });
 
+    // Gamepads are polled on this thread and reach the logic thread as input events
+    #[cfg(not(target_os = "android"))]
+    let mut gilrs = match gilrs::Gilrs::new() {
+        Ok(gilrs) => {
+            for (id, _) in gilrs.gamepads() {
+                let _ = input_tx.send(InputEvent::GamepadConnected(id.into()));
+            }
+            Some(gilrs)
+        }
+        Err(err) => {
+            log::warn!("Gamepads are unavailable: {err}");
+            None
+        }
+    };
+
     let mut renderer: Option<Renderer> = None;
     let mut resource_loader: Option<ResourceLoader> = None;
     event_loop
@@ -182,6 +205,16 @@ fn start_with_event_loop(
                     resource_loader = ResourceLoader::new(resource_rx.clone()).into();
                     state.replace((gl_context, gl_surface, window));
                 }
+                #[cfg(not(target_os = "android"))]
+                Event::AboutToWait => {
+                    if let Some(gilrs) = &mut gilrs {
+                        while let Some(event) = gilrs.next_event() {
+                            if let Some(event) = gamepad::from_gilrs_event(event) {
+                                let _ = input_tx.send(event);
+                            }
+                        }
+                    }
+                }
                 Event::Suspended => {
                     let (gl_context, ..) = state.take().unwrap();
                     not_current_gl_context.replace(gl_context.make_not_current().unwrap());
@@ -284,16 +317,10 @@ fn start_with_event_loop(
                     WindowEvent::Touch(touch) => match touch.phase {
                         TouchPhase::Moved => handle_cursor_movement(&input_tx, touch.location),
                         TouchPhase::Started => {
-                            let _ = input_tx.send(InputEvent::MouseEvent {
-                                button: MouseButton::Left,
-                                pressed: true,
-                            });
+                            let _ = input_tx.send(InputEvent::Touch { pressed: true });
                         }
                         TouchPhase::Ended | TouchPhase::Cancelled => {
-                            let _ = input_tx.send(InputEvent::MouseEvent {
-                                button: MouseButton::Left,
-                                pressed: false,
-                            });
+                            let _ = input_tx.send(InputEvent::Touch { pressed: false });
                         }
                     },
                     _ => {}
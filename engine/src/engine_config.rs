@@ -3,8 +3,24 @@ pub const WINDOW_TITLE: &str = "Lumina";
 pub const INITIAL_WINDOW_WIDTH: i32 = 1200;
 pub const INITIAL_WINDOW_HEIGHT: i32 = 800;
 
+// Simulation
+// Seed of the scene's `StdRng`. None seeds from entropy, so runs differ
+pub const RNG_SEED: Option<u64> = None;
+// Seed of `HeadlessRunner::new`, so tests are reproducible unless they opt into entropy
+pub const HEADLESS_RNG_SEED: u64 = 0;
+// Environment variables naming a file to record input to, or to replay input from. A replay also
+// restores the recorded seed and delta times. Both executor modes hand out entities and apply
+// commands in system order, so a replay matches its recording in either
+pub const RECORD_INPUT_ENV: &str = "LUMINA_RECORD_INPUT";
+pub const REPLAY_INPUT_ENV: &str = "LUMINA_REPLAY_INPUT";
+// Environment variable naming a file of input bindings, loaded over the ones from the assets
+pub const INPUT_BINDINGS_ENV: &str = "LUMINA_INPUT_BINDINGS";
+
 // Assets
 pub const TEXTURES_PATH: &str = "textures";
+pub const PREFABS_PATH: &str = "prefabs";
+// Optional, see `ActionMap::load_bindings` for the format
+pub const INPUT_BINDINGS_PATH: &str = "config/input.ron";
 //#[cfg(target_os = "android")]
 //pub const SHADERS_PATH: &str = "es_shaders";
 //#[cfg(not(target_os = "android"))]
@@ -0,0 +1,150 @@
+use crate::{
+    engine_config,
+    logic::{
+        engine_logic::{init_scene, resolve_seed},
+        scene::{scene::Scene, time::Time},
+    },
+    render::resource::resource_manager::ResourceManager,
+    shared::{
+        engine_error::EngineError,
+        extracted_frame::ExtractedFrame,
+        input::{
+            input_event::InputEvent,
+            input_handler::InputHandler,
+            input_recording::{InputRecord, InputRecorder, InputRecording, InputReplay},
+        },
+    },
+};
+
+// Runs a scene without a window, GL context or render thread. Resources are placeholders and
+// ticks advance by a constant delta time instead of the wall clock
+pub struct HeadlessRunner {
+    scene: Scene,
+    delta_time: f32,
+    seed: u64,
+    recorder: Option<InputRecorder>,
+}
+
+impl HeadlessRunner {
+    pub fn new(on_init: impl FnMut(&mut Scene, &mut ResourceManager)) -> Self {
+        Self::seeded(engine_config::HEADLESS_RNG_SEED, on_init)
+    }
+
+    // A different run every time, like the app with no `RNG_SEED`. `seed` tells which one it was
+    pub fn from_entropy(on_init: impl FnMut(&mut Scene, &mut ResourceManager)) -> Self {
+        Self::with_rng_seed(None, on_init)
+    }
+
+    // Together with the constant delta time, the same seed and inputs give the same run
+    pub fn seeded(seed: u64, on_init: impl FnMut(&mut Scene, &mut ResourceManager)) -> Self {
+        Self::with_rng_seed(Some(seed), on_init)
+    }
+
+    // Seeded like the recorded run, ready for `replay`
+    pub fn for_replay(
+        recording: &InputRecording,
+        on_init: impl FnMut(&mut Scene, &mut ResourceManager),
+    ) -> Self {
+        Self::seeded(
+            recording.seed().unwrap_or(engine_config::HEADLESS_RNG_SEED),
+            on_init,
+        )
+    }
+
+    fn with_rng_seed(
+        seed: Option<u64>,
+        on_init: impl FnMut(&mut Scene, &mut ResourceManager),
+    ) -> Self {
+        let seed = resolve_seed(seed);
+        let mut scene = init_scene(ResourceManager::headless(), seed, on_init);
+        InputHandler::handle_input_event(
+            scene.get_world_mut(),
+            InputEvent::WindowResize {
+                width: engine_config::INITIAL_WINDOW_WIDTH,
+                height: engine_config::INITIAL_WINDOW_HEIGHT,
+            },
+        );
+        HeadlessRunner {
+            scene,
+            delta_time: 1.0 / 60.0,
+            seed,
+            recorder: None,
+        }
+    }
+
+    pub fn with_delta_time(mut self, delta_time: f32) -> Self {
+        self.delta_time = delta_time;
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Records the seed, then every input and tick from now on
+    pub fn start_recording(&mut self, mut recorder: InputRecorder) -> Result<(), EngineError> {
+        recorder.record(&InputRecord::Seed(self.seed))?;
+        self.recorder = Some(recorder);
+        Ok(())
+    }
+
+    // Handled before the next tick, like events from the window
+    pub fn send_input(&mut self, event: InputEvent) {
+        let frame = self.frame();
+        if let Some(recorder) = &mut self.recorder {
+            recorder
+                .record(&InputRecord::Event {
+                    frame,
+                    event: event.clone(),
+                })
+                .expect("Failed to record input");
+        }
+        InputHandler::handle_input_event(self.scene.get_world_mut(), event);
+    }
+
+    // One iteration of the logic loop. The frame would be sent to the renderer
+    pub fn tick(&mut self) -> ExtractedFrame {
+        self.tick_with(self.delta_time)
+    }
+
+    fn tick_with(&mut self, delta_time: f32) -> ExtractedFrame {
+        let frame = self.frame();
+        if let Some(recorder) = &mut self.recorder {
+            recorder
+                .record(&InputRecord::Frame { frame, delta_time })
+                .expect("Failed to record input");
+        }
+        self.scene.step(delta_time)
+    }
+
+    // Ticks until the recording runs out, with its inputs and delta times. Frames without a
+    // recorded delta time use the runner's
+    pub fn replay(&mut self, recording: InputRecording) {
+        let mut replay = InputReplay::new(recording);
+        while !replay.is_finished() {
+            let frame = self.frame();
+            let delta_time = replay
+                .apply(self.scene.get_world_mut(), frame)
+                .unwrap_or(self.delta_time);
+            self.tick_with(delta_time);
+        }
+    }
+
+    pub fn run(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.scene.get_world().expect_resource::<Time>().frame()
+    }
+
+    pub fn scene(&self) -> &Scene {
+        &self.scene
+    }
+
+    pub fn scene_mut(&mut self) -> &mut Scene {
+        &mut self.scene
+    }
+}
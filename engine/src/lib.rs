@@ -1,18 +1,22 @@
 extern crate gl;
 extern crate glutin;
 extern crate glutin_winit;
+// Lets code generated by `lumina_macro` use `::lumina_engine` paths inside this crate too
+extern crate self as lumina_engine;
 
 #[macro_use]
 pub mod macros;
 
 pub mod app;
 pub(crate) mod engine_config;
+pub mod headless;
 pub mod logic;
 pub mod math;
 pub mod render;
 pub mod shared;
 
 pub use app::start;
+pub use headless::HeadlessRunner;
 
 #[cfg(target_os = "android")]
 pub use app::start_with_android_app;
@@ -1,7 +1,13 @@
+pub mod access;
+pub mod borrow_cell;
+pub mod broad_phase;
+pub mod collision_event;
 pub mod collision_result;
+pub mod commands;
 pub mod component;
 pub mod component_storage;
 pub mod entity;
 pub mod extract;
 pub mod query;
+pub mod query_filter;
 pub mod system;
@@ -0,0 +1,72 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashSet,
+};
+
+use super::{component::component::Component, query::Query};
+
+// Components and resources a system reads and writes. The executor only runs systems at the
+// same time when their access is compatible; borrows are still checked at runtime, so access
+// that wasn't declared panics instead of racing
+#[derive(Clone, Debug, Default)]
+pub struct Access {
+    component_reads: HashSet<TypeId>,
+    component_writes: HashSet<TypeId>,
+    resource_reads: HashSet<TypeId>,
+    resource_writes: HashSet<TypeId>,
+}
+
+impl Access {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read<T: Component>(mut self) -> Self {
+        self.component_reads.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub fn write<T: Component>(mut self) -> Self {
+        self.component_writes.insert(TypeId::of::<T>());
+        self
+    }
+
+    // Everything the query borrows, e.g. `query::<(&mut Transform, &Movement)>()`
+    pub fn query<Q: Query>(mut self) -> Self {
+        Q::access(&mut self);
+        self
+    }
+
+    pub fn read_resource<T: 'static + Any + Send + Sync>(mut self) -> Self {
+        self.resource_reads.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub fn write_resource<T: 'static + Any + Send + Sync>(mut self) -> Self {
+        self.resource_writes.insert(TypeId::of::<T>());
+        self
+    }
+
+    pub(crate) fn add_component_read(&mut self, type_id: TypeId) {
+        self.component_reads.insert(type_id);
+    }
+
+    pub(crate) fn add_component_write(&mut self, type_id: TypeId) {
+        self.component_writes.insert(type_id);
+    }
+
+    // A query can't borrow a component's storage for reading and writing at once
+    pub(crate) fn reads_own_writes(&self) -> bool {
+        !self.component_reads.is_disjoint(&self.component_writes)
+    }
+
+    // Two systems conflict when one writes something the other reads or writes
+    pub fn is_compatible(&self, other: &Access) -> bool {
+        self.component_writes.is_disjoint(&other.component_reads)
+            && self.component_writes.is_disjoint(&other.component_writes)
+            && other.component_writes.is_disjoint(&self.component_reads)
+            && self.resource_writes.is_disjoint(&other.resource_reads)
+            && self.resource_writes.is_disjoint(&other.resource_writes)
+            && other.resource_writes.is_disjoint(&self.resource_reads)
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    cell::UnsafeCell,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicIsize, Ordering},
+};
+
+// Number of active shared borrows, or WRITING while mutably borrowed
+const WRITING: isize = -1;
+
+pub struct BorrowCell<T: ?Sized> {
+    state: AtomicIsize,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for BorrowCell<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for BorrowCell<T> {}
+
+impl<T> BorrowCell<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            state: AtomicIsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+}
+
+impl<T: ?Sized> BorrowCell<T> {
+    pub fn try_borrow(&self) -> Option<Ref<'_, T>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+        loop {
+            if state == WRITING {
+                return None;
+            }
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => state = current,
+            }
+        }
+        Some(Ref {
+            value: unsafe { NonNull::new_unchecked(self.value.get()) },
+            state: &self.state,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn try_borrow_mut(&self) -> Option<RefMut<'_, T>> {
+        self.state
+            .compare_exchange(0, WRITING, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(RefMut {
+            value: unsafe { NonNull::new_unchecked(self.value.get()) },
+            state: &self.state,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn is_borrowed(&self) -> bool {
+        self.state.load(Ordering::Relaxed) != 0
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+}
+
+pub struct Ref<'a, T: ?Sized> {
+    value: NonNull<T>,
+    state: &'a AtomicIsize,
+    _marker: PhantomData<&'a T>,
+}
+
+unsafe impl<T: ?Sized + Sync> Send for Ref<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Ref<'_, T> {}
+
+impl<'a, T: ?Sized> Ref<'a, T> {
+    pub fn map<U: ?Sized>(orig: Ref<'a, T>, f: impl FnOnce(&T) -> &U) -> Ref<'a, U> {
+        let value = NonNull::from(f(unsafe { orig.value.as_ref() }));
+        let state = orig.state;
+        std::mem::forget(orig);
+        Ref {
+            value,
+            state,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Drop for Ref<'_, T> {
+    fn drop(&mut self) {
+        self.state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+pub struct RefMut<'a, T: ?Sized> {
+    value: NonNull<T>,
+    state: &'a AtomicIsize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for RefMut<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for RefMut<'_, T> {}
+
+impl<'a, T: ?Sized> RefMut<'a, T> {
+    pub fn map<U: ?Sized>(
+        mut orig: RefMut<'a, T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> RefMut<'a, U> {
+        let value = NonNull::from(f(unsafe { orig.value.as_mut() }));
+        let state = orig.state;
+        std::mem::forget(orig);
+        RefMut {
+            value,
+            state,
+            _marker: PhantomData,
+        }
+    }
+
+    // Lets queries hand out disjoint `&mut` items from one guard
+    pub(crate) fn as_ptr(&self) -> *mut T {
+        self.value.as_ptr()
+    }
+}
+
+impl<T: ?Sized> Deref for RefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for RefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.value.as_mut() }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for RefMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Drop for RefMut<'_, T> {
+    fn drop(&mut self) {
+        self.state.store(0, Ordering::Release);
+    }
+}
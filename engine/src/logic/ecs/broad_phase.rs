@@ -0,0 +1,24 @@
+use crate::math::rect::Rect;
+
+// Sweep and prune along x: returns the index pairs `(i, j)` with `i < j` whose bounds overlap,
+// sorted like the nested loop over all pairs would visit them
+pub fn sweep_and_prune(bounds: &[Rect]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..bounds.len()).collect();
+    order.sort_by(|&a, &b| bounds[a].bottom_left.x.total_cmp(&bounds[b].bottom_left.x));
+
+    let mut pairs = vec![];
+    let mut active: Vec<usize> = vec![];
+    for index in order {
+        let rect = &bounds[index];
+        // Everything ending left of this rect ends left of all following ones as well
+        active.retain(|&other| bounds[other].top_right.x >= rect.bottom_left.x);
+        for &other in &active {
+            if rect.overlaps(&bounds[other]) {
+                pairs.push((index.min(other), index.max(other)));
+            }
+        }
+        active.push(index);
+    }
+    pairs.sort_unstable();
+    pairs
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::logic::ecs::entity::entity::Entity;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollisionEventKind {
+    Started,
+    Stayed,
+    Ended,
+}
+
+// `entity_a` is the one with the lower id
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionEvent {
+    pub kind: CollisionEventKind,
+    pub entity_a: Entity,
+    pub entity_b: Entity,
+    pub is_sensor: bool,
+}
+
+impl CollisionEvent {
+    // The entity on the other side, if `entity` is part of the collision
+    pub fn other(&self, entity: Entity) -> Option<Entity> {
+        match entity {
+            _ if entity == self.entity_a => Some(self.entity_b),
+            _ if entity == self.entity_b => Some(self.entity_a),
+            _ => None,
+        }
+    }
+}
+
+// Written by `CollisionSystem` on every fixed step and cleared at the start of every frame, so
+// systems in `Update` see the events of all fixed steps of their frame
+#[derive(Default)]
+pub struct CollisionEvents {
+    events: Vec<CollisionEvent>,
+    touching: HashMap<(Entity, Entity), bool>,
+}
+
+impl CollisionEvents {
+    pub fn iter(&self) -> impl Iterator<Item = &CollisionEvent> {
+        self.events.iter()
+    }
+
+    pub fn started(&self) -> impl Iterator<Item = &CollisionEvent> {
+        self.of_kind(CollisionEventKind::Started)
+    }
+
+    pub fn stayed(&self) -> impl Iterator<Item = &CollisionEvent> {
+        self.of_kind(CollisionEventKind::Stayed)
+    }
+
+    pub fn ended(&self) -> impl Iterator<Item = &CollisionEvent> {
+        self.of_kind(CollisionEventKind::Ended)
+    }
+
+    pub fn is_touching(&self, a: Entity, b: Entity) -> bool {
+        self.touching.contains_key(&Self::key(a, b))
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    // Takes every pair touching in this step along with whether it involves a sensor
+    pub fn record(&mut self, contacts: impl IntoIterator<Item = (Entity, Entity, bool)>) {
+        let mut touching = HashMap::new();
+        for (a, b, is_sensor) in contacts {
+            let key = Self::key(a, b);
+            let kind = match self.touching.contains_key(&key) {
+                true => CollisionEventKind::Stayed,
+                false => CollisionEventKind::Started,
+            };
+            self.push(kind, key, is_sensor);
+            touching.insert(key, is_sensor);
+        }
+        let mut ended: Vec<((Entity, Entity), bool)> = self
+            .touching
+            .drain()
+            .filter(|(key, _)| !touching.contains_key(key))
+            .collect();
+        ended.sort_by_key(|((a, b), _)| (a.id, a.generation, b.id, b.generation));
+        for (key, is_sensor) in ended {
+            self.push(CollisionEventKind::Ended, key, is_sensor);
+        }
+        self.touching = touching;
+    }
+
+    fn of_kind(&self, kind: CollisionEventKind) -> impl Iterator<Item = &CollisionEvent> {
+        self.events.iter().filter(move |event| event.kind == kind)
+    }
+
+    fn push(
+        &mut self,
+        kind: CollisionEventKind,
+        (entity_a, entity_b): (Entity, Entity),
+        is_sensor: bool,
+    ) {
+        self.events.push(CollisionEvent {
+            kind,
+            entity_a,
+            entity_b,
+            is_sensor,
+        });
+    }
+
+    fn key(a: Entity, b: Entity) -> (Entity, Entity) {
+        match (a.id, a.generation) <= (b.id, b.generation) {
+            true => (a, b),
+            false => (b, a),
+        }
+    }
+}
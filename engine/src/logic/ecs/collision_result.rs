@@ -1,5 +1,6 @@
 use crate::{logic::ecs::entity::entity::Entity, math::vec2::Vec2};
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct CollisionResult {
     pub entity_a: Entity,
     pub entity_b: Entity,
@@ -0,0 +1,59 @@
+use std::any::Any;
+
+use crate::logic::scene::world::World;
+
+use super::{component::component::Component, entity::entity::Entity};
+
+pub type Command = Box<dyn FnOnce(&mut World) + Send>;
+
+// Records structural changes so they can be made while the world is borrowed (e.g. inside a query).
+// Recorded commands are applied in order by `World::apply_commands`, which the schedule calls
+// after every batch of systems. Commands of a batch are applied in the order its systems were added.
+pub struct Commands<'w> {
+    world: &'w World,
+}
+
+impl<'w> Commands<'w> {
+    pub(crate) fn new(world: &'w World) -> Self {
+        Self { world }
+    }
+
+    // The entity can be used right away, but it only becomes alive once commands are applied
+    pub fn create_entity(&mut self) -> Entity {
+        self.world.reserve_entity()
+    }
+
+    pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
+        self.add(move |world| world.add_component(entity, component));
+    }
+
+    pub fn remove_component<T: Component>(&mut self, entity: Entity) {
+        self.add(move |world| {
+            world.remove_component::<T>(entity);
+        });
+    }
+
+    pub fn delete_entity(&mut self, entity: Entity) {
+        self.add(move |world| world.delete_entity(entity));
+    }
+
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        self.add(move |world| world.despawn_recursive(entity));
+    }
+
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        self.add(move |world| {
+            if let Err(err) = world.set_parent(child, parent) {
+                log::warn!("{}", err);
+            }
+        });
+    }
+
+    pub fn insert_resource<T: 'static + Any + Send + Sync>(&mut self, resource: T) {
+        self.add(move |world| world.insert_resource(resource));
+    }
+
+    pub fn add(&mut self, command: impl FnOnce(&mut World) + Send + 'static) {
+        self.world.push_command(Box::new(command));
+    }
+}
@@ -1,10 +1,14 @@
 pub mod camera;
+pub mod children;
 pub mod collider;
 pub mod component;
 pub mod emitter;
 pub mod force;
+pub mod global_transform;
 pub mod material;
 pub mod model;
 pub mod movement;
 pub mod parent;
+pub mod previous_transform;
+pub mod rigid_body;
 pub mod transform;
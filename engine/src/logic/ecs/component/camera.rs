@@ -1,6 +1,9 @@
 use crate::{
-    logic::ecs::component::component::Component,
-    math::{transformation, vec3::Vec3},
+    logic::{
+        ecs::component::component::Component, scene::matrix_uniform_buffer::MatrixUniformBuffer,
+    },
+    math::{mat4::Mat4, transformation, vec2::Vec2, vec3::Vec3},
+    shared::window_size::WindowSize,
 };
 
 #[derive(Clone, Component, Debug)]
@@ -13,10 +16,58 @@ pub struct Camera {
 }
 
 impl Camera {
-    pub fn get_view_matrix(&self) -> [[f32; 4]; 4] {
-        transformation::create_view_matrix(self.position)
+    pub fn get_view_matrix(&self) -> Mat4 {
+        Mat4::from_translation(-self.position)
     }
-    pub fn get_projection_matrix(&self, aspect_ratio: f32) -> [[f32; 4]; 4] {
+    pub fn get_projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
         transformation::create_ortographic_projection_matrix(aspect_ratio, self.near, self.far)
+            .into()
+    }
+
+    // Point at world depth `z` that is drawn at `screen_position`, given in window pixels from the
+    // top left like the mouse position. None if the depth can't be seen
+    pub fn screen_to_world(
+        matrices: &MatrixUniformBuffer,
+        window_size: &WindowSize,
+        screen_position: Vec2,
+        z: f32,
+    ) -> Option<Vec3> {
+        let ndc_x = screen_position.x / window_size.width as f32 * 2.0 - 1.0;
+        let ndc_y = 1.0 - screen_position.y / window_size.height as f32 * 2.0;
+        let cols = Self::view_projection(matrices).cols;
+        // Both clip coordinates have to land on the same ndc after dividing by w, which leaves a
+        // linear system in x and y
+        let row = |row: usize, ndc: f32| {
+            let entry = |col: usize| cols[col][row] - ndc * cols[col][3];
+            (entry(0), entry(1), -(entry(2) * z + entry(3)))
+        };
+        let (a, b, e) = row(0, ndc_x);
+        let (c, d, f) = row(1, ndc_y);
+        let determinant = a * d - b * c;
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+        Some(Vec3::new(
+            (e * d - b * f) / determinant,
+            (a * f - e * c) / determinant,
+            z,
+        ))
+    }
+
+    // Inverse of `screen_to_world`
+    pub fn world_to_screen(
+        matrices: &MatrixUniformBuffer,
+        window_size: &WindowSize,
+        position: Vec3,
+    ) -> Vec2 {
+        let ndc = Self::view_projection(matrices).transform_point(position);
+        Vec2::new(
+            (ndc.x + 1.0) / 2.0 * window_size.width as f32,
+            (1.0 - ndc.y) / 2.0 * window_size.height as f32,
+        )
+    }
+
+    fn view_projection(matrices: &MatrixUniformBuffer) -> Mat4 {
+        Mat4::from(matrices.projection_matrix) * Mat4::from(matrices.view_matrix)
     }
 }
@@ -0,0 +1,42 @@
+use crate::logic::ecs::entity::entity::Entity;
+
+use super::component::Component;
+
+// Entities whose `Parent` points at this entity. Kept up to date whenever a `Parent` is inserted or
+// removed, and by `HierarchySystem` for parents that are changed in place
+#[derive(Component, Clone, Debug, Default)]
+pub struct Children {
+    children: Vec<Entity>,
+}
+
+impl Children {
+    pub(crate) fn new(children: Vec<Entity>) -> Self {
+        Self { children }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.children.iter().copied()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.children.contains(&entity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub(crate) fn push(&mut self, child: Entity) {
+        if !self.contains(child) {
+            self.children.push(child);
+        }
+    }
+
+    pub(crate) fn remove(&mut self, child: Entity) {
+        self.children.retain(|&entity| entity != child);
+    }
+}
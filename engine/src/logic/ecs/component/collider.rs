@@ -1,35 +1,156 @@
-use crate::{logic::ecs::component::component::Component, math::vec2::Vec2};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-#[derive(Clone, PartialEq)]
+use crate::{
+    logic::ecs::component::component::Component,
+    math::{rect::Rect, vec2::Vec2},
+    shared::engine_error::EngineError,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ColliderShape {
     Rect { width: f32, height: f32 },
     Capsule2D { width: f32, height: f32 },
+    // Scaled by the x scale, like the width of a capsule
+    Circle { radius: f32 },
+    // Convex, in either winding order
+    Polygon { points: Vec<Vec2> },
+    // Several shapes moving as one, e.g. to follow the outline of a sprite
+    Compound { parts: Vec<ColliderPart> },
+    // Ground through heights spaced evenly over the width, solid from the surface down to one
+    // width below its lowest height. Contacts push out along the normal of the slope they hit
+    Heightfield { width: f32, heights: Vec<f32> },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColliderPart {
+    #[serde(default)]
+    pub offset: Vec2,
+    pub shape: ColliderShape,
+}
+
+impl ColliderShape {
+    // Polygons need at least three points that aren't all on one line, or they have no center
+    pub fn validate(&self) -> Result<(), EngineError> {
+        match self {
+            ColliderShape::Polygon { points } => {
+                let doubled_area: f32 = points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|(a, b)| a.x * b.y - b.x * a.y)
+                    .sum();
+                if points.len() < 3 || doubled_area.abs() <= TRESHOLD {
+                    return Err(EngineError::Generic(format!(
+                        "Degenerate collider polygon {:?}",
+                        points
+                    )));
+                }
+                Ok(())
+            }
+            ColliderShape::Compound { parts } => {
+                parts.iter().try_for_each(|part| part.shape.validate())
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
-#[derive(Component, Clone)]
+// A convex shape rounded by a radius: rects and polygons are their corners, capsules their
+// spine and circles their center
+struct Convex {
+    points: Vec<Vec2>,
+    radius: f32,
+}
+
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+#[component(serialize)]
 pub struct Collider {
+    #[serde(deserialize_with = "deserialize_shape")]
     pub shape: ColliderShape,
+    #[serde(default)]
     pub offset: Vec2,
+    // Bit masks: two colliders only collide if each one's layer is in the other's mask
+    #[serde(default = "default_layer")]
+    pub layer: u32,
+    #[serde(default = "all_layers")]
+    pub mask: u32,
+    // Sensors report their collisions as events but never push or get pushed
+    #[serde(default)]
+    pub is_sensor: bool,
+    // Recomputed by the collision system every step
+    #[serde(skip)]
     boundary_points: Vec<Vec2>,
 }
 
 const TRESHOLD: f32 = 1e-6;
 
+pub const DEFAULT_LAYER: u32 = 1;
+pub const ALL_LAYERS: u32 = u32::MAX;
+
+fn default_layer() -> u32 {
+    DEFAULT_LAYER
+}
+
+fn all_layers() -> u32 {
+    ALL_LAYERS
+}
+
+fn deserialize_shape<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ColliderShape, D::Error> {
+    let shape = ColliderShape::deserialize(deserializer)?;
+    shape.validate().map_err(de::Error::custom)?;
+    Ok(shape)
+}
+
 impl Collider {
+    // Each side is placed by its position, scale and rotation
     pub fn intersect(
         &self,
-        pos_a: Vec2,
-        scale_a: Vec2,
-        rot_a: f32,
+        (pos_a, scale_a, rot_a): (Vec2, Vec2, f32),
         other: &Collider,
-        pos_b: Vec2,
-        scale_b: Vec2,
-        rot_b: f32,
+        (pos_b, scale_b, rot_b): (Vec2, Vec2, f32),
     ) -> Option<(f32, Vec2)> {
-        let (width_a, height_a) = self.scaled_dims(scale_a);
-        let (width_b, height_b) = other.scaled_dims(scale_b);
+        Self::intersect_shapes(
+            (&self.shape, pos_a, scale_a, rot_a),
+            (&other.shape, pos_b, scale_b, rot_b),
+        )
+    }
 
-        match (&self.shape, &other.shape) {
+    // Each side is a shape with its position, scale and rotation
+    fn intersect_shapes(
+        (shape_a, pos_a, scale_a, rot_a): (&ColliderShape, Vec2, Vec2, f32),
+        (shape_b, pos_b, scale_b, rot_b): (&ColliderShape, Vec2, Vec2, f32),
+    ) -> Option<(f32, Vec2)> {
+        let (width_a, height_a) = Self::scaled_dims(shape_a, scale_a);
+        let (width_b, height_b) = Self::scaled_dims(shape_b, scale_b);
+
+        match (shape_a, shape_b) {
+            // Compound colliders report their deepest part
+            (ColliderShape::Compound { parts }, _) => parts
+                .iter()
+                .filter_map(|part| {
+                    let part_pos = Self::part_position(pos_a, scale_a, rot_a, part.offset);
+                    Self::intersect_shapes(
+                        (&part.shape, part_pos, scale_a, rot_a),
+                        (shape_b, pos_b, scale_b, rot_b),
+                    )
+                })
+                .max_by(|(depth_1, _), (depth_2, _)| depth_1.total_cmp(depth_2)),
+            (_, ColliderShape::Compound { .. }) => Self::intersect_shapes(
+                (shape_b, pos_b, scale_b, rot_b),
+                (shape_a, pos_a, scale_a, rot_a),
+            )
+            .map(|(depth, normal)| (depth, -normal)),
+            (ColliderShape::Heightfield { .. }, ColliderShape::Heightfield { .. }) => None,
+            (ColliderShape::Heightfield { .. }, _) => Self::intersect_shapes(
+                (shape_b, pos_b, scale_b, rot_b),
+                (shape_a, pos_a, scale_a, rot_a),
+            )
+            .map(|(depth, normal)| (depth, -normal)),
+            (_, ColliderShape::Heightfield { .. }) => Self::intersect_heightfield(
+                &Self::convex(shape_a, pos_a, scale_a, rot_a),
+                &Self::heightfield_columns(shape_b, pos_b, scale_b, rot_b),
+            ),
             (ColliderShape::Rect { .. }, ColliderShape::Rect { .. }) => Self::intersect_rect_rect(
                 pos_a, rot_a, width_a, height_a, pos_b, rot_b, width_b, height_b,
             ),
@@ -49,13 +170,56 @@ impl Collider {
                     pos_a, rot_a, width_a, height_a, pos_b, rot_b, width_b, height_b,
                 )
             }
+            _ => Self::intersect_convex(
+                &Self::convex(shape_a, pos_a, scale_a, rot_a),
+                &Self::convex(shape_b, pos_b, scale_b, rot_b),
+            ),
         }
     }
 
-    fn scaled_dims(&self, scale: Vec2) -> (f32, f32) {
-        match self.shape {
+    fn scaled_dims(shape: &ColliderShape, scale: Vec2) -> (f32, f32) {
+        match shape {
             ColliderShape::Rect { width, height } => (width * scale.x, height * scale.y),
             ColliderShape::Capsule2D { width, height } => (width * scale.x, height * scale.y),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    // Parts are placed in the collider's space, so they scale and rotate with it
+    fn part_position(pos: Vec2, scale: Vec2, rot: f32, offset: Vec2) -> Vec2 {
+        Vec2::new(offset.x * scale.x, offset.y * scale.y).rotated(rot) + pos
+    }
+
+    fn convex(shape: &ColliderShape, pos: Vec2, scale: Vec2, rot: f32) -> Convex {
+        let (width, height) = Self::scaled_dims(shape, scale);
+        match shape {
+            ColliderShape::Rect { .. } => Convex {
+                points: Self::rect_corners(pos, rot, width, height).to_vec(),
+                radius: 0.0,
+            },
+            ColliderShape::Capsule2D { .. } => {
+                let (spine_1, spine_2) = Self::capsule_spine(pos, rot, width, height);
+                Convex {
+                    points: vec![spine_1, spine_2],
+                    radius: width.abs() / 2.0,
+                }
+            }
+            ColliderShape::Circle { radius } => Convex {
+                points: vec![pos],
+                radius: (radius * scale.x).abs(),
+            },
+            ColliderShape::Polygon { points } => Convex {
+                points: points
+                    .iter()
+                    .map(|point| Self::part_position(pos, scale, rot, *point))
+                    .collect(),
+                radius: 0.0,
+            },
+            // Split into their parts before
+            ColliderShape::Compound { .. } | ColliderShape::Heightfield { .. } => Convex {
+                points: vec![pos],
+                radius: 0.0,
+            },
         }
     }
 
@@ -89,6 +253,9 @@ impl Collider {
 
     fn closest_on_segment(point: Vec2, seg_a: Vec2, seg_b: Vec2) -> Vec2 {
         let seg_vec = seg_b - seg_a;
+        if Vec2::dot(&seg_vec, &seg_vec) <= TRESHOLD {
+            return seg_a;
+        }
         let t =
             (Vec2::dot(&(point - seg_a), &seg_vec) / Vec2::dot(&seg_vec, &seg_vec)).clamp(0.0, 1.0);
         seg_a + seg_vec * t
@@ -171,11 +338,12 @@ impl Collider {
     ) -> Option<(f32, Vec2)> {
         let corners_a = Self::rect_corners(pos_a, rot_a, width_a, height_a);
         let corners_b = Self::rect_corners(pos_b, rot_b, width_b, height_b);
+        // Rotated the same way as the corners
         let axes = [
-            Vec2::new(rot_a.cos(), rot_a.sin()),
-            Vec2::new(-rot_a.sin(), rot_a.cos()),
-            Vec2::new(rot_b.cos(), rot_b.sin()),
-            Vec2::new(-rot_b.sin(), rot_b.cos()),
+            Vec2::new(1.0, 0.0).rotated(rot_a),
+            Vec2::new(0.0, 1.0).rotated(rot_a),
+            Vec2::new(1.0, 0.0).rotated(rot_b),
+            Vec2::new(0.0, 1.0).rotated(rot_b),
         ];
         let mut min_overlap = f32::INFINITY;
         let mut collision_normal = Vec2::zero();
@@ -294,14 +462,231 @@ impl Collider {
         Some((radius_sum - dist, normal))
     }
 
+    // Edge normals of a polygon, or the normal of a segment
+    fn edge_normals(points: &[Vec2]) -> Vec<Vec2> {
+        let edges = match points.len() {
+            0 | 1 => 0,
+            2 => 1,
+            len => len,
+        };
+        (0..edges)
+            .map(|i| points[(i + 1) % points.len()] - points[i])
+            .filter(|edge| edge.length() > TRESHOLD)
+            .map(|edge| Vec2::new(-edge.y, edge.x).normalized())
+            .collect()
+    }
+
+    fn edges(points: &[Vec2]) -> Vec<(Vec2, Vec2)> {
+        match points.len() {
+            0 => vec![],
+            1 => vec![(points[0], points[0])],
+            2 => vec![(points[0], points[1])],
+            len => (0..len)
+                .map(|i| (points[i], points[(i + 1) % len]))
+                .collect(),
+        }
+    }
+
+    // Closest points of two disjoint convex shapes, one of them is always on a corner
+    fn closest_points_convex(points_a: &[Vec2], points_b: &[Vec2]) -> (Vec2, Vec2) {
+        let a_to_b = points_a.iter().flat_map(|&point| {
+            Self::edges(points_b)
+                .into_iter()
+                .map(move |(start, end)| (point, Self::closest_on_segment(point, start, end)))
+        });
+        let b_to_a = points_b.iter().flat_map(|&point| {
+            Self::edges(points_a)
+                .into_iter()
+                .map(move |(start, end)| (Self::closest_on_segment(point, start, end), point))
+        });
+        a_to_b
+            .chain(b_to_a)
+            .min_by(|(a_1, b_1), (a_2, b_2)| {
+                (*a_1 - *b_1).length().total_cmp(&(*a_2 - *b_2).length())
+            })
+            .unwrap_or((Vec2::zero(), Vec2::zero()))
+    }
+
+    // Separating axis test over the edge normals of both shapes, plus the axis between their
+    // closest points which separates rounded shapes near a corner
+    fn intersect_convex(a: &Convex, b: &Convex) -> Option<(f32, Vec2)> {
+        let mut axes = Self::edge_normals(&a.points);
+        axes.extend(Self::edge_normals(&b.points));
+        let (closest_a, closest_b) = Self::closest_points_convex(&a.points, &b.points);
+        let gap = closest_a - closest_b;
+        if gap.length() > TRESHOLD {
+            axes.push(gap.normalized());
+        }
+        if axes.is_empty() {
+            axes.push(Vec2::new(0.0, 1.0));
+        }
+
+        let mut min_overlap = f32::INFINITY;
+        let mut collision_normal = Vec2::zero();
+        for axis in axes {
+            let (min_a, max_a) = Self::project_onto_axis(&a.points, axis);
+            let (min_b, max_b) = Self::project_onto_axis(&b.points, axis);
+            let overlap =
+                (max_a + a.radius).min(max_b + b.radius) - (min_a - a.radius).max(min_b - b.radius);
+            if overlap <= 0.0 {
+                return None;
+            }
+            if overlap < min_overlap {
+                min_overlap = overlap;
+                collision_normal = axis;
+            }
+        }
+        let center = |points: &[Vec2]| {
+            points.iter().fold(Vec2::zero(), |sum, point| sum + *point) / points.len() as f32
+        };
+        if Vec2::dot(&(center(&a.points) - center(&b.points)), &collision_normal) < 0.0 {
+            collision_normal *= -1.0; // Normal should point from B to A
+        }
+        Some((min_overlap, collision_normal))
+    }
+
+    // Inside the polygon core, or within the radius of its outline
+    fn convex_contains_point(convex: &Convex, point: Vec2) -> bool {
+        let inside_core = convex.points.len() > 2
+            && Self::edge_normals(&convex.points).into_iter().all(|axis| {
+                let (min, max) = Self::project_onto_axis(&convex.points, axis);
+                (min..=max).contains(&Vec2::dot(&point, &axis))
+            });
+        let distance = Self::edges(&convex.points)
+            .into_iter()
+            .map(|(start, end)| (point - Self::closest_on_segment(point, start, end)).length())
+            .fold(f32::INFINITY, f32::min);
+        inside_core || distance <= convex.radius
+    }
+
+    // The solid column below each segment of a heightfield along with the segment's upward normal
+    fn heightfield_columns(
+        shape: &ColliderShape,
+        pos: Vec2,
+        scale: Vec2,
+        rot: f32,
+    ) -> Vec<(Convex, Vec2)> {
+        let ColliderShape::Heightfield { width, heights } = shape else {
+            return vec![];
+        };
+        let bottom = heights.iter().copied().fold(f32::INFINITY, f32::min) - width;
+        let step = width / (heights.len().max(2) - 1) as f32;
+        let place = |x: f32, y: f32| Self::part_position(pos, scale, rot, Vec2::new(x, y));
+        heights
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let left = -width / 2.0 + step * i as f32;
+                let right = left + step;
+                let (start, end) = (place(left, pair[0]), place(right, pair[1]));
+                let edge = end - start;
+                let column = Convex {
+                    points: vec![start, end, place(right, bottom), place(left, bottom)],
+                    radius: 0.0,
+                };
+                (column, Vec2::new(-edge.y, edge.x).normalized())
+            })
+            .collect()
+    }
+
+    // Of the columns the shape overlaps, the one it sank deepest into below its surface. Pushing
+    // out along the surface normal instead of the shallowest axis keeps bodies from catching on
+    // the sides of the columns
+    fn intersect_heightfield(shape: &Convex, columns: &[(Convex, Vec2)]) -> Option<(f32, Vec2)> {
+        columns
+            .iter()
+            .filter(|(column, _)| Self::intersect_convex(shape, column).is_some())
+            .map(|(column, normal)| {
+                let (lowest, _) = Self::project_onto_axis(&shape.points, *normal);
+                let (_, surface) = Self::project_onto_axis(&column.points, *normal);
+                (surface - (lowest - shape.radius), *normal)
+            })
+            .filter(|(depth, _)| *depth > 0.0)
+            .max_by(|(depth_1, _), (depth_2, _)| depth_1.total_cmp(depth_2))
+    }
+
+    // Whether a point lies inside the collider, placed like in `intersect`
+    pub fn contains_point(&self, point: Vec2, pos: Vec2, scale: Vec2, rot: f32) -> bool {
+        Self::shape_contains_point(&self.shape, point, pos, scale, rot)
+    }
+
+    fn shape_contains_point(
+        shape: &ColliderShape,
+        point: Vec2,
+        pos: Vec2,
+        scale: Vec2,
+        rot: f32,
+    ) -> bool {
+        let (width, height) = Self::scaled_dims(shape, scale);
+        match shape {
+            ColliderShape::Rect { .. } => {
+                let local = (point - pos).rotated(-rot);
+                local.x.abs() <= width / 2.0 && local.y.abs() <= height / 2.0
+            }
+            ColliderShape::Capsule2D { .. } => {
+                let (spine_1, spine_2) = Self::capsule_spine(pos, rot, width, height);
+                (point - Self::closest_on_segment(point, spine_1, spine_2)).length() <= width / 2.0
+            }
+            ColliderShape::Compound { parts } => parts.iter().any(|part| {
+                let part_pos = Self::part_position(pos, scale, rot, part.offset);
+                Self::shape_contains_point(&part.shape, point, part_pos, scale, rot)
+            }),
+            ColliderShape::Heightfield { .. } => Self::heightfield_columns(shape, pos, scale, rot)
+                .iter()
+                .any(|(column, _)| Self::convex_contains_point(column, point)),
+            _ => Self::convex_contains_point(&Self::convex(shape, pos, scale, rot), point),
+        }
+    }
+
+    // Axis aligned bounds of the collider, placed like in `intersect`
+    pub fn bounds(&self, pos: Vec2, scale: Vec2, rot: f32) -> Rect {
+        Self::shape_bounds(&self.shape, pos, scale, rot)
+    }
+
+    fn shape_bounds(shape: &ColliderShape, pos: Vec2, scale: Vec2, rot: f32) -> Rect {
+        match shape {
+            ColliderShape::Compound { parts } => {
+                let corners: Vec<Vec2> = parts
+                    .iter()
+                    .flat_map(|part| {
+                        let part_pos = Self::part_position(pos, scale, rot, part.offset);
+                        let bounds = Self::shape_bounds(&part.shape, part_pos, scale, rot);
+                        [bounds.bottom_left, bounds.top_right]
+                    })
+                    .collect();
+                Rect::from_points(&corners)
+            }
+            ColliderShape::Heightfield { .. } => {
+                let corners: Vec<Vec2> = Self::heightfield_columns(shape, pos, scale, rot)
+                    .into_iter()
+                    .flat_map(|(column, _)| column.points)
+                    .collect();
+                Rect::from_points(&corners)
+            }
+            _ => {
+                let convex = Self::convex(shape, pos, scale, rot);
+                Rect::from_points(&convex.points).grown(convex.radius)
+            }
+        }
+    }
+
     pub fn boundary_points(&self) -> &[Vec2] {
         &self.boundary_points
     }
 
     pub fn compute_boundary_points(&mut self, pos: Vec2, scale: Vec2, rotation: f32) {
+        self.boundary_points = Self::shape_boundary_points(&self.shape, pos, scale, rotation);
+    }
+
+    fn shape_boundary_points(
+        shape: &ColliderShape,
+        pos: Vec2,
+        scale: Vec2,
+        rotation: f32,
+    ) -> Vec<Vec2> {
         // TODO: improve resolution
-        let (width, height) = self.scaled_dims(scale);
-        self.boundary_points = match self.shape {
+        let (width, height) = Self::scaled_dims(shape, scale);
+        match shape {
             ColliderShape::Rect { .. } => Self::rect_corners(pos, rotation, width, height).to_vec(),
             ColliderShape::Capsule2D { .. } => {
                 let radius = width / 2.0;
@@ -315,19 +700,72 @@ impl Collider {
                     spine_bottom + Vec2::new(-radius, 0.0).rotated(rotation),
                 ]
             }
-        };
+            ColliderShape::Circle { radius } => {
+                let radius = radius * scale.x;
+                (0..8)
+                    .map(|i| {
+                        let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+                        pos + Vec2::new(radius, 0.0).rotated(rotation + angle)
+                    })
+                    .collect()
+            }
+            ColliderShape::Polygon { .. } => Self::convex(shape, pos, scale, rotation).points,
+            ColliderShape::Compound { parts } => parts
+                .iter()
+                .flat_map(|part| {
+                    let part_pos = Self::part_position(pos, scale, rotation, part.offset);
+                    Self::shape_boundary_points(&part.shape, part_pos, scale, rotation)
+                })
+                .collect(),
+            // The surface
+            ColliderShape::Heightfield { .. } => {
+                let columns = Self::heightfield_columns(shape, pos, scale, rotation);
+                let last = columns.last().map(|(column, _)| column.points[1]);
+                columns
+                    .iter()
+                    .map(|(column, _)| column.points[0])
+                    .chain(last)
+                    .collect()
+            }
+        }
     }
 }
 
 impl Collider {
+    // Panics on shapes that don't pass `ColliderShape::validate`
     pub fn new(shape: ColliderShape) -> Self {
+        if let Err(err) = shape.validate() {
+            panic!("Invalid collider: {}", err);
+        }
         Self {
             shape,
             offset: Vec2::zero(),
+            layer: DEFAULT_LAYER,
+            mask: ALL_LAYERS,
+            is_sensor: false,
             boundary_points: Vec::new(),
         }
     }
 
+    pub fn with_layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: u32) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn as_sensor(mut self) -> Self {
+        self.is_sensor = true;
+        self
+    }
+
+    pub fn collides_with(&self, other: &Collider) -> bool {
+        self.layer & other.mask != 0 && other.layer & self.mask != 0
+    }
+
     pub fn with_offset(mut self, offset: Vec2) -> Self {
         self.offset = offset;
         self
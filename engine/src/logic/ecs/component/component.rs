@@ -1,6 +1,20 @@
 use std::any::Any;
 
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::logic::ecs::entity::entity_map::EntityMap;
+
 pub trait Component: 'static + Any + Send + Sync {}
 
+// Components that can be stored in a world snapshot, see `SnapshotRegistry`. Usually implemented
+// with `#[derive(Component)]` and `#[component(serialize)]`. Prefabs clone them for every spawn
+pub trait SerializeComponent: Component + Clone + Serialize + DeserializeOwned {
+    // Key of the component in snapshots, so it must stay stable across versions
+    const NAME: &'static str;
+
+    // Points entity references at the entities they were loaded as
+    fn map_entities(&mut self, _entity_map: &EntityMap) {}
+}
+
 use lumina_macro;
 pub use lumina_macro::Component;
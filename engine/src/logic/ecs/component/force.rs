@@ -1,24 +1,31 @@
+use serde::{Deserialize, Serialize};
+
 use crate::math::vec3::Vec3;
 
 use super::component::Component;
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
+#[component(serialize)]
 pub struct Force {
     pub mass: f32,
+    #[serde(default)]
     forces: Vec<AppliedForce>,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct AppliedForce {
     pub id: String,
     pub effect: ForceEffect,
     pub mode: ForceMode,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ForceEffect {
     Linear(Vec3),
     Drag(f32),
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ForceMode {
     Impulse,
     Continuous,
@@ -0,0 +1,22 @@
+use crate::math::transformation;
+
+use super::{component::Component, transform::Transform};
+
+// World space transform of an entity, its ancestors applied. Written by
+// `TransformPropagationSystem`, so changes to `Transform` show up after the next propagation
+#[derive(Component, Default, Clone, Debug, PartialEq)]
+pub struct GlobalTransform {
+    pub transform: Transform,
+    // Built from each ancestor's `PreviousTransform`, or its `Transform` where there is none
+    pub previous: Transform,
+}
+
+impl GlobalTransform {
+    pub fn matrix(&self) -> [[f32; 4]; 4] {
+        transformation::create_transform_matrix(&self.transform, None)
+    }
+
+    pub fn previous_matrix(&self) -> [[f32; 4]; 4] {
+        transformation::create_transform_matrix(&self.previous, None)
+    }
+}
@@ -1,5 +1,7 @@
 use std::{collections::HashMap, sync::Arc};
 
+use serde::{Deserialize, Serialize};
+
 use crate::render::{
     resource::shader::{
         material_parameter::MaterialParameter,
@@ -10,8 +12,9 @@ use crate::render::{
 
 use super::component::Component;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum DrawMode {
+    #[default]
     Triangles,
     Lines,
     Patches,
@@ -1,8 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::math::vec3::Vec3;
 
 use super::component::Component;
 
-#[derive(Component, Default)]
+#[derive(Component, Clone, Default, Serialize, Deserialize)]
+#[component(serialize)]
+#[serde(default)]
 pub struct Movement {
     pub direction: Vec3,
     pub velocity: Vec3,
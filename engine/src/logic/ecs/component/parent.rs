@@ -1,8 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::logic::ecs::entity::entity::Entity;
 
 use super::component::Component;
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Serialize, Deserialize)]
+#[component(serialize)]
 pub struct Parent {
     pub parent: Entity,
 }
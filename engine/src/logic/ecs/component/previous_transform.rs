@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use super::{component::Component, transform::Transform};
+
+// Opts an entity into render interpolation. Holds its `Transform` from before the latest fixed
+// step, or None until the first step has run
+#[derive(Component, Default, Clone, Debug, Serialize, Deserialize)]
+#[component(serialize)]
+pub struct PreviousTransform(pub Option<Transform>);
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use super::component::Component;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BodyType {
+    // Never moved by collisions
+    Static,
+    // Moves with its `Movement` but pushes others like a static body
+    Kinematic,
+    // Takes its mass from `Force::mass`
+    #[default]
+    Dynamic,
+}
+
+// How an entity with a `Collider` responds to collisions. Colliders without one are dynamic
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[component(serialize)]
+#[serde(default)]
+pub struct RigidBody {
+    pub body_type: BodyType,
+    // 0 stops along the collision normal, 1 bounces back at the same speed
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+impl Default for RigidBody {
+    fn default() -> Self {
+        Self {
+            body_type: BodyType::Dynamic,
+            restitution: 0.0,
+            friction: 0.3,
+        }
+    }
+}
+
+impl RigidBody {
+    pub fn new(body_type: BodyType) -> Self {
+        Self {
+            body_type,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+}
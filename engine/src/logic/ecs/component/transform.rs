@@ -1,8 +1,12 @@
+use serde::{Deserialize, Serialize};
+
 use crate::math::{vec2::Vec2, vec3::Vec3};
 
 use super::component::Component;
 
-#[derive(Component, Default, Clone, Debug)]
+#[derive(Component, Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[component(serialize)]
+#[serde(default)]
 pub struct Transform {
     pub position: Vec3,
     pub rotation: f32,
@@ -1,40 +1,152 @@
-use std::{any::Any, collections::HashMap};
+use std::any::Any;
 
 use super::{component::component::Component, entity::entity::Entity};
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComponentTicks {
+    pub added: u32,
+    pub changed: u32,
+}
+
+impl ComponentTicks {
+    pub fn new(tick: u32) -> Self {
+        Self {
+            added: tick,
+            changed: tick,
+        }
+    }
+
+    pub fn is_added(&self, last_run: u32, this_run: u32) -> bool {
+        is_newer(self.added, last_run, this_run)
+    }
+
+    pub fn is_changed(&self, last_run: u32, this_run: u32) -> bool {
+        is_newer(self.changed, last_run, this_run)
+    }
+}
+
+// Compares ages relative to `this_run` so ticks keep working after the counter wraps around
+fn is_newer(tick: u32, last_run: u32, this_run: u32) -> bool {
+    this_run.wrapping_sub(tick) < this_run.wrapping_sub(last_run)
+}
+
+// Sparse set: `sparse` maps entity ids to indices into the densely packed `entities`, `components` and `ticks`
 pub struct ComponentStorage<T: Component> {
-    storage: HashMap<Entity, T>,
+    sparse: Vec<Option<usize>>,
+    entities: Vec<Entity>,
+    components: Vec<T>,
+    ticks: Vec<ComponentTicks>,
 }
 
 impl<T: Component> ComponentStorage<T> {
     pub fn new() -> Box<ComponentStorage<T>> {
         Box::new(Self {
-            storage: HashMap::new(),
+            sparse: Vec::new(),
+            entities: Vec::new(),
+            components: Vec::new(),
+            ticks: Vec::new(),
         })
     }
 
-    pub fn as_any(component: &Box<dyn Component>) -> &dyn Any {
-        component
+    fn dense_index(&self, entity: Entity) -> Option<usize> {
+        let index = (*self.sparse.get(entity.id as usize)?)?;
+        (self.entities[index] == entity).then_some(index)
     }
 
-    pub fn add(&mut self, entity: Entity, component: Box<dyn Any>) {
-        if let Ok(comp) = component.downcast::<T>() {
-            self.storage.insert(entity, *comp);
-        } else {
-            panic!("Attempted to insert a component of the wrong type")
+    pub fn insert(&mut self, entity: Entity, component: T, tick: u32) {
+        let id = entity.id as usize;
+        if self.sparse.len() <= id {
+            self.sparse.resize(id + 1, None);
+        }
+        match self.sparse[id] {
+            // Overwrites both the same entity and a stale generation occupying the slot
+            Some(index) => {
+                if self.entities[index] == entity {
+                    self.ticks[index].changed = tick;
+                } else {
+                    self.ticks[index] = ComponentTicks::new(tick);
+                }
+                self.entities[index] = entity;
+                self.components[index] = component;
+            }
+            None => {
+                self.sparse[id] = Some(self.entities.len());
+                self.entities.push(entity);
+                self.components.push(component);
+                self.ticks.push(ComponentTicks::new(tick));
+            }
         }
     }
 
     pub fn remove(&mut self, entity: Entity) -> Option<T> {
-        self.storage.remove(&entity)
+        let index = self.dense_index(entity)?;
+        self.sparse[entity.id as usize] = None;
+        self.entities.swap_remove(index);
+        self.ticks.swap_remove(index);
+        let component = self.components.swap_remove(index);
+        if let Some(moved) = self.entities.get(index) {
+            self.sparse[moved.id as usize] = Some(index);
+        }
+        Some(component)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.dense_index(entity).is_some()
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        self.dense_index(entity)
+            .map(|index| &self.components[index])
+    }
+
+    // Doesn't mark the component as changed, see `get_mut_marked`
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        self.dense_index(entity)
+            .map(|index| &mut self.components[index])
+    }
+
+    pub fn get_mut_marked(&mut self, entity: Entity, tick: u32) -> Option<&mut T> {
+        let index = self.dense_index(entity)?;
+        self.ticks[index].changed = tick;
+        Some(&mut self.components[index])
+    }
+
+    pub fn ticks(&self, entity: Entity) -> Option<ComponentTicks> {
+        self.dense_index(entity).map(|index| self.ticks[index])
+    }
+
+    // Safety: the caller must ensure no other reference to this entity's component is alive for 'a
+    pub(crate) unsafe fn get_mut_unchecked<'a>(
+        storage: *mut Self,
+        entity: Entity,
+        tick: u32,
+    ) -> Option<&'a mut T> {
+        let index = (*storage).dense_index(entity)?;
+        (*(*storage).ticks.as_mut_ptr().add(index)).changed = tick;
+        Some(&mut *(*storage).components.as_mut_ptr().add(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
     }
 
-    pub fn get(&self, entity: Entity) -> Option<&dyn Any> {
-        self.storage.get(&entity).map(|c| c as &dyn Any)
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.entities.iter().copied().zip(self.components.iter())
     }
 
-    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut dyn Any> {
-        self.storage.get_mut(&entity).map(|c| c as &mut dyn Any)
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.entities
+            .iter()
+            .copied()
+            .zip(self.components.iter_mut())
     }
 }
 
@@ -46,7 +158,7 @@ pub trait ComponentStorageTrait: Any + Send + Sync + 'static {
 
 impl<T: Component> ComponentStorageTrait for ComponentStorage<T> {
     fn remove_entity(&mut self, entity: Entity) {
-        self.storage.remove(&entity);
+        self.remove(entity);
     }
 
     fn as_any(&self) -> &dyn Any {
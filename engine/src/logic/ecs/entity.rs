@@ -1,2 +1,3 @@
 pub mod entity;
+pub mod entity_map;
 pub mod particle_entity;
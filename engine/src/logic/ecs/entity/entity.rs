@@ -1,2 +1,19 @@
-#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug)]
-pub struct Entity(pub u32);
+use serde::{Deserialize, Serialize};
+
+#[derive(Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Entity {
+    pub id: u32,
+    pub generation: u32,
+}
+
+impl Entity {
+    pub const NULL: Entity = Entity::new(0, 0);
+
+    pub const fn new(id: u32, generation: u32) -> Self {
+        Self { id, generation }
+    }
+
+    pub const fn is_null(&self) -> bool {
+        self.id == 0
+    }
+}
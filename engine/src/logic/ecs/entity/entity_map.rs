@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use super::entity::Entity;
+
+// Maps the entities of a snapshot or prefab to the entities they were spawned as
+#[derive(Clone, Debug, Default)]
+pub struct EntityMap {
+    entities: HashMap<Entity, Entity>,
+}
+
+impl EntityMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, from: Entity, to: Entity) {
+        self.entities.insert(from, to);
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<Entity> {
+        self.entities.get(&entity).copied()
+    }
+
+    // References to entities that weren't part of the snapshot become null
+    pub fn map(&self, entity: Entity) -> Entity {
+        self.get(entity).unwrap_or(Entity::NULL)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
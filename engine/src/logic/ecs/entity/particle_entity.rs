@@ -1,8 +1,4 @@
-use std::{
-    f32::consts::PI,
-    ops::Range,
-    time::{Duration, Instant},
-};
+use std::{f32::consts::PI, ops::Range, time::Duration};
 
 use rand::{rngs::StdRng, Rng};
 
@@ -62,7 +58,8 @@ pub struct ParticleEntity {
     pub spawn_position: Vec3,
     pub position: Vec3,
     pub velocity: Vec3,
-    pub spawn_time: Instant,
+    // Scene time the particle was spawned at
+    pub spawn_time: Duration,
     pub amplitude: f32,
     pub frequency: f32,
     pub offset: f32,
@@ -75,6 +72,7 @@ impl ParticleEntity {
         mut spawn_position: Vec3,
         model: Model,
         rng: &mut StdRng,
+        now: Duration,
     ) -> Self {
         if config.spawn_jitter.x > 0.0 {
             spawn_position.x += rng.gen_range(-config.spawn_jitter.x..config.spawn_jitter.x);
@@ -89,7 +87,7 @@ impl ParticleEntity {
             spawn_position,
             position: spawn_position,
             velocity: config.base_velocity,
-            spawn_time: Instant::now(),
+            spawn_time: now,
             amplitude: rng.gen_range(config.amplitude_range.clone()),
             frequency: rng.gen_range(config.frequency_range.clone()),
             offset: rng.gen_range(config.offset_range.clone()),
@@ -99,8 +97,8 @@ impl ParticleEntity {
         }
     }
 
-    pub fn update(&mut self, delta_time: f32) {
-        let duration = Instant::now().duration_since(self.spawn_time);
+    pub fn update(&mut self, delta_time: f32, now: Duration) {
+        let duration = now.saturating_sub(self.spawn_time);
         if let Some(ls) = self.config.lifespan {
             if ls < duration {
                 self.alive = false;
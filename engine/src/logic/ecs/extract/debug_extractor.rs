@@ -30,7 +30,7 @@ impl Extractor for DebugExtractor {
             return;
         }
 
-        for (_, (collider, transform)) in world.query::<(&Collider, &Transform)>() {
+        for (_, (collider, transform)) in world.query::<(&Collider, &Transform)>().iter() {
             if let Some(resource_manager) = world.get_resource::<ResourceManager>() {
                 let key = ColliderShapeKey::from_shape(&collider.shape);
                 if let Some(mesh) = resource_manager.get_collider_mesh(key) {
@@ -50,6 +50,7 @@ impl Extractor for DebugExtractor {
                         mesh: mesh.clone(),
                         material,
                         z_index: transform.position.z,
+                        previous_model_matrix: None,
                     });
                 }
             }
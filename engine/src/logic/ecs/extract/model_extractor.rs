@@ -1,22 +1,36 @@
+use std::collections::HashMap;
+
 use crate::{
     logic::{
         ecs::{
             component::{
-                emitter::Emitter, material::Material, model::Model, parent::Parent,
+                emitter::Emitter, global_transform::GlobalTransform, material::Material,
+                model::Model, parent::Parent, previous_transform::PreviousTransform,
                 transform::Transform,
             },
             entity::entity::Entity,
             extract::extractor::Extractor,
+            query_filter::{Changed, Without},
         },
         scene::{matrix_uniform_buffer::MatrixUniformBuffer, world::World},
     },
-    math::transformation::{self, get_world_transform},
+    math::transformation,
     render::uniform_buffer_source::UniformBufferSource,
     shared::{
         extracted_frame::ExtractedFrame, render_entity::RenderEntity, window_size::WindowSize,
     },
 };
-pub struct ModelExtractor;
+#[derive(Default)]
+pub struct ModelExtractor {
+    // Model matrices, recomputed only when their transform changes
+    model_matrices: HashMap<Entity, [[f32; 4]; 4]>,
+}
+
+impl ModelExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
 impl Extractor for ModelExtractor {
     fn extract(&mut self, world: &World, frame: &mut ExtractedFrame) {
@@ -24,66 +38,98 @@ impl Extractor for ModelExtractor {
             frame.uniform_buffers.push(source.extract());
         }
         let window_size = world.get_resource::<WindowSize>();
-        frame.window_size = window_size.cloned();
-        for (entity, (model, transform)) in world.query::<(&Model, &Transform)>() {
-            let parent_component = world.get_component::<Parent>(entity).cloned();
-            if let None = world.get_component::<Emitter>(entity) {
-                Self::prepare_entity(
-                    world,
-                    frame,
-                    entity,
-                    parent_component,
-                    transform.clone(),
-                    model,
-                );
+        frame.window_size = window_size.as_deref().cloned();
+        for (entity, _) in world
+            .query::<(Changed<Transform>,)>()
+            .iter()
+            .chain(world.query::<(Changed<GlobalTransform>,)>().iter())
+        {
+            self.model_matrices.remove(&entity);
+        }
+        self.model_matrices
+            .retain(|entity, _| world.is_alive(*entity));
+        for (entity, (model, transform, global, parent, previous, _)) in world
+            .query::<(
+                &Model,
+                &Transform,
+                Option<&GlobalTransform>,
+                Option<&Parent>,
+                Option<&PreviousTransform>,
+                Without<Emitter>,
+            )>()
+            .iter()
+        {
+            if parent.is_some_and(|parent| parent.parent.is_null()) {
+                continue;
             }
+            // Entities spawned since the last propagation are drawn where they are locally
+            let world_transform = global.map_or(transform, |global| &global.transform);
+            let transform_matrix = *self
+                .model_matrices
+                .entry(entity)
+                .or_insert_with(|| transformation::create_transform_matrix(world_transform, None));
+            let previous_matrix =
+                previous
+                    .and_then(|previous| previous.0.as_ref())
+                    .map(|previous| match global {
+                        Some(global) => global.previous_matrix(),
+                        None => transformation::create_transform_matrix(previous, None),
+                    });
+            Self::push_entity(
+                world,
+                frame,
+                entity,
+                world_transform,
+                transform_matrix,
+                previous_matrix,
+                model,
+            );
         }
     }
 }
 
 impl ModelExtractor {
+    // Draws the model at a transform that is already in world space, without interpolation
     pub fn prepare_entity(
         world: &World,
         frame: &mut ExtractedFrame,
         entity: Entity,
-        parent: Option<Parent>,
         transform: Transform,
         model: &Model,
     ) {
-        if let Some(parent) = &parent {
-            if parent.parent.0 == 0 {
-                return;
-            }
-        }
-        let parent_world_transform = parent.as_ref().and_then(|parent| {
-            get_world_transform(
-                parent.parent,
-                &|e| world.get_component::<Transform>(e).cloned(),
-                &|e| world.get_component::<Parent>(e).cloned(),
-            )
-        });
-        let transform_matrix =
-            transformation::create_transform_matrix(&transform, parent_world_transform.as_ref());
-        let material = world.get_component::<Material>(entity).cloned();
+        let transform_matrix = transformation::create_transform_matrix(&transform, None);
+        Self::push_entity(
+            world,
+            frame,
+            entity,
+            &transform,
+            transform_matrix,
+            None,
+            model,
+        );
+    }
+
+    fn push_entity(
+        world: &World,
+        frame: &mut ExtractedFrame,
+        entity: Entity,
+        world_transform: &Transform,
+        transform_matrix: [[f32; 4]; 4],
+        previous_matrix: Option<[[f32; 4]; 4]>,
+        model: &Model,
+    ) {
+        let material = world.get_component::<Material>(entity).as_deref().cloned();
         if material.is_none() {
             return;
         }
         let mut material = material.unwrap();
-        let is_flipped = transform.is_flipped
-            ^ parent_world_transform
-                .as_ref()
-                .map(|e| e.is_flipped)
-                .unwrap_or(false);
         material.set_param("uModelMatrix", transform_matrix);
-        material.set_param("uFlipped", is_flipped as i32);
+        material.set_param("uFlipped", world_transform.is_flipped as i32);
         frame.entities.push(RenderEntity {
             mesh: model.mesh.clone(),
             material: material,
-            z_index: transform.position.z
-                + parent_world_transform
-                    .as_ref()
-                    .map(|e| e.position.z)
-                    .unwrap_or(0.0),
+            z_index: world_transform.position.z,
+            previous_model_matrix: previous_matrix,
         });
     }
 }
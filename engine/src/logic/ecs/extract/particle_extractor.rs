@@ -12,14 +12,14 @@ pub struct ParticleExtractor;
 
 impl Extractor for ParticleExtractor {
     fn extract(&mut self, world: &World, frame: &mut ExtractedFrame) {
-        for (entity, (emitter, model, transform)) in world.query::<(&Emitter, &Model, &Transform)>()
+        for (entity, (emitter, model, transform)) in
+            world.query::<(&Emitter, &Model, &Transform)>().iter()
         {
             for particle in emitter.particles.iter() {
                 ModelExtractor::prepare_entity(
                     world,
                     frame,
                     entity,
-                    None,
                     Transform {
                         position: particle.position,
                         rotation: transform.rotation,
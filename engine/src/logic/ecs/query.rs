@@ -1,141 +1,205 @@
-use std::marker::PhantomData;
-
 use crate::logic::scene::world::World;
 
+use std::any::TypeId;
+
 use super::{
-    component::component::Component, component_storage::ComponentStorage, entity::entity::Entity,
+    access::Access,
+    borrow_cell::{Ref, RefMut},
+    component::component::Component,
+    component_storage::ComponentStorage,
+    entity::entity::Entity,
 };
 
-pub trait Query<'a> {
-    type Item;
-    type Iterator: Iterator<Item = (Entity, Self::Item)>;
+// A query borrows the storage of every component it reads or writes for as long as it is alive.
+// Conflicting borrows (e.g. `&mut T` while another query reads `T`) panic instead of aliasing.
+pub trait Query {
+    type Borrow<'w>;
+    type Item<'q>;
+
+    // Returns None when a required storage doesn't exist, so the query matches nothing
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>>;
+
+    // Entities that can possibly match, or None if this part doesn't narrow the query down
+    fn entities<'a>(borrow: &'a Self::Borrow<'_>) -> Option<&'a [Entity]>;
+
+    /// # Safety
+    /// The caller must not fetch the same entity again while the returned item is alive
+    unsafe fn fetch<'q>(borrow: &'q Self::Borrow<'_>, entity: Entity) -> Option<Self::Item<'q>>;
 
-    fn create_query(world: &World) -> Self::Iterator;
+    // Records the storages `borrow` takes, so systems can declare their access from queries
+    fn access(access: &mut Access);
 }
 
-pub trait QueryMut<'a> {
-    type Item;
-    type Iterator: Iterator<Item = (Entity, Self::Item)>;
+impl<T: Component> Query for &T {
+    type Borrow<'w> = Ref<'w, ComponentStorage<T>>;
+    type Item<'q> = &'q T;
 
-    fn create_query(world: &mut World) -> Self::Iterator;
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        world.get_storage::<T>()
+    }
+
+    fn entities<'a>(borrow: &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        Some(borrow.entities())
+    }
+
+    unsafe fn fetch<'q>(borrow: &'q Self::Borrow<'_>, entity: Entity) -> Option<Self::Item<'q>> {
+        borrow.get(entity)
+    }
+
+    fn access(access: &mut Access) {
+        access.add_component_read(TypeId::of::<T>());
+    }
 }
 
-macro_rules! impl_query {
-    ($IterName:ident, $(($Comp:ident => $var:ident)),+) => {
-        pub struct $IterName<'a, $($Comp: Component),+> {
-            entities_ptr: *const Entity,
-            entities_len: usize,
-            $( $var: Option<*const ComponentStorage<$Comp>>, ) +
-            index: usize,
-            #[allow(unused_parens)]
-            _marker: PhantomData<&'a ( $($Comp),+ )>,
-        }
+// Mutable access marks the component as changed, whether or not it is written to
+impl<T: Component> Query for &mut T {
+    type Borrow<'w> = (RefMut<'w, ComponentStorage<T>>, u32);
+    type Item<'q> = &'q mut T;
+
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        Some((world.get_storage_mut::<T>()?, world.change_tick()))
+    }
+
+    fn entities<'a>((storage, _): &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        Some(storage.entities())
+    }
+
+    unsafe fn fetch<'q>(
+        (storage, tick): &'q Self::Borrow<'_>,
+        entity: Entity,
+    ) -> Option<Self::Item<'q>> {
+        ComponentStorage::get_mut_unchecked(storage.as_ptr(), entity, *tick)
+    }
+
+    fn access(access: &mut Access) {
+        access.add_component_write(TypeId::of::<T>());
+    }
+}
 
-        impl<'a, $($Comp: Component),+> Query<'a> for ( $(&'a $Comp),+, ) {
-            type Item = ( $(&'a $Comp),+, );
-            type Iterator = $IterName<'a, $($Comp),+>;
-
-            fn create_query(world: &World) -> Self::Iterator {
-                $IterName {
-                    entities_ptr: world.entities.as_ptr(),
-                    entities_len: world.entities.len(),
-                    $( $var: world.get_storage_ptr::<$Comp>(), )+
-                    index: 0,
-                    _marker: PhantomData,
-                }
+impl<Q: Query> Query for Option<Q> {
+    type Borrow<'w> = Option<Q::Borrow<'w>>;
+    type Item<'q> = Option<Q::Item<'q>>;
+
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        Some(Q::borrow(world))
+    }
+
+    fn entities<'a>(_: &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        None
+    }
+
+    unsafe fn fetch<'q>(borrow: &'q Self::Borrow<'_>, entity: Entity) -> Option<Self::Item<'q>> {
+        Some(borrow.as_ref().and_then(|borrow| Q::fetch(borrow, entity)))
+    }
+
+    fn access(access: &mut Access) {
+        Q::access(access);
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($Q:ident => $var:ident),+) => {
+        impl<$($Q: Query),+> Query for ($($Q,)+) {
+            type Borrow<'w> = ($($Q::Borrow<'w>,)+);
+            type Item<'q> = ($($Q::Item<'q>,)+);
+
+            fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+                Some(($($Q::borrow(world)?,)+))
+            }
+
+            // Iteration is driven by the smallest storage so only entities that can match are visited
+            fn entities<'a>(borrow: &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+                let ($($var,)+) = borrow;
+                [$($Q::entities($var)),+]
+                    .into_iter()
+                    .flatten()
+                    .min_by_key(|entities| entities.len())
+            }
+
+            unsafe fn fetch<'q>(borrow: &'q Self::Borrow<'_>, entity: Entity) -> Option<Self::Item<'q>> {
+                let ($($var,)+) = borrow;
+                Some(($($Q::fetch($var, entity)?,)+))
             }
-        }
 
-        impl<'a, $($Comp: Component),+> Iterator for $IterName<'a, $($Comp),+> {
-            type Item = (Entity, ( $(&'a $Comp),+, ));
-
-            fn next(&mut self) -> Option<Self::Item> {
-                #[allow(unused_parens)]
-                if let ( $( Some($var) ),+ ) = ( $( self.$var ),+ ) {
-                    while self.index < self.entities_len {
-                        let entity = unsafe { *self.entities_ptr.add(self.index) };
-                        self.index += 1;
-
-                        unsafe {
-                            $( let $var = &* $var; )+
-
-                            if let ( $( Some($var) ),+ ) = ( $( $var.get(entity) ),+ ) {
-                                if let ( $( Some($var) ),+ ) = ( $( $var.downcast_ref() ),+ ) {
-                                    return Some((entity, ( $( $var ),+, )));
-                                }
-                            }
-                        }
-                    }
-                }
-                None
+            fn access(access: &mut Access) {
+                $($Q::access(access);)+
             }
         }
     };
 }
 
-macro_rules! impl_query_mut {
-    ($IterName:ident, $(($Comp:ident => $var:ident)),+) => {
-        pub struct $IterName<'a, $($Comp: Component),+> {
-            entities_ptr: *const Entity,
-            entities_len: usize,
-            $( $var: Option<*mut ComponentStorage<$Comp>>, ) +
-            index: usize,
-            #[allow(unused_parens)]
-            _marker: PhantomData<&'a ( $($Comp),+ )>,
-        }
+impl_query_tuple!(A => a);
+impl_query_tuple!(A => a, B => b);
+impl_query_tuple!(A => a, B => b, C => c);
+impl_query_tuple!(A => a, B => b, C => c, D => d);
+impl_query_tuple!(A => a, B => b, C => c, D => d, E => e);
+impl_query_tuple!(A => a, B => b, C => c, D => d, E => e, F => f);
 
-        impl<'a, $($Comp: Component),+> QueryMut<'a> for ( $(&'a mut $Comp),+, ) {
-            type Item = ( $(&'a mut $Comp),+, );
-            type Iterator = $IterName<'a, $($Comp),+>;
-
-            fn create_query(world: &mut World) -> Self::Iterator {
-                $IterName {
-                    entities_ptr: world.entities.as_ptr(),
-                    entities_len: world.entities.len(),
-                    $( $var: world.get_storage_ptr_mut::<$Comp>(), )+
-                    index: 0,
-                    _marker: PhantomData,
-                }
-            }
+pub struct QueryBorrow<'w, Q: Query> {
+    borrow: Option<Q::Borrow<'w>>,
+    all_entities: &'w [Entity],
+}
+
+impl<'w, Q: Query> QueryBorrow<'w, Q> {
+    pub(crate) fn new(world: &'w World) -> Self {
+        if Access::new().query::<Q>().reads_own_writes() {
+            panic!(
+                "Query {} reads a component it writes, which is already borrowed mutably. \
+                 Filters like `Changed<T>` can't be combined with `&mut T`",
+                std::any::type_name::<Q>()
+            );
+        }
+        Self {
+            borrow: Q::borrow(world),
+            // Skip the null entity
+            all_entities: &world.entities[1..],
         }
+    }
+
+    pub fn iter(&mut self) -> QueryIter<'_, 'w, Q> {
+        let entities = match &self.borrow {
+            Some(borrow) => Q::entities(borrow).unwrap_or(self.all_entities),
+            None => &[],
+        };
+        QueryIter {
+            entities,
+            borrow: self.borrow.as_ref(),
+            index: 0,
+        }
+    }
 
-        impl<'a, $($Comp: Component),+> Iterator for $IterName<'a, $($Comp),+> {
-            type Item = (Entity, ( $(&'a mut $Comp),+, ));
-
-            fn next(&mut self) -> Option<Self::Item> {
-                #[allow(unused_parens)]
-                if let ( $( Some($var) ),+ ) = ( $( self.$var ),+ ) {
-                    while self.index < self.entities_len {
-                        let entity = unsafe { *self.entities_ptr.add(self.index) };
-                        self.index += 1;
-
-                        unsafe {
-                            $( let $var = &mut * $var; )+
-
-                            if let ( $( Some($var) ),+ ) = ( $( $var.get_mut(entity) ),+ ) {
-                                if let ( $( Some($var) ),+ ) = ( $( $var.downcast_mut() ),+ ) {
-                                    return Some((entity, ( $( $var ),+, )));
-                                }
-                            }
-                        }
-                    }
-                }
-                None
+    pub fn get(&mut self, entity: Entity) -> Option<Q::Item<'_>> {
+        unsafe { Q::fetch(self.borrow.as_ref()?, entity) }
+    }
+}
+
+impl<'q, 'w, Q: Query> IntoIterator for &'q mut QueryBorrow<'w, Q> {
+    type Item = (Entity, Q::Item<'q>);
+    type IntoIter = QueryIter<'q, 'w, Q>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct QueryIter<'q, 'w, Q: Query> {
+    borrow: Option<&'q Q::Borrow<'w>>,
+    entities: &'q [Entity],
+    index: usize,
+}
+
+impl<'q, Q: Query> Iterator for QueryIter<'q, '_, Q> {
+    type Item = (Entity, Q::Item<'q>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let borrow = self.borrow?;
+        while let Some(&entity) = self.entities.get(self.index) {
+            self.index += 1;
+            // Every entity appears once in the driving storage, so items never alias
+            if let Some(item) = unsafe { Q::fetch(borrow, entity) } {
+                return Some((entity, item));
             }
         }
-    };
+        None
+    }
 }
-
-// TODO: Handle single component case separately to avoid the tuple overhead
-impl_query!(SingleComponentIter, (A => a));
-impl_query!(DoubleComponentIter, (A => a), (B => b));
-impl_query!(TripleComponentIter, (A => a), (B => b), (C => c));
-impl_query!(FourComponentIter, (A => a), (B => b), (C => c), (D => d));
-impl_query!(FiveComponentIter, (A => a), (B => b), (C => c), (D => d), (E => e));
-impl_query!(SixComponentIter, (A => a), (B => b), (C => c), (D => d), (E => e), (F => f));
-impl_query_mut!(SingleComponentIterMut, (A => a));
-impl_query_mut!(DoubleComponentIterMut, (A => a), (B => b));
-impl_query_mut!(TripleComponentIterMut, (A => a), (B => b), (C => c));
-impl_query_mut!(FourComponentIterMut, (A => a), (B => b), (C => c), (D => d));
-impl_query_mut!(FiveComponentIterMut, (A => a), (B => b), (C => c), (D => d), (E => e));
-impl_query_mut!(SixComponentIterMut, (A => a), (B => b), (C => c), (D => d), (E => e), (F => f));
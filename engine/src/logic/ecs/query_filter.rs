@@ -0,0 +1,130 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use crate::logic::scene::world::World;
+
+use super::{
+    access::Access, borrow_cell::Ref, component::component::Component,
+    component_storage::ComponentStorage, entity::entity::Entity, query::Query,
+};
+
+// Matches entities that have `T` without fetching it
+pub struct With<T: Component>(PhantomData<T>);
+
+// Matches entities that don't have `T`
+pub struct Without<T: Component>(PhantomData<T>);
+
+// Matches entities whose `T` was added since the querying system last ran. Reads the ticks from
+// T's storage, so a query can't also contain `&mut T`: fetching `&mut T` marks it changed anyway.
+// Such queries panic; collect the matching entities first and fetch `&mut T` afterwards
+pub struct Added<T: Component>(PhantomData<T>);
+
+// Matches entities whose `T` was added or mutably accessed since the querying system last ran.
+// Can't be combined with `&mut T` in one query, see `Added`
+pub struct Changed<T: Component>(PhantomData<T>);
+
+impl<T: Component> Query for With<T> {
+    type Borrow<'w> = Ref<'w, ComponentStorage<T>>;
+    type Item<'q> = ();
+
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        world.get_storage::<T>()
+    }
+
+    fn entities<'a>(borrow: &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        Some(borrow.entities())
+    }
+
+    unsafe fn fetch<'q>(borrow: &'q Self::Borrow<'_>, entity: Entity) -> Option<Self::Item<'q>> {
+        borrow.contains(entity).then_some(())
+    }
+
+    fn access(access: &mut Access) {
+        access.add_component_read(TypeId::of::<T>());
+    }
+}
+
+impl<T: Component> Query for Without<T> {
+    type Borrow<'w> = Option<Ref<'w, ComponentStorage<T>>>;
+    type Item<'q> = ();
+
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        Some(world.get_storage::<T>())
+    }
+
+    fn entities<'a>(_: &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        None
+    }
+
+    unsafe fn fetch<'q>(borrow: &'q Self::Borrow<'_>, entity: Entity) -> Option<Self::Item<'q>> {
+        match borrow {
+            Some(storage) if storage.contains(entity) => None,
+            _ => Some(()),
+        }
+    }
+
+    fn access(access: &mut Access) {
+        access.add_component_read(TypeId::of::<T>());
+    }
+}
+
+impl<T: Component> Query for Added<T> {
+    type Borrow<'w> = (Ref<'w, ComponentStorage<T>>, u32, u32);
+    type Item<'q> = ();
+
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        Some((
+            world.get_storage::<T>()?,
+            world.last_change_tick(),
+            world.change_tick(),
+        ))
+    }
+
+    fn entities<'a>((storage, _, _): &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        Some(storage.entities())
+    }
+
+    unsafe fn fetch<'q>(
+        (storage, last_run, this_run): &'q Self::Borrow<'_>,
+        entity: Entity,
+    ) -> Option<Self::Item<'q>> {
+        storage
+            .ticks(entity)?
+            .is_added(*last_run, *this_run)
+            .then_some(())
+    }
+
+    fn access(access: &mut Access) {
+        access.add_component_read(TypeId::of::<T>());
+    }
+}
+
+impl<T: Component> Query for Changed<T> {
+    type Borrow<'w> = (Ref<'w, ComponentStorage<T>>, u32, u32);
+    type Item<'q> = ();
+
+    fn borrow(world: &World) -> Option<Self::Borrow<'_>> {
+        Some((
+            world.get_storage::<T>()?,
+            world.last_change_tick(),
+            world.change_tick(),
+        ))
+    }
+
+    fn entities<'a>((storage, _, _): &'a Self::Borrow<'_>) -> Option<&'a [Entity]> {
+        Some(storage.entities())
+    }
+
+    unsafe fn fetch<'q>(
+        (storage, last_run, this_run): &'q Self::Borrow<'_>,
+        entity: Entity,
+    ) -> Option<Self::Item<'q>> {
+        storage
+            .ticks(entity)?
+            .is_changed(*last_run, *this_run)
+            .then_some(())
+    }
+
+    fn access(access: &mut Access) {
+        access.add_component_read(TypeId::of::<T>());
+    }
+}
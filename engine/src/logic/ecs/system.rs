@@ -1,6 +1,12 @@
+pub mod action_system;
 pub mod collision_system;
 pub mod debug_system;
+pub mod debug_toggle_system;
 pub mod emitter_system;
+pub mod hierarchy_system;
 pub mod movement_system;
 pub mod particle_system;
+pub mod previous_transform_system;
+pub mod schedule;
 pub mod system;
+pub mod transform_propagation_system;
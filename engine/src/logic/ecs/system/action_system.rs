@@ -0,0 +1,25 @@
+use super::system::ParallelSystem;
+use crate::{
+    logic::{ecs::access::Access, scene::world::World},
+    shared::input::{action_map::ActionMap, input_state::InputState},
+};
+
+// Runs first in `PreUpdate`, so every system of a frame sees the same action states
+pub struct ActionSystem;
+
+impl ParallelSystem for ActionSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .read_resource::<InputState>()
+            .write_resource::<ActionMap>()
+    }
+
+    fn run(&mut self, world: &World, _: f32) {
+        if let (Some(input), Some(mut action_map)) = (
+            world.get_resource::<InputState>(),
+            world.get_resource_mut::<ActionMap>(),
+        ) {
+            action_map.update(&input);
+        }
+    }
+}
@@ -1,81 +1,260 @@
-use super::system::System;
+use std::collections::HashSet;
+
+use super::system::ParallelSystem;
 use crate::{
     logic::{
         ecs::{
+            access::Access,
+            broad_phase,
+            collision_event::CollisionEvents,
             collision_result::CollisionResult,
             component::{
                 collider::Collider,
-                force::{AppliedForce, Force, ForceEffect, ForceMode},
+                force::Force,
+                global_transform::GlobalTransform,
+                movement::Movement,
+                parent::Parent,
+                rigid_body::{BodyType, RigidBody},
                 transform::Transform,
             },
             entity::entity::Entity,
+            query_filter::Changed,
         },
         scene::world::World,
     },
-    math::vec3::Vec3,
+    math::{mat4::Mat4, rect::Rect, vec2::Vec2, vec3::Vec3},
 };
 
-const COLLISION_IMPULSE_STRENGTH: f32 = 40.0;
+// Share of the penetration removed per step, and the depth left alone so resting contacts don't
+// jitter
+const CORRECTION_PERCENT: f32 = 0.8;
+const CORRECTION_SLOP: f32 = 0.0005;
 
 pub struct CollisionSystem;
 
-impl System for CollisionSystem {
-    fn run(&mut self, world: &mut World, _: f32) {
-        for (_, (transform, collider)) in world.query_mut::<(&mut Transform, &mut Collider)>() {
-            collider.compute_boundary_points(
-                transform.position.xy() + collider.offset,
-                transform.scale,
-                transform.rotation,
-            );
+struct Body {
+    inverse_mass: f32,
+    velocity: Vec2,
+    restitution: f32,
+    friction: f32,
+}
+
+impl Body {
+    fn of(world: &World, entity: Entity) -> Self {
+        let rigid_body = world
+            .get_component::<RigidBody>(entity)
+            .map(|rigid_body| rigid_body.clone())
+            .unwrap_or_default();
+        let movement = world.get_component::<Movement>(entity);
+        let velocity = match (rigid_body.body_type, &movement) {
+            (BodyType::Static, _) | (_, None) => Vec2::zero(),
+            (_, Some(movement)) => to_world(world, entity, movement.velocity.xy()),
+        };
+        // Only dynamic bodies that can move have a finite mass
+        let inverse_mass = match (rigid_body.body_type, &movement) {
+            (BodyType::Dynamic, Some(_)) => world
+                .get_component::<Force>(entity)
+                .filter(|force| force.mass > 0.0)
+                .map_or(0.0, |force| 1.0 / force.mass),
+            _ => 0.0,
+        };
+        Self {
+            inverse_mass,
+            velocity,
+            restitution: rigid_body.restitution,
+            friction: rigid_body.friction,
         }
+    }
+}
 
-        let snapshot: Vec<(Entity, Transform, Collider)> = world
-            .query::<(&Transform, &Collider)>()
-            .map(|(entity, (transform, collider))| (entity, transform.clone(), collider.clone()))
+// Collisions are resolved in world space, while `Transform` and `Movement` are relative to the
+// parent, so corrections and impulses go through the parent's world transform
+fn parent_matrix(world: &World, entity: Entity) -> Option<Mat4> {
+    let parent = world.get_component::<Parent>(entity)?.parent;
+    let global = world.get_component::<GlobalTransform>(parent)?;
+    Some(Mat4::from_transform(&global.transform))
+}
+
+fn to_world(world: &World, entity: Entity, vector: Vec2) -> Vec2 {
+    parent_matrix(world, entity).map_or(vector, |matrix| {
+        matrix.transform_vector(Vec3::from_vec2(vector, 0.0)).xy()
+    })
+}
+
+fn to_local(world: &World, entity: Entity, vector: Vec2) -> Vec2 {
+    parent_matrix(world, entity)
+        .and_then(|matrix| matrix.inverse())
+        .map_or(vector, |inverse| {
+            inverse.transform_vector(Vec3::from_vec2(vector, 0.0)).xy()
+        })
+}
+
+impl CollisionSystem {
+    // Pushes the bodies apart by their inverse masses, then applies an impulse along the normal and
+    // a friction impulse along the contact
+    fn resolve(world: &World, collision: &CollisionResult) {
+        let a = Body::of(world, collision.entity_a);
+        let b = Body::of(world, collision.entity_b);
+        let inverse_mass_sum = a.inverse_mass + b.inverse_mass;
+        if inverse_mass_sum <= 0.0 {
+            return;
+        }
+        let normal = collision.collision_normal;
+
+        let correction = normal
+            * ((collision.penetration_depth - CORRECTION_SLOP).max(0.0) / inverse_mass_sum
+                * CORRECTION_PERCENT);
+        Self::move_by(world, collision.entity_a, correction * a.inverse_mass);
+        Self::move_by(world, collision.entity_b, correction * -b.inverse_mass);
+
+        // The normal points from b to a, so a positive speed means they already separate
+        let relative_velocity = a.velocity - b.velocity;
+        let normal_speed = Vec2::dot(&relative_velocity, &normal);
+        if normal_speed > 0.0 {
+            return;
+        }
+        let restitution = a.restitution.max(b.restitution);
+        let normal_impulse = -(1.0 + restitution) * normal_speed / inverse_mass_sum;
+        let mut impulse = normal * normal_impulse;
+
+        let tangent_velocity = relative_velocity - normal * normal_speed;
+        if tangent_velocity.length() > 0.0 {
+            let tangent = tangent_velocity.normalized();
+            let friction = (a.friction * b.friction).sqrt();
+            // Coulomb friction can't exceed the normal impulse times the friction coefficient
+            let tangent_impulse = (-Vec2::dot(&relative_velocity, &tangent) / inverse_mass_sum)
+                .clamp(-normal_impulse * friction, normal_impulse * friction);
+            impulse += tangent * tangent_impulse;
+        }
+        Self::accelerate(world, collision.entity_a, impulse * a.inverse_mass);
+        Self::accelerate(world, collision.entity_b, impulse * -b.inverse_mass);
+    }
+
+    fn move_by(world: &World, entity: Entity, offset: Vec2) {
+        if offset.length() > 0.0 {
+            let offset = to_local(world, entity, offset);
+            if let Some(mut transform) = world.get_component_mut::<Transform>(entity) {
+                transform.position += Vec3::from_vec2(offset, 0.0);
+            }
+        }
+    }
+
+    fn accelerate(world: &World, entity: Entity, velocity: Vec2) {
+        if velocity.length() > 0.0 {
+            let velocity = to_local(world, entity, velocity);
+            if let Some(mut movement) = world.get_component_mut::<Movement>(entity) {
+                movement.velocity += Vec3::from_vec2(velocity, 0.0);
+            }
+        }
+    }
+
+    // Only pairs whose bounds overlap and whose layers match reach `Collider::intersect`
+    pub fn detect_collisions(colliders: &[(Entity, Transform, Collider)]) -> Vec<CollisionResult> {
+        let bounds: Vec<Rect> = colliders
+            .iter()
+            .map(|(_, transform, collider)| {
+                collider.bounds(
+                    transform.position.xy() + collider.offset,
+                    transform.scale,
+                    transform.rotation,
+                )
+            })
             .collect();
 
         let mut collisions: Vec<CollisionResult> = vec![];
-        for i in 0..snapshot.len() {
-            for j in (i + 1)..snapshot.len() {
-                let (entity_a, transform_a, collider_a) = &snapshot[i];
-                let (entity_b, transform_b, collider_b) = &snapshot[j];
-                if let Some((penetration_depth, normal)) = collider_a.intersect(
-                    transform_a.position.xy().clone() + collider_a.offset,
+        for (i, j) in broad_phase::sweep_and_prune(&bounds)
+            .into_iter()
+            .filter(|&(i, j)| colliders[i].2.collides_with(&colliders[j].2))
+        {
+            let (entity_a, transform_a, collider_a) = &colliders[i];
+            let (entity_b, transform_b, collider_b) = &colliders[j];
+            if let Some((penetration_depth, normal)) = collider_a.intersect(
+                (
+                    transform_a.position.xy() + collider_a.offset,
                     transform_a.scale,
                     transform_a.rotation,
-                    collider_b,
-                    transform_b.position.xy().clone() + collider_b.offset,
+                ),
+                collider_b,
+                (
+                    transform_b.position.xy() + collider_b.offset,
                     transform_b.scale,
                     transform_b.rotation,
-                ) {
-                    collisions.push(CollisionResult {
-                        entity_a: *entity_a,
-                        entity_b: *entity_b,
-                        collision_normal: normal,
-                        penetration_depth: penetration_depth,
-                    });
-                }
+                ),
+            ) {
+                collisions.push(CollisionResult {
+                    entity_a: *entity_a,
+                    entity_b: *entity_b,
+                    collision_normal: normal,
+                    penetration_depth,
+                });
             }
         }
-        // TODO: isstatic?
-        for collision in collisions {
-            let push = collision.collision_normal
-                * collision.penetration_depth
-                * COLLISION_IMPULSE_STRENGTH;
-            if let Some(force) = world.get_component_mut::<Force>(collision.entity_a) {
-                force.apply_force(AppliedForce {
-                    id: format!("collision_{:?}", collision.entity_b),
-                    effect: ForceEffect::Linear(Vec3::from_vec2(push, 0.0)),
-                    mode: ForceMode::Impulse,
-                });
+        collisions
+    }
+}
+
+impl ParallelSystem for CollisionSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .query::<(&mut Collider, &GlobalTransform)>()
+            .read::<RigidBody>()
+            .read::<Force>()
+            .read::<Parent>()
+            .write::<Movement>()
+            .write::<Transform>()
+            .write_resource::<CollisionEvents>()
+    }
+
+    fn run(&mut self, world: &World, _: f32) {
+        // Colliders are placed by their world transform. Only colliders that moved or were added/modified since the last run need new boundary points
+        let mut dirty: Vec<Entity> = world
+            .query::<(&Collider, Changed<GlobalTransform>)>()
+            .iter()
+            .chain(world.query::<(&Collider, Changed<Collider>)>().iter())
+            .map(|(entity, _)| entity)
+            .collect();
+        dirty.sort_by_key(|entity| entity.id);
+        dirty.dedup();
+        let mut colliders = world.query::<(&GlobalTransform, &mut Collider)>();
+        for entity in dirty {
+            if let Some((global, collider)) = colliders.get(entity) {
+                let transform = &global.transform;
+                collider.compute_boundary_points(
+                    transform.position.xy() + collider.offset,
+                    transform.scale,
+                    transform.rotation,
+                );
             }
-            if let Some(force) = world.get_component_mut::<Force>(collision.entity_b) {
-                force.apply_force(AppliedForce {
-                    id: format!("collision_{:?}", collision.entity_a),
-                    effect: ForceEffect::Linear(Vec3::from_vec2(-push, 0.0)),
-                    mode: ForceMode::Impulse,
-                });
+        }
+        drop(colliders);
+
+        let snapshot: Vec<(Entity, Transform, Collider)> = world
+            .query::<(&GlobalTransform, &Collider)>()
+            .iter()
+            .map(|(entity, (global, collider))| {
+                (entity, global.transform.clone(), collider.clone())
+            })
+            .collect();
+
+        let sensors: HashSet<Entity> = snapshot
+            .iter()
+            .filter(|(_, _, collider)| collider.is_sensor)
+            .map(|(entity, _, _)| *entity)
+            .collect();
+        let collisions = Self::detect_collisions(&snapshot);
+        for collision in &collisions {
+            if !sensors.contains(&collision.entity_a) && !sensors.contains(&collision.entity_b) {
+                Self::resolve(world, collision);
             }
         }
+        if let Some(mut events) = world.get_resource_mut::<CollisionEvents>() {
+            events.record(collisions.iter().map(|collision| {
+                (
+                    collision.entity_a,
+                    collision.entity_b,
+                    sensors.contains(&collision.entity_a) || sensors.contains(&collision.entity_b),
+                )
+            }));
+        }
     }
 }
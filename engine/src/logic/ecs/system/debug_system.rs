@@ -1,66 +1,55 @@
-use super::system::System;
+use super::system::ParallelSystem;
 use crate::{
     logic::{
-        ecs::component::collider::{Collider, ColliderShape},
-        scene::{debug_config::DebugConfig, world::World},
+        ecs::{
+            access::Access,
+            component::collider::{Collider, ColliderShape},
+        },
+        scene::world::World,
     },
     render::{
-        model::wireframe,
+        model::wireframe::{self, Wireframe},
         resource::resource_manager::{ColliderShapeKey, ResourceManager},
     },
-    shared::input::input_state::InputState,
 };
 use std::sync::Arc;
-use winit::keyboard::{Key, NamedKey};
 
-pub struct DebugSystem {
-    was_pressed: bool,
-}
+// Builds wireframe meshes for colliders. Scheduled to run only while debug rendering is enabled
+pub struct DebugSystem;
 
 impl DebugSystem {
-    pub fn new() -> Self {
-        Self { was_pressed: false }
-    }
-}
-
-impl System for DebugSystem {
-    fn run(&mut self, world: &mut World, _: f32) {
-        if let Some(input) = world.get_resource::<InputState>() {
-            let pressed = input.is_pressed(Key::Named(NamedKey::F3));
-            if pressed && !self.was_pressed {
-                if let Some(config) = world.get_resource_mut::<DebugConfig>() {
-                    config.enabled = !config.enabled;
-                    log::info!(
-                        "Debug rendering {}",
-                        match config.enabled {
-                            true => "enabled",
-                            false => "disabled",
-                        }
-                    );
-                }
+    fn wireframe(shape: &ColliderShape) -> Wireframe {
+        match shape {
+            ColliderShape::Capsule2D { width, height } => wireframe::capsule(*width, *height, 16),
+            ColliderShape::Rect { width, height } => wireframe::rectangle(*width, *height),
+            ColliderShape::Circle { radius } => wireframe::circle(*radius, 32),
+            ColliderShape::Polygon { points } => wireframe::polygon(points),
+            ColliderShape::Compound { parts } => wireframe::merged(
+                parts
+                    .iter()
+                    .map(|part| (part.offset, Self::wireframe(&part.shape)))
+                    .collect(),
+            ),
+            ColliderShape::Heightfield { width, heights } => {
+                wireframe::heightfield(*width, heights)
             }
-            self.was_pressed = pressed;
         }
+    }
+}
 
-        let enabled = world
-            .get_resource::<DebugConfig>()
-            .map_or(false, |c| c.enabled);
-        if !enabled {
-            return;
-        }
+impl ParallelSystem for DebugSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .read::<Collider>()
+            .write_resource::<ResourceManager>()
+    }
 
-        for (_, (collider,)) in world.query::<(&Collider,)>() {
-            if let Some(resource_manager) = world.get_resource_mut::<ResourceManager>() {
+    fn run(&mut self, world: &World, _: f32) {
+        for (_, (collider,)) in world.query::<(&Collider,)>().iter() {
+            if let Some(mut resource_manager) = world.get_resource_mut::<ResourceManager>() {
                 let key = ColliderShapeKey::from_shape(&collider.shape);
                 if resource_manager.get_collider_mesh(key.clone()).is_none() {
-                    let (vertices, indices, uvs) = match collider.shape {
-                        ColliderShape::Capsule2D { width, height } => {
-                            wireframe::capsule(width, height, 16)
-                        }
-                        ColliderShape::Rect { width, height } => {
-                            wireframe::rectangle(width, height)
-                        }
-                    };
+                    let (vertices, indices, uvs) = Self::wireframe(&collider.shape);
                     let mesh = resource_manager.load_mesh(vertices, indices, uvs);
                     resource_manager.save_collider_mesh(
                         key,
@@ -0,0 +1,35 @@
+use super::system::System;
+use crate::{
+    logic::scene::{debug_config::DebugConfig, world::World},
+    shared::input::action_map::ActionMap,
+};
+
+// Toggles debug rendering on the "toggle_debug" action, bound to F3 by default
+#[derive(Default)]
+pub struct DebugToggleSystem;
+
+impl DebugToggleSystem {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl System for DebugToggleSystem {
+    fn run(&mut self, world: &mut World, _: f32) {
+        let toggled = world
+            .get_resource::<ActionMap>()
+            .is_some_and(|actions| actions.just_pressed("toggle_debug"));
+        if toggled {
+            if let Some(mut config) = world.get_resource_mut::<DebugConfig>() {
+                config.enabled = !config.enabled;
+                log::info!(
+                    "Debug rendering {}",
+                    match config.enabled {
+                        true => "enabled",
+                        false => "disabled",
+                    }
+                );
+            }
+        }
+    }
+}
@@ -1,20 +1,30 @@
-use super::system::System;
+use super::system::ParallelSystem;
 use crate::logic::{
     ecs::{
+        access::Access,
         component::{emitter::Emitter, model::Model},
         entity::particle_entity::ParticleEntity,
     },
-    scene::{focus_point::FocusPoint, world::World},
+    scene::{focus_point::FocusPoint, time::Time, world::World},
 };
 use rand::rngs::StdRng;
 
 pub struct EmitterSystem;
 
-impl System for EmitterSystem {
-    fn run(&mut self, world: &mut World, delta_time: f32) {
-        let mut removeables = vec![];
-        let rng: *mut StdRng = world.expect_resource_ptr::<StdRng>();
-        for (entity, (emitter, model)) in world.query_mut::<(&mut Emitter, &mut Model)>() {
+impl ParallelSystem for EmitterSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .query::<(&mut Emitter, &Model)>()
+            .write_resource::<StdRng>()
+            .read_resource::<FocusPoint>()
+            .read_resource::<Time>()
+    }
+
+    fn run(&mut self, world: &World, delta_time: f32) {
+        let mut commands = world.commands();
+        let mut rng = world.expect_resource_mut::<StdRng>();
+        let now = world.expect_resource::<Time>().elapsed();
+        for (entity, (emitter, model)) in world.query::<(&mut Emitter, &Model)>().iter() {
             emitter.cycle_time += delta_time;
             emitter.now += delta_time;
             let should_spawn = match emitter.lifespan {
@@ -24,9 +34,9 @@ impl System for EmitterSystem {
             let mut has_loaded = should_spawn;
             let focus_point = world.get_resource::<FocusPoint>();
             emitter.particles.retain_mut(|particle| {
-                particle.update(delta_time);
+                particle.update(delta_time, now);
                 if !has_loaded {
-                    if let Some(focus_point) = focus_point {
+                    if let Some(focus_point) = &focus_point {
                         if particle.position.distance(focus_point.0)
                             <= emitter.cull_radius.unwrap_or(f32::INFINITY)
                         {
@@ -43,10 +53,11 @@ impl System for EmitterSystem {
                         emitter.particle_config.clone(),
                         emitter.spawn_position,
                         model.clone(),
-                        unsafe { &mut *rng },
+                        &mut rng,
+                        now,
                     );
 
-                    particle.update(i as f32 * emitter.interval.as_secs_f32());
+                    particle.update(i as f32 * emitter.interval.as_secs_f32(), now);
                     emitter.particles.push(particle);
                 }
                 emitter.cycle_time -= count.floor() * emitter.interval.as_secs_f32();
@@ -61,11 +72,8 @@ impl System for EmitterSystem {
             }
             if !should_spawn && emitter.particles.is_empty() {
                 emitter.alive = false;
-                removeables.push(entity);
+                commands.delete_entity(entity);
             }
         }
-        for entity in removeables {
-            world.delete_entity(entity);
-        }
     }
 }
@@ -0,0 +1,18 @@
+use super::system::System;
+use crate::logic::{
+    ecs::{component::parent::Parent, query_filter::Changed},
+    scene::{hierarchy, world::World},
+};
+
+// Brings `Children` up to date with parents that were changed in place. Inserting or removing a
+// `Parent` already updates them
+pub struct HierarchySystem;
+
+impl System for HierarchySystem {
+    fn run(&mut self, world: &mut World, _: f32) {
+        let changed = world.query::<(Changed<Parent>,)>().iter().next().is_some();
+        if changed {
+            hierarchy::sync_children(world);
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use super::system::System;
+use super::system::ParallelSystem;
 use crate::{
     logic::{
-        ecs::component::{force::Force, movement::Movement, transform::Transform},
+        ecs::{
+            access::Access,
+            component::{force::Force, movement::Movement, transform::Transform},
+        },
         scene::world::World,
     },
     math::vec3::Vec3,
@@ -9,10 +12,15 @@ use crate::{
 
 pub struct MovementSystem;
 
-impl System for MovementSystem {
-    fn run(&mut self, world: &mut World, delta_time: f32) {
-        for (_, (movement, force, transform)) in
-            world.query_mut::<(&mut Movement, &mut Force, &mut Transform)>()
+impl ParallelSystem for MovementSystem {
+    fn access(&self) -> Access {
+        Access::new().query::<(&mut Movement, &mut Force, &mut Transform)>()
+    }
+
+    fn run(&mut self, world: &World, delta_time: f32) {
+        for (_, (movement, force, transform)) in world
+            .query::<(&mut Movement, &mut Force, &mut Transform)>()
+            .iter()
         {
             let net_force: Vec3 = force
                 .get_linear_force_vecs()
@@ -1,44 +1,43 @@
-use super::system::System;
+use super::system::ParallelSystem;
 use crate::{
     logic::{
         ecs::{
-            component::{emitter::Emitter, parent::Parent, transform::Transform},
-            entity::entity::Entity,
+            access::Access,
+            component::{
+                emitter::Emitter, global_transform::GlobalTransform, parent::Parent,
+                transform::Transform,
+            },
         },
         scene::world::World,
     },
-    math::{transformation::get_world_transform, vec3::Vec3},
+    math::vec3::Vec3,
 };
 pub struct ParticleSystem;
 
-impl System for ParticleSystem {
-    fn run(&mut self, world: &mut World, _: f32) {
-        let transform_storage = world.get_storage_ptr_mut::<Transform>();
+impl ParallelSystem for ParticleSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .query::<(&mut Emitter, &Parent)>()
+            .read::<Transform>()
+            .read::<GlobalTransform>()
+    }
+
+    fn run(&mut self, world: &World, _: f32) {
         for (entity, (emitter_component, parent_component)) in
-            world.query_mut::<(&mut Emitter, &mut Parent)>()
+            world.query::<(&mut Emitter, &Parent)>().iter()
         {
-            if parent_component.parent == Entity(0).into() {
+            if parent_component.parent.is_null() {
                 continue;
             }
             let mut emitter_offset = Vec3::zero();
-            if let Some(transform_storage) =
-                transform_storage.map(|transform_storage| unsafe { &mut *transform_storage })
-            {
-                if let Some(Some(own_transform)) = transform_storage
-                    .get(entity)
-                    .map(|e| e.downcast_ref::<Transform>())
-                {
-                    emitter_offset = own_transform.position;
-                }
+            if let Some(own_transform) = world.get_component::<Transform>(entity) {
+                emitter_offset = own_transform.position;
             }
 
-            let parent_world = get_world_transform(
-                parent_component.parent,
-                &|e| world.get_component::<Transform>(e).cloned(),
-                &|e| world.get_component::<Parent>(e).cloned(),
-            );
-
-            if let Some(parent_world) = parent_world {
+            if let Some(parent_global) =
+                world.get_component::<GlobalTransform>(parent_component.parent)
+            {
+                let parent_world = &parent_global.transform;
                 let rot = parent_world.rotation;
                 let is_flipped = parent_world.is_flipped;
                 let flip_mul = if is_flipped { -1.0f32 } else { 1.0f32 };
@@ -0,0 +1,25 @@
+use super::system::ParallelSystem;
+use crate::logic::{
+    ecs::{
+        access::Access,
+        component::{previous_transform::PreviousTransform, transform::Transform},
+    },
+    scene::world::World,
+};
+
+// Runs first in every fixed step, so `PreviousTransform` holds the state the step started from
+pub struct PreviousTransformSystem;
+
+impl ParallelSystem for PreviousTransformSystem {
+    fn access(&self) -> Access {
+        Access::new().query::<(&Transform, &mut PreviousTransform)>()
+    }
+
+    fn run(&mut self, world: &World, _: f32) {
+        for (_, (transform, previous)) in
+            world.query::<(&Transform, &mut PreviousTransform)>().iter()
+        {
+            previous.0 = Some(transform.clone());
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    logic::{ecs::access::Access, scene::world::World},
+    shared::engine_error::EngineError,
+};
+
+use super::system::{ParallelSystem, System};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stage {
+    PreUpdate,
+    // Runs zero or more times per frame with a constant delta time, see `FixedTime`
+    FixedUpdate,
+    Update,
+    PostUpdate,
+    // Runs right before the extractors, once per extracted frame
+    Extract,
+}
+
+// Serial runs the systems of a batch one after another on the calling thread instead of on the
+// thread pool. Both modes apply commands and hand out entity ids the same way, so a schedule gives
+// the same results in either
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutorMode {
+    #[default]
+    Parallel,
+    Serial,
+}
+
+pub type RunCondition = Box<dyn Fn(&World) -> bool + Send + Sync>;
+
+enum SystemKind {
+    Exclusive(Box<dyn System>),
+    Parallel(Box<dyn ParallelSystem>, Access),
+}
+
+pub struct SystemConfig {
+    name: String,
+    system: SystemKind,
+    stage: Stage,
+    before: Vec<String>,
+    after: Vec<String>,
+    run_condition: Option<RunCondition>,
+}
+
+impl SystemConfig {
+    pub fn new(name: impl Into<String>, system: impl System + 'static) -> Self {
+        Self::with_kind(name.into(), SystemKind::Exclusive(Box::new(system)))
+    }
+
+    pub fn parallel(name: impl Into<String>, system: impl ParallelSystem + 'static) -> Self {
+        let access = system.access();
+        Self::with_kind(name.into(), SystemKind::Parallel(Box::new(system), access))
+    }
+
+    fn with_kind(name: String, system: SystemKind) -> Self {
+        Self {
+            name,
+            system,
+            stage: Stage::Update,
+            before: Vec::new(),
+            after: Vec::new(),
+            run_condition: None,
+        }
+    }
+
+    pub fn in_stage(mut self, stage: Stage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    // Constraints refer to systems of the same stage by name
+    pub fn before(mut self, name: impl Into<String>) -> Self {
+        self.before.push(name.into());
+        self
+    }
+
+    pub fn after(mut self, name: impl Into<String>) -> Self {
+        self.after.push(name.into());
+        self
+    }
+
+    // The system is skipped (and doesn't count as having run) while the condition is false
+    pub fn run_if(mut self, condition: impl Fn(&World) -> bool + Send + Sync + 'static) -> Self {
+        self.run_condition = Some(Box::new(condition));
+        self
+    }
+}
+
+struct ScheduledSystem {
+    config: SystemConfig,
+    // Change tick at which the system last ran, for `Changed` and `Added` queries
+    last_run: u32,
+}
+
+impl ScheduledSystem {
+    fn run(&mut self, world: &mut World, delta_time: f32) {
+        world.set_last_change_tick(self.last_run);
+        self.last_run = world.increment_change_tick();
+        match &mut self.config.system {
+            SystemKind::Exclusive(system) => system.run(world, delta_time),
+            SystemKind::Parallel(system, _) => system.run(world, delta_time),
+        }
+        world.apply_commands();
+    }
+
+    fn access(&self) -> Option<&Access> {
+        match &self.config.system {
+            SystemKind::Exclusive(_) => None,
+            SystemKind::Parallel(_, access) => Some(access),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Schedule {
+    systems: Vec<ScheduledSystem>,
+    // Per stage, batches of indices into `systems` in run order. Systems of a batch don't
+    // conflict and aren't ordered against each other. None until built
+    order: Option<HashMap<Stage, Vec<Vec<usize>>>>,
+    mode: ExecutorMode,
+}
+
+impl Schedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_executor_mode(&mut self, mode: ExecutorMode) {
+        self.mode = mode;
+    }
+
+    pub fn executor_mode(&self) -> ExecutorMode {
+        self.mode
+    }
+
+    pub fn add_system(&mut self, config: SystemConfig) {
+        self.systems.push(ScheduledSystem {
+            config,
+            last_run: 0,
+        });
+        self.order = None;
+    }
+
+    pub fn remove_system(&mut self, name: &str) -> bool {
+        let len = self.systems.len();
+        self.systems.retain(|system| system.config.name != name);
+        self.order = None;
+        self.systems.len() != len
+    }
+
+    pub fn clear(&mut self) {
+        self.systems.clear();
+        self.order = None;
+    }
+
+    // Resolves `before`/`after` constraints. Unconstrained systems keep their registration order
+    pub fn build(&mut self) -> Result<(), EngineError> {
+        let mut order = HashMap::new();
+        for stage in [
+            Stage::PreUpdate,
+            Stage::FixedUpdate,
+            Stage::Update,
+            Stage::PostUpdate,
+            Stage::Extract,
+        ] {
+            order.insert(stage, self.sort_stage(stage)?);
+        }
+        self.order = Some(order);
+        Ok(())
+    }
+
+    // System names of a stage in run order
+    pub fn system_names(&mut self, stage: Stage) -> Result<Vec<&str>, EngineError> {
+        if self.order.is_none() {
+            self.build()?;
+        }
+        let order = &self.order.as_ref().unwrap()[&stage];
+        Ok(order
+            .iter()
+            .flatten()
+            .map(|&index| self.systems[index].config.name.as_str())
+            .collect())
+    }
+
+    // System names of a stage grouped by the batches the parallel executor runs together
+    pub fn batches(&mut self, stage: Stage) -> Result<Vec<Vec<&str>>, EngineError> {
+        if self.order.is_none() {
+            self.build()?;
+        }
+        let order = &self.order.as_ref().unwrap()[&stage];
+        Ok(order
+            .iter()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .map(|&index| self.systems[index].config.name.as_str())
+                    .collect()
+            })
+            .collect())
+    }
+
+    pub fn run_stage(&mut self, stage: Stage, world: &mut World, delta_time: f32) {
+        if self.order.is_none() {
+            if let Err(err) = self.build() {
+                panic!("Invalid system schedule: {}", err);
+            }
+        }
+        for batch in &self.order.as_ref().unwrap()[&stage] {
+            let mut ready = Vec::with_capacity(batch.len());
+            for &index in batch {
+                if let Some(condition) = &self.systems[index].config.run_condition {
+                    if !condition(world) {
+                        continue;
+                    }
+                }
+                ready.push(index);
+            }
+            // Commands are applied once the whole batch has run, in either mode
+            match ready[..] {
+                [] => continue,
+                [index] => {
+                    self.systems[index].run(world, delta_time);
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Batches with more than one system only contain parallel systems
+            let mut jobs = Vec::with_capacity(ready.len());
+            for (index, scheduled) in self.systems.iter_mut().enumerate() {
+                if !ready.contains(&index) {
+                    continue;
+                }
+                let last_run = scheduled.last_run;
+                scheduled.last_run = world.increment_change_tick();
+                if let SystemKind::Parallel(system, _) = &mut scheduled.config.system {
+                    jobs.push((system, last_run, scheduled.last_run));
+                }
+            }
+            let shared: &World = world;
+            let lanes = jobs.len();
+            let run = |(lane, (system, last_run, this_run)): (
+                usize,
+                (&mut Box<dyn ParallelSystem>, u32, u32),
+            )| {
+                World::with_system_ticks(last_run, this_run, || {
+                    shared.with_system_commands(lane, lanes, || system.run(shared, delta_time))
+                })
+                .1
+            };
+            let recorded = match self.mode {
+                ExecutorMode::Parallel => jobs.into_par_iter().enumerate().map(run).collect(),
+                ExecutorMode::Serial => jobs.into_iter().enumerate().map(run).collect(),
+            };
+            world.apply_batch(recorded);
+        }
+    }
+
+    fn sort_stage(&self, stage: Stage) -> Result<Vec<Vec<usize>>, EngineError> {
+        let nodes: Vec<usize> = (0..self.systems.len())
+            .filter(|&index| self.systems[index].config.stage == stage)
+            .collect();
+        let mut position = HashMap::new();
+        for (node, &index) in nodes.iter().enumerate() {
+            let name = &self.systems[index].config.name;
+            if position.insert(name.as_str(), node).is_some() {
+                return Err(EngineError::DuplicateSystem(name.clone()));
+            }
+        }
+
+        // edges[a] contains b when a must run before b
+        let mut edges = vec![Vec::new(); nodes.len()];
+        for (node, &index) in nodes.iter().enumerate() {
+            let config = &self.systems[index].config;
+            let lookup = |other: &String| {
+                position
+                    .get(other.as_str())
+                    .copied()
+                    .ok_or_else(|| EngineError::UnknownSystem(config.name.clone(), other.clone()))
+            };
+            for other in &config.before {
+                edges[node].push(lookup(other)?);
+            }
+            for other in &config.after {
+                edges[lookup(other)?].push(node);
+            }
+        }
+
+        let mut in_degree = vec![0; nodes.len()];
+        for &next in edges.iter().flatten() {
+            in_degree[next] += 1;
+        }
+        // Always pick the earliest registered ready system so the order is stable
+        let mut sorted = Vec::with_capacity(nodes.len());
+        let mut done = vec![false; nodes.len()];
+        while let Some(node) = (0..nodes.len()).find(|&node| !done[node] && in_degree[node] == 0) {
+            done[node] = true;
+            sorted.push(nodes[node]);
+            for &next in &edges[node] {
+                in_degree[next] -= 1;
+            }
+        }
+        if sorted.len() < nodes.len() {
+            let cycle = Self::find_cycle(&edges, &done)
+                .into_iter()
+                .map(|node| self.systems[nodes[node]].config.name.clone())
+                .collect();
+            return Err(EngineError::SystemCycle(cycle));
+        }
+        Ok(self.batch(&nodes, &edges, sorted))
+    }
+
+    // Greedily groups consecutive systems that may run at the same time. A system joins the
+    // current batch when it is parallel, compatible with every system in it and not ordered
+    // after any of them; otherwise it starts the next batch
+    fn batch(&self, nodes: &[usize], edges: &[Vec<usize>], sorted: Vec<usize>) -> Vec<Vec<usize>> {
+        let node_of = |index: usize| nodes.iter().position(|&other| other == index).unwrap();
+        let mut batches: Vec<Vec<usize>> = Vec::new();
+        for index in sorted {
+            let joins_last = batches.last().is_some_and(|batch| {
+                let Some(access) = self.systems[index].access() else {
+                    return false;
+                };
+                batch.iter().all(|&other| {
+                    self.systems[other]
+                        .access()
+                        .is_some_and(|other_access| access.is_compatible(other_access))
+                        && !edges[node_of(other)].contains(&node_of(index))
+                })
+            });
+            match batches.last_mut() {
+                Some(batch) if joins_last => batch.push(index),
+                _ => batches.push(vec![index]),
+            }
+        }
+        batches
+    }
+
+    // Every system left after sorting waits on another one that is left, so walking those
+    // dependencies backwards must eventually revisit a system
+    fn find_cycle(edges: &[Vec<usize>], done: &[bool]) -> Vec<usize> {
+        let mut path = vec![(0..done.len()).find(|&node| !done[node]).unwrap()];
+        loop {
+            let current = *path.last().unwrap();
+            let previous = (0..edges.len())
+                .find(|&node| !done[node] && edges[node].contains(&current))
+                .unwrap();
+            if let Some(start) = path.iter().position(|&node| node == previous) {
+                let mut cycle = path.split_off(start);
+                cycle.reverse();
+                // Start from the earliest registered system so the report doesn't depend on the walk
+                let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+                cycle.rotate_left(first);
+                cycle.push(cycle[0]);
+                return cycle;
+            }
+            path.push(previous);
+        }
+    }
+}
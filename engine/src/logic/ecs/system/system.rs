@@ -1,5 +1,13 @@
-use crate::logic::scene::world::World;
+use crate::logic::{ecs::access::Access, scene::world::World};
 
 pub trait System: Send + Sync {
     fn run(&mut self, world: &mut World, delta_time: f32);
 }
+
+// A system that only needs shared access to the world, making structural changes through
+// `Commands`. Systems whose declared access doesn't conflict may run at the same time
+pub trait ParallelSystem: Send + Sync {
+    fn access(&self) -> Access;
+
+    fn run(&mut self, world: &World, delta_time: f32);
+}
@@ -0,0 +1,88 @@
+use super::system::System;
+use crate::{
+    logic::{
+        ecs::{
+            component::{
+                children::Children, global_transform::GlobalTransform, parent::Parent,
+                previous_transform::PreviousTransform, transform::Transform,
+            },
+            entity::entity::Entity,
+            query_filter::{Changed, With, Without},
+        },
+        scene::{hierarchy, world::World},
+    },
+    math::transformation::calc_inherited_transform,
+};
+
+// Writes `GlobalTransform` for every entity with a `Transform`, walking down from the roots so
+// parents are always done before their children. Entities in a parent cycle are never reached
+// and keep the last value they had
+pub struct TransformPropagationSystem;
+
+impl System for TransformPropagationSystem {
+    fn run(&mut self, world: &mut World, _: f32) {
+        // Children are walked below, so they have to match the parents first
+        if world.query::<(Changed<Parent>,)>().iter().next().is_some() {
+            hierarchy::sync_children(world);
+        }
+        let added: Vec<Entity> = world
+            .query::<(With<Transform>, Without<GlobalTransform>)>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in added {
+            world.add_component(entity, GlobalTransform::default());
+        }
+        let removed: Vec<Entity> = world
+            .query::<(With<GlobalTransform>, Without<Transform>)>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in removed {
+            world.remove_component::<GlobalTransform>(entity);
+        }
+
+        // A parent without a transform, including a null one, starts a new root
+        let mut stack: Vec<(Entity, Option<GlobalTransform>)> = world
+            .query::<(&Transform, Option<&Parent>)>()
+            .iter()
+            .filter(|(_, (_, parent))| {
+                parent
+                    .is_none_or(|parent| world.get_component::<Transform>(parent.parent).is_none())
+            })
+            .map(|(entity, _)| (entity, None))
+            .collect();
+        while let Some((entity, parent)) = stack.pop() {
+            let Some(transform) = world.get_component::<Transform>(entity).as_deref().cloned()
+            else {
+                continue;
+            };
+            let previous = world
+                .get_component::<PreviousTransform>(entity)
+                .and_then(|previous| previous.0.clone())
+                .unwrap_or_else(|| transform.clone());
+            let global = GlobalTransform {
+                transform: calc_inherited_transform(
+                    &transform,
+                    parent.as_ref().map(|parent| &parent.transform),
+                ),
+                previous: calc_inherited_transform(
+                    &previous,
+                    parent.as_ref().map(|parent| &parent.previous),
+                ),
+            };
+            if let Some(children) = world.get_component::<Children>(entity) {
+                stack.extend(children.iter().map(|child| (child, Some(global.clone()))));
+            }
+            // Written only when it moved, so `Changed<GlobalTransform>` stays meaningful
+            let unchanged = world
+                .get_component::<GlobalTransform>(entity)
+                .is_some_and(|current| *current == global);
+            if !unchanged {
+                if let Some(mut current) = world.get_component_mut::<GlobalTransform>(entity) {
+                    *current = global;
+                }
+            }
+        }
+    }
+}
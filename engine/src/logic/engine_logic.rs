@@ -1,44 +1,143 @@
 use std::time::{Duration, Instant};
 
 use flume::{Receiver, Sender};
+use include_assets::{include_dir, NamedArchive};
 use rand::{rngs::StdRng, SeedableRng};
 
 use crate::{
-    logic::scene::scene::Scene,
+    engine_config,
+    logic::scene::{scene::Scene, time::Time},
+    render::resource::{resource_manager::ResourceManager, resource_provider::ResourceProvider},
     shared::{
+        engine_error::EngineError,
         extracted_frame::ExtractedFrame,
-        input::{input_event::InputEvent, input_handler::InputHandler},
+        input::{
+            action_map::ActionMap,
+            input_event::InputEvent,
+            input_handler::InputHandler,
+            input_recording::{InputRecord, InputRecorder, InputRecording, InputReplay},
+        },
     },
 };
 
 const TARGET_INTERVAL: Duration = Duration::from_micros(16666);
 
+// Sets up the scene the same way with or without a window
+pub(crate) fn init_scene(
+    mut resource_manager: ResourceManager,
+    seed: u64,
+    mut on_init: impl FnMut(&mut Scene, &mut ResourceManager),
+) -> Scene {
+    let mut scene = Scene::new();
+    let rng: StdRng = SeedableRng::seed_from_u64(seed);
+    scene.get_world_mut().insert_resource(rng);
+    resource_manager.attach_archive(NamedArchive::load(include_dir!("assets")));
+    resource_manager.load_default_meshes();
+    resource_manager.load_default_shaders();
+    on_init(&mut scene, &mut resource_manager);
+    load_input_bindings(&mut scene, &mut resource_manager);
+    scene.get_world_mut().insert_resource(resource_manager);
+    scene
+}
+
+// Bindings from the assets, then from the file named by the environment. Mistakes in either are
+// logged rather than fatal, the defaults still work
+fn load_input_bindings(scene: &mut Scene, resource_manager: &mut ResourceManager) {
+    let mut action_map = scene.get_world().expect_resource_mut::<ActionMap>();
+    match resource_manager.load_file(engine_config::INPUT_BINDINGS_PATH) {
+        Ok(bytes) => {
+            let result = String::from_utf8(bytes)
+                .map_err(|err| EngineError::Parse("input bindings".to_string(), err.to_string()))
+                .and_then(|source| action_map.load_bindings(&source));
+            if let Err(err) = result {
+                log::error!("{}", err);
+            }
+        }
+        Err(EngineError::FileNotFound(_)) => {}
+        Err(err) => log::error!("{}", err),
+    }
+    if let Ok(path) = std::env::var(engine_config::INPUT_BINDINGS_ENV) {
+        if let Err(err) = action_map.load_file(&path) {
+            log::error!("{}", err);
+        }
+    }
+}
+
+// The configured seed, or a random one so it can still be recorded
+pub(crate) fn resolve_seed(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(rand::random)
+}
+
+// Opens the recording and replay files named by the environment, see `engine_config`
+pub(crate) fn input_files_from_env() -> (Option<InputRecorder>, Option<InputRecording>) {
+    let recorder = std::env::var(engine_config::RECORD_INPUT_ENV)
+        .ok()
+        .map(|path| InputRecorder::create(&path).expect("Failed to create input recording"));
+    let recording = std::env::var(engine_config::REPLAY_INPUT_ENV)
+        .ok()
+        .map(|path| InputRecording::load(&path).expect("Failed to load input recording"));
+    (recorder, recording)
+}
+
 pub fn run_logic_loop(
     input_rx: Receiver<InputEvent>,
     render_tx: Sender<ExtractedFrame>,
     mut scene: Scene,
+    mut recorder: Option<InputRecorder>,
+    mut replay: Option<InputReplay>,
 ) {
-    let rng: StdRng = SeedableRng::from_entropy();
     let mut delta_time: Duration;
     let mut last: Instant = Instant::now();
-    let world = scene.get_world_mut();
-    world.insert_resource(rng);
 
     loop {
         delta_time = last.elapsed();
         if delta_time < TARGET_INTERVAL {
             spin_sleep::sleep(TARGET_INTERVAL - delta_time);
         }
-        delta_time = Duration::max(delta_time, TARGET_INTERVAL);
+        // The real frame time, so the fixed timestep accumulates wall clock time
+        delta_time = last.elapsed();
         last = Instant::now();
 
+        let frame_number = scene.get_world().expect_resource::<Time>().frame();
+        let mut records = Vec::new();
+        let world = scene.get_world_mut();
         while let Ok(event) = input_rx.try_recv() {
-            let world = scene.get_world_mut();
+            // Only the window size may change while replaying, anything else would desync it
+            if replay.is_some() && !matches!(event, InputEvent::WindowResize { .. }) {
+                continue;
+            }
+            if recorder.is_some() {
+                records.push(InputRecord::Event {
+                    frame: frame_number,
+                    event: event.clone(),
+                });
+            }
             InputHandler::handle_input_event(world, event);
         }
-        scene.update(delta_time.as_secs_f32());
-
-        let frame = scene.extract();
+        let mut delta_time = delta_time.as_secs_f32();
+        if let Some(active_replay) = &mut replay {
+            delta_time = active_replay
+                .apply(world, frame_number)
+                .unwrap_or(delta_time);
+            if active_replay.is_finished() {
+                log::info!("Input replay finished at frame {}", frame_number);
+                replay = None;
+            }
+        }
+        if let Some(active_recorder) = &mut recorder {
+            records.push(InputRecord::Frame {
+                frame: frame_number,
+                delta_time,
+            });
+            if let Err(err) = records
+                .iter()
+                .try_for_each(|record| active_recorder.record(record))
+            {
+                log::error!("Stopped recording input: {}", err);
+                recorder = None;
+            }
+        }
+        let frame = scene.step(delta_time);
         let _ = render_tx.send(frame);
     }
 }
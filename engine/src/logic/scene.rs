@@ -1,6 +1,12 @@
 pub mod debug_config;
+pub mod fixed_time;
 pub mod focus_point;
+pub mod hierarchy;
 pub mod matrix_uniform_buffer;
 pub mod particle_config;
+pub mod picking;
+pub mod prefab;
 pub mod scene;
+pub mod snapshot;
+pub mod time;
 pub mod world;
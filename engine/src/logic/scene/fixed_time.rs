@@ -0,0 +1,41 @@
+// Drives the `FixedUpdate` stage: every `timestep` seconds of accumulated frame time run one step,
+// but at most `max_substeps` per frame so a stall can't snowball into ever longer frames
+pub struct FixedTime {
+    pub timestep: f32,
+    pub max_substeps: u32,
+    accumulator: f32,
+}
+
+impl FixedTime {
+    pub fn from_hz(rate: f32) -> Self {
+        Self {
+            timestep: 1.0 / rate,
+            max_substeps: 5,
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn with_max_substeps(mut self, max_substeps: u32) -> Self {
+        self.max_substeps = max_substeps;
+        self
+    }
+
+    // Adds frame time and returns how many steps to run now. Time beyond `max_substeps` is dropped
+    pub fn accumulate(&mut self, delta_time: f32) -> u32 {
+        self.accumulator += delta_time;
+        let steps = (self.accumulator / self.timestep) as u32;
+        self.accumulator -= steps as f32 * self.timestep;
+        steps.min(self.max_substeps)
+    }
+
+    // How far the leftover time is into the next step, for interpolating between the last two steps
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.timestep).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::from_hz(60.0)
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use crate::{
+    logic::{
+        ecs::{
+            component::{children::Children, parent::Parent},
+            entity::entity::Entity,
+        },
+        scene::world::World,
+    },
+    shared::engine_error::EngineError,
+};
+
+fn parent_of(world: &World, entity: Entity) -> Option<Entity> {
+    world
+        .get_component::<Parent>(entity)
+        .map(|parent| parent.parent)
+        .filter(|parent| !parent.is_null())
+}
+
+// Nearest first. A parent that was already seen ends the walk with the cycle it closes
+fn ancestors(world: &World, entity: Entity) -> Result<Vec<Entity>, EngineError> {
+    let mut chain = vec![entity];
+    while let Some(parent) = parent_of(world, *chain.last().unwrap()) {
+        if let Some(start) = chain.iter().position(|&seen| seen == parent) {
+            let mut cycle = chain.split_off(start);
+            cycle.push(parent);
+            return Err(EngineError::HierarchyCycle(cycle));
+        }
+        chain.push(parent);
+    }
+    chain.remove(0);
+    Ok(chain)
+}
+
+pub(crate) fn set_parent(
+    world: &mut World,
+    child: Entity,
+    parent: Entity,
+) -> Result<(), EngineError> {
+    if child.is_null() || !world.is_alive(child) || !world.is_alive(parent) {
+        return Err(EngineError::Generic(format!(
+            "Can't parent {:?} to {:?}, both must be alive",
+            child, parent
+        )));
+    }
+    if !parent.is_null() {
+        if parent == child {
+            return Err(EngineError::HierarchyCycle(vec![child, child]));
+        }
+        let ancestors = ancestors(world, parent)?;
+        if let Some(index) = ancestors.iter().position(|&ancestor| ancestor == child) {
+            let mut cycle = vec![child, parent];
+            cycle.extend_from_slice(&ancestors[..=index]);
+            return Err(EngineError::HierarchyCycle(cycle));
+        }
+    }
+
+    // Inserting the `Parent` moves the entity to its new parent's `Children`
+    world.add_component(child, Parent::from(parent));
+    Ok(())
+}
+
+// Adds the entity to the `Children` of a parent it was just given
+pub(crate) fn attach(world: &mut World, child: Entity, parent: Entity) {
+    if parent.is_null() || !world.is_alive(parent) {
+        return;
+    }
+    let added = world
+        .get_component_mut::<Children>(parent)
+        .map(|mut children| children.push(child))
+        .is_some();
+    if !added {
+        world.add_component(parent, Children::new(vec![child]));
+    }
+}
+
+// Takes the entity out of its parent's `Children`, leaving its `Parent` as it is
+pub(crate) fn detach(world: &mut World, child: Entity) {
+    let Some(parent) = parent_of(world, child) else {
+        return;
+    };
+    let now_empty = match world.get_component_mut::<Children>(parent) {
+        Some(mut children) => {
+            children.remove(child);
+            children.is_empty()
+        }
+        None => false,
+    };
+    if now_empty {
+        world.remove_component::<Children>(parent);
+    }
+}
+
+// Depth first, without the entity itself. Cycles are walked only once
+pub(crate) fn descendants(world: &World, entity: Entity) -> Vec<Entity> {
+    let mut descendants = Vec::new();
+    let mut stack = vec![entity];
+    while let Some(current) = stack.pop() {
+        let Some(children) = world.get_component::<Children>(current) else {
+            continue;
+        };
+        for child in children.iter() {
+            if child != entity && !descendants.contains(&child) {
+                descendants.push(child);
+                stack.push(child);
+            }
+        }
+    }
+    descendants
+}
+
+pub(crate) fn validate(world: &World) -> Result<(), EngineError> {
+    for (entity, _) in world.query::<&Parent>().iter() {
+        ancestors(world, entity)?;
+    }
+    Ok(())
+}
+
+// Rebuilds `Children` from every `Parent`. Children that are still parented keep their order
+pub(crate) fn sync_children(world: &mut World) {
+    let mut parents: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    for (child, parent) in world.query::<&Parent>().iter() {
+        if !parent.parent.is_null() && world.is_alive(parent.parent) {
+            parents.entry(parent.parent).or_default().push(child);
+        }
+    }
+    let stale: Vec<Entity> = world
+        .query::<&Children>()
+        .iter()
+        .filter(|(entity, _)| !parents.contains_key(entity))
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in stale {
+        world.remove_component::<Children>(entity);
+    }
+
+    for (parent, children) in parents {
+        let existing: Vec<Entity> = world
+            .get_component::<Children>(parent)
+            .map(|existing| existing.iter().collect())
+            .unwrap_or_default();
+        let mut updated: Vec<Entity> = existing
+            .iter()
+            .copied()
+            .filter(|child| children.contains(child))
+            .collect();
+        for child in children {
+            if !updated.contains(&child) {
+                updated.push(child);
+            }
+        }
+        if updated != existing {
+            world.add_component(parent, Children::new(updated));
+        }
+    }
+}
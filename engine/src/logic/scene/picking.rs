@@ -0,0 +1,49 @@
+use crate::{
+    logic::{
+        ecs::{
+            component::{
+                camera::Camera, collider::Collider, global_transform::GlobalTransform,
+                transform::Transform,
+            },
+            entity::entity::Entity,
+        },
+        scene::{matrix_uniform_buffer::MatrixUniformBuffer, world::World},
+    },
+    math::vec2::Vec2,
+    render::uniform_buffer_source::UniformBufferSource,
+    shared::window_size::WindowSize,
+};
+
+// Colliders under a screen position, front most first. Each collider is tested at its own depth,
+// since the projection scales entities by their distance to the camera
+pub(crate) fn pick(world: &World, screen_position: Vec2) -> Vec<Entity> {
+    let (Some(matrices), Some(window_size)) = (
+        world.get_resource::<UniformBufferSource<MatrixUniformBuffer>>(),
+        world.get_resource::<WindowSize>(),
+    ) else {
+        return Vec::new();
+    };
+    let mut hits: Vec<(Entity, f32)> = world
+        .query::<(&Collider, &Transform, Option<&GlobalTransform>)>()
+        .iter()
+        .filter_map(|(entity, (collider, transform, global))| {
+            let transform = global.map_or(transform, |global| &global.transform);
+            let point = Camera::screen_to_world(
+                &matrices.data,
+                &window_size,
+                screen_position,
+                transform.position.z,
+            )?;
+            collider
+                .contains_point(
+                    point.xy(),
+                    transform.position.xy() + collider.offset,
+                    transform.scale,
+                    transform.rotation,
+                )
+                .then_some((entity, transform.position.z))
+        })
+        .collect();
+    hits.sort_by(|(_, z_1), (_, z_2)| z_2.total_cmp(z_1));
+    hits.into_iter().map(|(entity, _)| entity).collect()
+}
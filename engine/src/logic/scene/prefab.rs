@@ -0,0 +1,490 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use serde::{
+    de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{
+    engine_config,
+    logic::{
+        ecs::{
+            component::{
+                component::SerializeComponent,
+                material::{DrawMode, Material},
+                model::Model,
+                parent::Parent,
+            },
+            entity::{entity::Entity, entity_map::EntityMap},
+        },
+        scene::{
+            snapshot::{ComponentRegistration, ErasedValue, InsertComponentFn, SnapshotRegistry},
+            world::World,
+        },
+    },
+    math::vec3::Vec3,
+    render::resource::{
+        resource_manager::ResourceManager,
+        resource_provider::ResourceProvider,
+        shader::material_parameter::MaterialParameter,
+        texture::texture::{StaticColor, Texture},
+    },
+    shared::engine_error::EngineError,
+};
+
+// Prefabs are RON files in the asset archive, under `engine_config::PREFABS_PATH`:
+//
+// (
+//     prefab: "sea_trash",
+//     id: 1,
+//     name: "can",
+//     components: { "Transform": (...), "Parent": (parent: (id: 1, generation: 0)) },
+//     model: "square",
+//     material: (texture: Static("fish.png"), shader: "model", params: { "uColor": Vec3(...) }),
+//     children: [(...)],
+// )
+//
+// Every field is optional. `prefab` names a base prefab, whose components are overridden by
+// components of the same name and whose children come before the node's own. Components use
+// their snapshot format, see `SnapshotRegistry`, and refer to other entities of the prefab by
+// their `id`. Children are parented to their node unless they have a `Parent` of their own. A
+// `name` lets code find the spawned entity, see `PrefabInstance::named`
+#[derive(Default)]
+pub struct PrefabLibrary {
+    sources: HashMap<String, String>,
+    // Merged with their bases, and copied for every spawn instead of being parsed again
+    parsed: HashMap<String, PrefabNode>,
+}
+
+impl PrefabLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Adds a prefab without going through the asset archive, or replaces a loaded one
+    pub fn insert(&mut self, name: &str, source: impl Into<String>) {
+        self.sources.insert(name.to_string(), source.into());
+        // Prefabs based on it have to be merged again
+        self.parsed.clear();
+    }
+
+    fn source(
+        &mut self,
+        name: &str,
+        resource_manager: Option<&mut ResourceManager>,
+    ) -> Result<&str, EngineError> {
+        if !self.sources.contains_key(name) {
+            let path = format!("{}/{}.ron", engine_config::PREFABS_PATH, name);
+            let resource_manager = resource_manager
+                .ok_or_else(|| EngineError::Generic(format!("Prefab '{}' isn't loaded", name)))?;
+            let data = resource_manager.load_file(&path)?;
+            let source =
+                String::from_utf8(data).map_err(|err| EngineError::Parse(path, err.to_string()))?;
+            self.sources.insert(name.to_string(), source);
+        }
+        Ok(&self.sources[name])
+    }
+}
+
+// The spawned entities, with the prefab's ids mapped to them as `Entity::new(id, 0)`
+pub struct PrefabInstance {
+    pub root: Entity,
+    pub entities: EntityMap,
+    names: HashMap<String, Entity>,
+}
+
+impl PrefabInstance {
+    // The entity spawned for the node with the given `name`
+    pub fn named(&self, name: &str) -> Option<Entity> {
+        self.names.get(name).copied()
+    }
+}
+
+#[derive(Clone, Default, Deserialize)]
+enum TextureDescriptor {
+    #[default]
+    None,
+    Color(Vec3),
+    Static(String),
+    Animated {
+        frames: Vec<String>,
+        animation_time: u64,
+    },
+}
+
+#[derive(Clone, Deserialize)]
+struct MaterialDescriptor {
+    #[serde(default)]
+    texture: TextureDescriptor,
+    shader: String,
+    #[serde(default)]
+    params: BTreeMap<String, MaterialParameter>,
+    #[serde(default)]
+    draw_mode: DrawMode,
+}
+
+struct PrefabNode {
+    base: Option<String>,
+    id: Option<u32>,
+    name: Option<String>,
+    components: Vec<(ComponentRegistration, ErasedValue)>,
+    model: Option<String>,
+    material: Option<MaterialDescriptor>,
+    children: Vec<PrefabNode>,
+}
+
+impl PrefabNode {
+    fn duplicate(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            id: self.id,
+            name: self.name.clone(),
+            components: self
+                .components
+                .iter()
+                .map(|(registration, component)| {
+                    (*registration, (registration.duplicate)(component))
+                })
+                .collect(),
+            model: self.model.clone(),
+            material: self.material.clone(),
+            children: self.children.iter().map(Self::duplicate).collect(),
+        }
+    }
+}
+
+struct PrefabEntity {
+    parent: Option<usize>,
+    id: Option<u32>,
+    name: Option<String>,
+    components: Vec<(InsertComponentFn, ErasedValue)>,
+    has_parent: bool,
+    model: Option<Model>,
+    material: Option<Material>,
+}
+
+// Entities are spawned through commands, so they exist once the world's commands are applied
+pub(crate) fn instantiate(
+    world: &World,
+    name: &str,
+    mut resource_manager: Option<&mut ResourceManager>,
+) -> Result<PrefabInstance, EngineError> {
+    let registry = world.get_resource::<SnapshotRegistry>().ok_or_else(|| {
+        EngineError::Generic("Prefabs need a SnapshotRegistry resource".to_string())
+    })?;
+    let mut library = world
+        .get_resource_mut::<PrefabLibrary>()
+        .ok_or_else(|| EngineError::Generic("Prefabs need a PrefabLibrary resource".to_string()))?;
+    let node = PrefabLoader {
+        library: &mut library,
+        registry: &registry,
+        resource_manager: resource_manager.as_deref_mut(),
+        loading: Vec::new(),
+    }
+    .load(name)?;
+
+    let mut prefab_entities = Vec::new();
+    flatten(node, None, name, resource_manager, &mut prefab_entities)?;
+
+    let entities: Vec<Entity> = prefab_entities
+        .iter()
+        .map(|_| world.reserve_entity())
+        .collect();
+    let mut entity_map = EntityMap::new();
+    let mut names = HashMap::new();
+    for (prefab_entity, &entity) in prefab_entities.iter().zip(&entities) {
+        if let Some(id) = prefab_entity.id {
+            entity_map.insert(Entity::new(id, 0), entity);
+        }
+        if let Some(name) = &prefab_entity.name {
+            names.insert(name.clone(), entity);
+        }
+    }
+
+    let instance = PrefabInstance {
+        root: entities[0],
+        entities: entity_map.clone(),
+        names,
+    };
+    world.commands().add(move |world| {
+        for (prefab_entity, &entity) in prefab_entities.into_iter().zip(&entities) {
+            for (insert, component) in prefab_entity.components {
+                insert(world, entity, component, &entity_map);
+            }
+            if let (Some(parent), false) = (prefab_entity.parent, prefab_entity.has_parent) {
+                world.add_component(entity, Parent::from(entities[parent]));
+            }
+            if let Some(model) = prefab_entity.model {
+                world.add_component(entity, model);
+            }
+            if let Some(material) = prefab_entity.material {
+                world.add_component(entity, material);
+            }
+        }
+    });
+    Ok(instance)
+}
+
+struct PrefabLoader<'a, 'r> {
+    library: &'a mut PrefabLibrary,
+    registry: &'r SnapshotRegistry,
+    resource_manager: Option<&'a mut ResourceManager>,
+    // Prefabs whose base is being loaded, to catch prefabs that end up being their own base
+    loading: Vec<String>,
+}
+
+impl PrefabLoader<'_, '_> {
+    fn load(&mut self, name: &str) -> Result<PrefabNode, EngineError> {
+        if let Some(node) = self.library.parsed.get(name) {
+            return Ok(node.duplicate());
+        }
+        if self.loading.iter().any(|loading| loading == name) {
+            return Err(EngineError::Generic(format!(
+                "Prefab base cycle: {} -> {}",
+                self.loading.join(" -> "),
+                name
+            )));
+        }
+        let source = self
+            .library
+            .source(name, self.resource_manager.as_deref_mut())?;
+        let node = ron::Options::default()
+            .from_str_seed(source, NodeSeed(self.registry))
+            .map_err(|err| EngineError::Parse(format!("prefab '{}'", name), err.to_string()))?;
+        self.loading.push(name.to_string());
+        let node = self.resolve(node);
+        self.loading.pop();
+        let node = node?;
+        self.library
+            .parsed
+            .insert(name.to_string(), node.duplicate());
+        Ok(node)
+    }
+
+    // Merges the node and its children with their base prefabs
+    fn resolve(&mut self, node: PrefabNode) -> Result<PrefabNode, EngineError> {
+        let mut children = Vec::with_capacity(node.children.len());
+        for child in node.children {
+            children.push(self.resolve(child)?);
+        }
+        let Some(base_name) = node.base else {
+            return Ok(PrefabNode { children, ..node });
+        };
+        let mut base = self.load(&base_name)?;
+        base.components.retain(|(registration, _)| {
+            node.components
+                .iter()
+                .all(|(overriding, _)| overriding.name != registration.name)
+        });
+        base.components.extend(node.components);
+        base.children.extend(children);
+        Ok(PrefabNode {
+            base: None,
+            id: node.id.or(base.id),
+            name: node.name.or(base.name),
+            components: base.components,
+            model: node.model.or(base.model),
+            material: node.material.or(base.material),
+            children: base.children,
+        })
+    }
+}
+
+// Lists the entities of the hierarchy depth first, so the root comes first
+fn flatten(
+    node: PrefabNode,
+    parent: Option<usize>,
+    name: &str,
+    mut resource_manager: Option<&mut ResourceManager>,
+    prefab_entities: &mut Vec<PrefabEntity>,
+) -> Result<(), EngineError> {
+    let error = |message: String| EngineError::Generic(format!("Prefab '{}': {}", name, message));
+    if let Some(id) = node.id {
+        if id == 0 {
+            return Err(error("id 0 is reserved for the null entity".to_string()));
+        }
+        if prefab_entities.iter().any(|other| other.id == Some(id)) {
+            return Err(error(format!("id {} is used more than once", id)));
+        }
+    }
+    if let Some(name) = &node.name {
+        if prefab_entities
+            .iter()
+            .any(|other| other.name.as_ref() == Some(name))
+        {
+            return Err(error(format!("name '{}' is used more than once", name)));
+        }
+    }
+    let (model, material) = match resource_manager.as_deref_mut() {
+        Some(resource_manager) => (
+            node.model
+                .map(|model| Model::from(resource_manager.get_mesh(&model))),
+            node.material
+                .map(|material| build_material(material, resource_manager)),
+        ),
+        None if node.model.is_some() || node.material.is_some() => {
+            return Err(error(
+                "a model or material needs a ResourceManager".to_string(),
+            ))
+        }
+        None => (None, None),
+    };
+
+    let index = prefab_entities.len();
+    let has_parent = node
+        .components
+        .iter()
+        .any(|(registration, _)| registration.name == Parent::NAME);
+    prefab_entities.push(PrefabEntity {
+        parent,
+        id: node.id,
+        name: node.name,
+        components: node
+            .components
+            .into_iter()
+            .map(|(registration, component)| (registration.insert, component))
+            .collect(),
+        has_parent,
+        model,
+        material,
+    });
+    for child in node.children {
+        flatten(
+            child,
+            Some(index),
+            name,
+            resource_manager.as_deref_mut(),
+            prefab_entities,
+        )?;
+    }
+    Ok(())
+}
+
+fn build_material(
+    descriptor: MaterialDescriptor,
+    resource_manager: &mut ResourceManager,
+) -> Material {
+    // Textures that fail to load show up gray, like in the rest of the game
+    let missing = || Texture::StaticColor(StaticColor::new((0.5, 0.5, 0.5).into()));
+    let texture = match descriptor.texture {
+        TextureDescriptor::None => Texture::None,
+        TextureDescriptor::Color(color) => Texture::StaticColor(StaticColor::new(color)),
+        TextureDescriptor::Static(texture_name) => resource_manager
+            .load_static_texture(&texture_name)
+            .unwrap_or_else(missing),
+        TextureDescriptor::Animated {
+            frames,
+            animation_time,
+        } => {
+            let frames: Vec<&str> = frames.iter().map(String::as_str).collect();
+            resource_manager
+                .load_animated_texture(&frames, animation_time as u128)
+                .unwrap_or_else(missing)
+        }
+    };
+    let shader = resource_manager.get_shader(&descriptor.shader);
+    let mut material = Material::new(texture, shader).with_draw_mode(descriptor.draw_mode);
+    for (param_name, value) in descriptor.params {
+        material.set_param(&param_name, value);
+    }
+    material
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum NodeField {
+    Prefab,
+    Id,
+    Name,
+    Components,
+    Model,
+    Material,
+    Children,
+}
+
+const NODE_FIELDS: &[&str] = &[
+    "prefab",
+    "id",
+    "name",
+    "components",
+    "model",
+    "material",
+    "children",
+];
+
+struct NodeSeed<'r>(&'r SnapshotRegistry);
+
+impl<'de, 'r> DeserializeSeed<'de> for NodeSeed<'r> {
+    type Value = PrefabNode;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("Prefab", NODE_FIELDS, self)
+    }
+}
+
+impl<'de, 'r> Visitor<'de> for NodeSeed<'r> {
+    type Value = PrefabNode;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a prefab")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut node = PrefabNode {
+            base: None,
+            id: None,
+            name: None,
+            components: Vec::new(),
+            model: None,
+            material: None,
+            children: Vec::new(),
+        };
+        while let Some(field) = map.next_key()? {
+            match field {
+                NodeField::Prefab => node.base = Some(map.next_value()?),
+                NodeField::Id => node.id = Some(map.next_value()?),
+                NodeField::Name => node.name = Some(map.next_value()?),
+                NodeField::Components => {
+                    node.components = map
+                        .next_value_seed(self.0.components_seed())?
+                        .into_iter()
+                        .map(|(registration, component)| (*registration, component))
+                        .collect()
+                }
+                NodeField::Model => node.model = Some(map.next_value()?),
+                NodeField::Material => node.material = Some(map.next_value()?),
+                NodeField::Children => node.children = map.next_value_seed(ChildrenSeed(self.0))?,
+            }
+        }
+        Ok(node)
+    }
+}
+
+struct ChildrenSeed<'r>(&'r SnapshotRegistry);
+
+impl<'de, 'r> DeserializeSeed<'de> for ChildrenSeed<'r> {
+    type Value = Vec<PrefabNode>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'r> Visitor<'de> for ChildrenSeed<'r> {
+    type Value = Vec<PrefabNode>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of prefabs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some(child) = seq.next_element_seed(NodeSeed(self.0))? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
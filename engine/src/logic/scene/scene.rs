@@ -1,6 +1,11 @@
+use std::time::Duration;
+
+use winit::keyboard::{Key, NamedKey};
+
 use crate::{
     logic::{
         ecs::{
+            collision_event::CollisionEvents,
             component::camera::Camera,
             extract::{
                 debug_extractor::DebugExtractor, extractor::Extractor,
@@ -8,34 +13,66 @@ use crate::{
                 postprocess_extractor::PostprocessExtractor,
             },
             system::{
-                collision_system::CollisionSystem, debug_system::DebugSystem,
-                emitter_system::EmitterSystem, movement_system::MovementSystem,
-                particle_system::ParticleSystem, system::System,
+                action_system::ActionSystem,
+                collision_system::CollisionSystem,
+                debug_system::DebugSystem,
+                debug_toggle_system::DebugToggleSystem,
+                emitter_system::EmitterSystem,
+                hierarchy_system::HierarchySystem,
+                movement_system::MovementSystem,
+                particle_system::ParticleSystem,
+                previous_transform_system::PreviousTransformSystem,
+                schedule::{Schedule, Stage, SystemConfig},
+                transform_propagation_system::TransformPropagationSystem,
             },
         },
         scene::{
-            debug_config::DebugConfig, focus_point::FocusPoint,
-            matrix_uniform_buffer::MatrixUniformBuffer, world::World,
+            debug_config::DebugConfig, fixed_time::FixedTime, focus_point::FocusPoint,
+            matrix_uniform_buffer::MatrixUniformBuffer, prefab::PrefabLibrary,
+            snapshot::SnapshotRegistry, time::Time, world::World,
         },
     },
     math::vec3::Vec3,
     render::uniform_buffer_source::UniformBufferSource,
     shared::{
-        extracted_frame::ExtractedFrame, input::input_state::InputState, window_size::WindowSize,
+        extracted_frame::ExtractedFrame,
+        input::{
+            action_map::{ActionMap, Binding},
+            input_state::InputState,
+        },
+        window_size::WindowSize,
     },
 };
 
 pub struct Scene {
-    pub systems: Vec<Box<dyn System>>,
+    pub schedule: Schedule,
     pub extractors: Vec<Box<dyn Extractor>>,
+    // Change tick at which each extractor last ran, for `Changed` and `Added` queries
+    extractor_ticks: Vec<u32>,
+    // Passed to `Stage::Extract` systems
+    delta_time: f32,
     world: World,
 }
 
+impl Default for Scene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Scene {
     pub fn new() -> Self {
         let mut world = World::load();
         world.insert_resource(InputState::init());
+        let mut actions = ActionMap::new();
+        actions.bind("toggle_debug", Binding::Key(Key::Named(NamedKey::F3)));
+        world.insert_resource(actions);
         world.insert_resource(DebugConfig { enabled: false });
+        world.insert_resource(Time::new());
+        world.insert_resource(FixedTime::default());
+        world.insert_resource(CollisionEvents::default());
+        world.insert_resource(SnapshotRegistry::default());
+        world.insert_resource(PrefabLibrary::new());
         world.insert_resource(FocusPoint(Vec3::new(0.0, 0.0, 0.0)));
         world.insert_resource(WindowSize {
             width: 0,
@@ -61,30 +98,72 @@ impl Scene {
             },
         );
 
-        let systems: Vec<Box<dyn System>> = vec![
-            Box::new(MovementSystem),
-            Box::new(ParticleSystem),
-            Box::new(EmitterSystem),
-            Box::new(CollisionSystem),
-            Box::new(DebugSystem::new()),
-        ];
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(SystemConfig::parallel("action", ActionSystem).in_stage(Stage::PreUpdate));
+        schedule.add_system(
+            SystemConfig::new("debug_toggle", DebugToggleSystem::new())
+                .in_stage(Stage::PreUpdate)
+                .after("action"),
+        );
+        schedule.add_system(
+            SystemConfig::parallel("previous_transform", PreviousTransformSystem)
+                .in_stage(Stage::FixedUpdate),
+        );
+        schedule.add_system(
+            SystemConfig::parallel("movement", MovementSystem)
+                .in_stage(Stage::FixedUpdate)
+                .after("previous_transform"),
+        );
+        // Collision works in world space, so it gets its own propagation after movement
+        schedule.add_system(
+            SystemConfig::new("fixed_transform_propagation", TransformPropagationSystem)
+                .in_stage(Stage::FixedUpdate)
+                .after("movement"),
+        );
+        schedule.add_system(
+            SystemConfig::parallel("collision", CollisionSystem)
+                .in_stage(Stage::FixedUpdate)
+                .after("fixed_transform_propagation"),
+        );
+        schedule.add_system(SystemConfig::parallel("particle", ParticleSystem));
+        schedule.add_system(SystemConfig::parallel("emitter", EmitterSystem).after("particle"));
+        schedule.add_system(
+            SystemConfig::new("hierarchy", HierarchySystem).in_stage(Stage::PostUpdate),
+        );
+        schedule.add_system(
+            SystemConfig::new("transform_propagation", TransformPropagationSystem)
+                .in_stage(Stage::PostUpdate)
+                .after("hierarchy"),
+        );
+        schedule.add_system(
+            SystemConfig::parallel("debug", DebugSystem)
+                .in_stage(Stage::PostUpdate)
+                .run_if(|world| {
+                    world
+                        .get_resource::<DebugConfig>()
+                        .is_some_and(|config| config.enabled)
+                }),
+        );
 
         let extractors: Vec<Box<dyn Extractor>> = vec![
-            Box::new(ModelExtractor),
+            Box::new(ModelExtractor::new()),
             Box::new(ParticleExtractor),
             Box::new(DebugExtractor),
             Box::new(PostprocessExtractor),
         ];
 
         Scene {
-            systems,
-            world,
+            schedule,
             extractors,
+            extractor_ticks: Vec::new(),
+            delta_time: 0.0,
+            world,
         }
     }
 
-    pub fn register_system(&mut self, system: Box<dyn System>) {
-        self.systems.insert(self.systems.len(), system);
+    pub fn register_system(&mut self, config: SystemConfig) {
+        self.schedule.add_system(config);
     }
 
     pub fn register_extractor(&mut self, extractor: Box<dyn Extractor>) {
@@ -92,9 +171,34 @@ impl Scene {
     }
 
     pub fn update(&mut self, delta_time: f32) {
-        for system in &mut self.systems {
-            system.run(&mut self.world, delta_time);
+        self.delta_time = delta_time;
+        if let Some(mut time) = self.world.get_resource_mut::<Time>() {
+            time.advance(delta_time);
+        }
+        if let Some(mut events) = self.world.get_resource_mut::<CollisionEvents>() {
+            events.clear();
         }
+        self.schedule
+            .run_stage(Stage::PreUpdate, &mut self.world, delta_time);
+        let (steps, timestep) = match self.world.get_resource_mut::<FixedTime>() {
+            Some(mut fixed_time) => (fixed_time.accumulate(delta_time), fixed_time.timestep),
+            None => (0, 0.0),
+        };
+        for _ in 0..steps {
+            self.schedule
+                .run_stage(Stage::FixedUpdate, &mut self.world, timestep);
+        }
+        for stage in [Stage::Update, Stage::PostUpdate] {
+            self.schedule.run_stage(stage, &mut self.world, delta_time);
+        }
+        // Changes made outside of systems must be newer than every system's last run
+        self.world.increment_change_tick();
+    }
+
+    // One frame of the logic loop
+    pub fn step(&mut self, delta_time: f32) -> ExtractedFrame {
+        self.update(delta_time);
+        self.extract()
     }
 
     pub fn extract(&mut self) -> ExtractedFrame {
@@ -103,10 +207,24 @@ impl Scene {
             entities: Vec::new(),
             window_size: None,
             postprocess_pass: None,
+            interpolation_alpha: self
+                .world
+                .get_resource::<FixedTime>()
+                .map_or(1.0, |fixed_time| fixed_time.alpha()),
+            elapsed: self
+                .world
+                .get_resource::<Time>()
+                .map_or(Duration::ZERO, |time| time.elapsed()),
         };
-        for extractor in &mut self.extractors {
+        self.schedule
+            .run_stage(Stage::Extract, &mut self.world, self.delta_time);
+        self.extractor_ticks.resize(self.extractors.len(), 0);
+        for (extractor, last_run) in self.extractors.iter_mut().zip(&mut self.extractor_ticks) {
+            self.world.set_last_change_tick(*last_run);
+            *last_run = self.world.increment_change_tick();
             extractor.extract(&self.world, &mut frame);
         }
+        self.world.increment_change_tick();
         frame
     }
 
@@ -0,0 +1,516 @@
+use std::{any::Any, collections::BTreeMap, fmt};
+
+use bincode::Options;
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    logic::{
+        ecs::{
+            borrow_cell::Ref,
+            component::{
+                collider::Collider, component::SerializeComponent, force::Force,
+                movement::Movement, parent::Parent, previous_transform::PreviousTransform,
+                rigid_body::RigidBody, transform::Transform,
+            },
+            entity::{entity::Entity, entity_map::EntityMap},
+        },
+        scene::{time::Time, world::World},
+    },
+    shared::engine_error::EngineError,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    // Readable and diffable, for debugging and hand written worlds
+    Ron,
+    Binary,
+}
+
+// Resources that can be stored in a world snapshot, see `SnapshotRegistry`
+pub trait SerializeResource: Serialize + DeserializeOwned + Send + Sync + 'static {
+    const NAME: &'static str;
+
+    fn map_entities(&mut self, _entity_map: &EntityMap) {}
+}
+
+pub(crate) type ErasedValue = Box<dyn Any + Send + Sync>;
+pub(crate) type InsertComponentFn = fn(&mut World, Entity, ErasedValue, &EntityMap);
+type ErasedSerialize<'w> = Box<dyn erased_serde::Serialize + 'w>;
+type DeserializeFn = for<'de> fn(
+    &mut dyn erased_serde::Deserializer<'de>,
+) -> Result<ErasedValue, erased_serde::Error>;
+
+#[derive(Clone, Copy)]
+pub(crate) struct ComponentRegistration {
+    pub(crate) name: &'static str,
+    serialize: for<'w> fn(&'w World, Entity) -> Option<ErasedSerialize<'w>>,
+    deserialize: DeserializeFn,
+    pub(crate) insert: InsertComponentFn,
+    pub(crate) duplicate: fn(&ErasedValue) -> ErasedValue,
+}
+
+struct ResourceRegistration {
+    serialize: for<'w> fn(&'w World) -> Option<ErasedSerialize<'w>>,
+    deserialize: DeserializeFn,
+    insert: fn(&mut World, ErasedValue, &EntityMap),
+}
+
+// Components and resources that `World::save` writes, by name. Entities without any registered
+// component aren't saved. The registry lives in the world as a resource
+pub struct SnapshotRegistry {
+    components: BTreeMap<&'static str, ComponentRegistration>,
+    resources: BTreeMap<&'static str, ResourceRegistration>,
+}
+
+impl Default for SnapshotRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register_component::<Transform>();
+        registry.register_component::<PreviousTransform>();
+        registry.register_component::<Movement>();
+        registry.register_component::<Force>();
+        registry.register_component::<Collider>();
+        registry.register_component::<RigidBody>();
+        registry.register_component::<Parent>();
+        registry.register_resource::<Time>();
+        registry
+    }
+}
+
+impl SnapshotRegistry {
+    pub fn empty() -> Self {
+        Self {
+            components: BTreeMap::new(),
+            resources: BTreeMap::new(),
+        }
+    }
+
+    pub fn register_component<T: SerializeComponent>(&mut self) {
+        self.components.insert(
+            T::NAME,
+            ComponentRegistration {
+                name: T::NAME,
+                serialize: serialize_component::<T>,
+                deserialize: deserialize::<T>,
+                insert: insert_component::<T>,
+                duplicate: duplicate_component::<T>,
+            },
+        );
+    }
+
+    // Deserializes a map of component values by name, as used by snapshots and prefabs
+    pub(crate) fn components_seed(&self) -> ErasedMapSeed<'_, ComponentRegistration> {
+        ErasedMapSeed {
+            registrations: &self.components,
+            kind: "component",
+        }
+    }
+
+    pub fn register_resource<T: SerializeResource>(&mut self) {
+        self.resources.insert(
+            T::NAME,
+            ResourceRegistration {
+                serialize: serialize_resource::<T>,
+                deserialize: deserialize::<T>,
+                insert: insert_resource::<T>,
+            },
+        );
+    }
+}
+
+struct SerializeRef<'w, T>(Ref<'w, T>);
+
+impl<T: Serialize> Serialize for SerializeRef<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (*self.0).serialize(serializer)
+    }
+}
+
+fn serialize_component<T: SerializeComponent>(
+    world: &World,
+    entity: Entity,
+) -> Option<ErasedSerialize<'_>> {
+    let component = world.get_component::<T>(entity)?;
+    Some(Box::new(SerializeRef(component)))
+}
+
+fn serialize_resource<T: SerializeResource>(world: &World) -> Option<ErasedSerialize<'_>> {
+    let resource = world.get_resource::<T>()?;
+    Some(Box::new(SerializeRef(resource)))
+}
+
+fn deserialize<T: DeserializeOwned + Send + Sync + 'static>(
+    deserializer: &mut dyn erased_serde::Deserializer,
+) -> Result<ErasedValue, erased_serde::Error> {
+    Ok(Box::new(erased_serde::deserialize::<T>(deserializer)?))
+}
+
+fn insert_component<T: SerializeComponent>(
+    world: &mut World,
+    entity: Entity,
+    component: ErasedValue,
+    entity_map: &EntityMap,
+) {
+    let mut component = *component.downcast::<T>().unwrap();
+    component.map_entities(entity_map);
+    world.add_component(entity, component);
+}
+
+fn duplicate_component<T: SerializeComponent>(component: &ErasedValue) -> ErasedValue {
+    Box::new(component.downcast_ref::<T>().unwrap().clone())
+}
+
+fn insert_resource<T: SerializeResource>(
+    world: &mut World,
+    resource: ErasedValue,
+    entity_map: &EntityMap,
+) {
+    let mut resource = *resource.downcast::<T>().unwrap();
+    resource.map_entities(entity_map);
+    world.insert_resource(resource);
+}
+
+// Component or resource values by registered name
+struct ErasedMap<'w>(Vec<(&'static str, ErasedSerialize<'w>)>);
+
+impl Serialize for ErasedMap<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in &self.0 {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Serialize)]
+struct EntitySnapshot<'w> {
+    entity: Entity,
+    components: ErasedMap<'w>,
+}
+
+#[derive(Serialize)]
+struct Snapshot<'w> {
+    entities: Vec<EntitySnapshot<'w>>,
+    resources: ErasedMap<'w>,
+}
+
+fn missing_registry() -> EngineError {
+    EngineError::Generic("World has no SnapshotRegistry resource".to_string())
+}
+
+pub(crate) fn save(world: &World, format: SnapshotFormat) -> Result<Vec<u8>, EngineError> {
+    let registry = world
+        .get_resource::<SnapshotRegistry>()
+        .ok_or_else(missing_registry)?;
+    let mut entities: Vec<Entity> = world
+        .entities
+        .iter()
+        .copied()
+        .filter(|entity| !entity.is_null())
+        .collect();
+    entities.sort_by_key(|entity| entity.id);
+    let entities = entities
+        .into_iter()
+        .filter_map(|entity| {
+            let components: Vec<_> = registry
+                .components
+                .iter()
+                .filter_map(|(&name, registration)| {
+                    Some((name, (registration.serialize)(world, entity)?))
+                })
+                .collect();
+            (!components.is_empty()).then_some(EntitySnapshot {
+                entity,
+                components: ErasedMap(components),
+            })
+        })
+        .collect();
+    let resources = registry
+        .resources
+        .iter()
+        .filter_map(|(&name, registration)| Some((name, (registration.serialize)(world)?)))
+        .collect();
+    let snapshot = Snapshot {
+        entities,
+        resources: ErasedMap(resources),
+    };
+
+    let error = |err: String| EngineError::Generic(format!("Failed to save snapshot: {}", err));
+    match format {
+        SnapshotFormat::Ron => {
+            ron::ser::to_string_pretty(&snapshot, ron::ser::PrettyConfig::default())
+                .map(String::into_bytes)
+                .map_err(|err| error(err.to_string()))
+        }
+        SnapshotFormat::Binary => bincode::options()
+            .serialize(&snapshot)
+            .map_err(|err| error(err.to_string())),
+    }
+}
+
+// Every entity of the snapshot is spawned as a new entity, so a snapshot can be loaded into a
+// world that already has entities. Returns which entity each saved entity was loaded as
+pub(crate) fn load(
+    world: &mut World,
+    data: &[u8],
+    format: SnapshotFormat,
+) -> Result<EntityMap, EngineError> {
+    if world.get_resource::<SnapshotRegistry>().is_none() {
+        return Err(missing_registry());
+    }
+    world.resource_scope(|world, registry: &mut SnapshotRegistry| {
+        let seed = SnapshotSeed(registry);
+        let error = |err: String| EngineError::Parse("snapshot".to_string(), err);
+        let snapshot = match format {
+            SnapshotFormat::Ron => ron::Options::default()
+                .from_bytes_seed(data, seed)
+                .map_err(|err| error(err.to_string()))?,
+            SnapshotFormat::Binary => bincode::options()
+                .deserialize_seed(seed, data)
+                .map_err(|err| error(err.to_string()))?,
+        };
+
+        let mut entity_map = EntityMap::new();
+        for loaded in &snapshot.entities {
+            entity_map.insert(loaded.entity, world.create_entity());
+        }
+        for loaded in snapshot.entities {
+            let entity = entity_map.map(loaded.entity);
+            for (registration, component) in loaded.components {
+                (registration.insert)(world, entity, component, &entity_map);
+            }
+        }
+        for (registration, resource) in snapshot.resources {
+            (registration.insert)(world, resource, &entity_map);
+        }
+        Ok(entity_map)
+    })
+}
+
+struct LoadedEntity<'r> {
+    entity: Entity,
+    components: Vec<(&'r ComponentRegistration, ErasedValue)>,
+}
+
+struct LoadedSnapshot<'r> {
+    entities: Vec<LoadedEntity<'r>>,
+    resources: Vec<(&'r ResourceRegistration, ErasedValue)>,
+}
+
+// Values are deserialized straight into their registered types, since that is the only place
+// where the concrete type is known
+pub(crate) trait Registration {
+    fn deserialize_fn(&self) -> DeserializeFn;
+}
+
+impl Registration for ComponentRegistration {
+    fn deserialize_fn(&self) -> DeserializeFn {
+        self.deserialize
+    }
+}
+
+impl Registration for ResourceRegistration {
+    fn deserialize_fn(&self) -> DeserializeFn {
+        self.deserialize
+    }
+}
+
+struct ErasedSeed(DeserializeFn);
+
+impl<'de> DeserializeSeed<'de> for ErasedSeed {
+    type Value = ErasedValue;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut erased).map_err(de::Error::custom)
+    }
+}
+
+pub(crate) struct ErasedMapSeed<'r, R> {
+    registrations: &'r BTreeMap<&'static str, R>,
+    kind: &'static str,
+}
+
+impl<'de, 'r, R: Registration> DeserializeSeed<'de> for ErasedMapSeed<'r, R> {
+    type Value = Vec<(&'r R, ErasedValue)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'r, R: Registration> Visitor<'de> for ErasedMapSeed<'r, R> {
+    type Value = Vec<(&'r R, ErasedValue)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a map of {}s by name", self.kind)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(name) = map.next_key::<String>()? {
+            let registration = self
+                .registrations
+                .get(name.as_str())
+                .ok_or_else(|| de::Error::custom(format!("unknown {} '{}'", self.kind, name)))?;
+            let value = map.next_value_seed(ErasedSeed(registration.deserialize_fn()))?;
+            values.push((registration, value));
+        }
+        Ok(values)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum EntityField {
+    Entity,
+    Components,
+}
+
+struct EntitySeed<'r>(&'r SnapshotRegistry);
+
+impl<'r> EntitySeed<'r> {
+    fn components(&self) -> ErasedMapSeed<'r, ComponentRegistration> {
+        self.0.components_seed()
+    }
+}
+
+impl<'de, 'r> DeserializeSeed<'de> for EntitySeed<'r> {
+    type Value = LoadedEntity<'r>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("EntitySnapshot", &["entity", "components"], self)
+    }
+}
+
+impl<'de, 'r> Visitor<'de> for EntitySeed<'r> {
+    type Value = LoadedEntity<'r>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an entity with its components")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let entity = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let components = seq
+            .next_element_seed(self.components())?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(LoadedEntity { entity, components })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entity = None;
+        let mut components = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                EntityField::Entity => entity = Some(map.next_value()?),
+                EntityField::Components => {
+                    components = Some(map.next_value_seed(self.components())?)
+                }
+            }
+        }
+        Ok(LoadedEntity {
+            entity: entity.ok_or_else(|| de::Error::missing_field("entity"))?,
+            components: components.ok_or_else(|| de::Error::missing_field("components"))?,
+        })
+    }
+}
+
+struct EntitiesSeed<'r>(&'r SnapshotRegistry);
+
+impl<'de, 'r> DeserializeSeed<'de> for EntitiesSeed<'r> {
+    type Value = Vec<LoadedEntity<'r>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'r> Visitor<'de> for EntitiesSeed<'r> {
+    type Value = Vec<LoadedEntity<'r>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of entities")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut entities = Vec::new();
+        while let Some(entity) = seq.next_element_seed(EntitySeed(self.0))? {
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SnapshotField {
+    Entities,
+    Resources,
+}
+
+struct SnapshotSeed<'r>(&'r SnapshotRegistry);
+
+impl<'r> SnapshotSeed<'r> {
+    fn resources(&self) -> ErasedMapSeed<'r, ResourceRegistration> {
+        ErasedMapSeed {
+            registrations: &self.0.resources,
+            kind: "resource",
+        }
+    }
+}
+
+impl<'de, 'r> DeserializeSeed<'de> for SnapshotSeed<'r> {
+    type Value = LoadedSnapshot<'r>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("Snapshot", &["entities", "resources"], self)
+    }
+}
+
+impl<'de, 'r> Visitor<'de> for SnapshotSeed<'r> {
+    type Value = LoadedSnapshot<'r>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a world snapshot")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let entities = seq
+            .next_element_seed(EntitiesSeed(self.0))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let resources = seq
+            .next_element_seed(self.resources())?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(LoadedSnapshot {
+            entities,
+            resources,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entities = None;
+        let mut resources = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                SnapshotField::Entities => {
+                    entities = Some(map.next_value_seed(EntitiesSeed(self.0))?)
+                }
+                SnapshotField::Resources => {
+                    resources = Some(map.next_value_seed(self.resources())?)
+                }
+            }
+        }
+        Ok(LoadedSnapshot {
+            entities: entities.ok_or_else(|| de::Error::missing_field("entities"))?,
+            resources: resources.ok_or_else(|| de::Error::missing_field("resources"))?,
+        })
+    }
+}
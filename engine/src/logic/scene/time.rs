@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::snapshot::SerializeResource;
+
+// Scene time, advanced once per `Scene::update`. Anything that animates or expires reads this
+// instead of the wall clock, so a run only depends on its delta times, inputs and RNG seed
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Time {
+    elapsed: Duration,
+    delta_time: f32,
+    frame: u64,
+}
+
+impl Time {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    pub fn delta_time(&self) -> f32 {
+        self.delta_time
+    }
+
+    // Number of updates so far
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub(crate) fn advance(&mut self, delta_time: f32) {
+        self.elapsed += Duration::from_secs_f32(delta_time);
+        self.delta_time = delta_time;
+        self.frame += 1;
+    }
+}
+
+impl SerializeResource for Time {
+    const NAME: &'static str = "Time";
+}
@@ -1,169 +1,521 @@
 use std::{
     any::{type_name, Any, TypeId},
+    cell::{Cell, RefCell},
     collections::{HashMap, VecDeque},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use crate::{
     logic::ecs::{
-        component::{component::Component, model::Model},
+        borrow_cell::{BorrowCell, Ref, RefMut},
+        commands::{Command, Commands},
+        component::{component::Component, model::Model, parent::Parent},
         component_storage::{ComponentStorage, ComponentStorageTrait},
-        entity::entity::Entity,
-        query::{Query, QueryMut},
+        entity::{entity::Entity, entity_map::EntityMap},
+        query::{Query, QueryBorrow},
+    },
+    logic::scene::{
+        hierarchy, picking,
+        prefab::{self, PrefabInstance},
+        snapshot::{self, SnapshotFormat},
     },
+    math::vec2::Vec2,
     render::{mesh::Mesh, resource::resource_manager::ResourceManager},
+    shared::engine_error::EngineError,
 };
 
 extern crate noise;
+
+thread_local! {
+    // (last_change_tick, change_tick) of the system running on this thread while systems run in
+    // parallel, since the world's own ticks can only describe one system at a time
+    static SYSTEM_TICKS: Cell<Option<(u32, u32)>> = const { Cell::new(None) };
+    // Commands recorded by the system running on this thread, so a batch can apply them in the
+    // order the systems were added
+    static SYSTEM_COMMANDS: RefCell<Option<SystemCommands>> = const { RefCell::new(None) };
+}
+
+// What a system of a batch recorded for the world at `world`. Entities it reserves come from its
+// own lane of ids, every `lanes`th one starting at `lane`, so they don't depend on when the other
+// systems of the batch reserve theirs
+pub(crate) struct SystemCommands {
+    world: usize,
+    lane: usize,
+    lanes: usize,
+    reserved: usize,
+    commands: Vec<Command>,
+}
+
+struct EntitySlot {
+    generation: u32,
+    alive: bool,
+}
+
 pub struct World {
     pub entities: Vec<Entity>,
+    slots: Vec<EntitySlot>,
     available_ids: VecDeque<u32>,
-    pub components: HashMap<TypeId, Box<dyn ComponentStorageTrait + Send + Sync>>,
-    resources: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    // Number of ids handed out by `reserve_entity` that aren't alive yet
+    reserved_entities: AtomicUsize,
+    command_queue: Mutex<Vec<Command>>,
+    pub components: HashMap<TypeId, BorrowCell<Box<dyn ComponentStorageTrait + Send + Sync>>>,
+    resources: HashMap<TypeId, BorrowCell<Box<dyn Any + Send + Sync>>>,
+    change_tick: u32,
+    last_change_tick: u32,
 }
 
 impl World {
     pub fn load() -> Self {
         World {
-            entities: vec![Entity(0)], // Have 0 as a null entity
+            entities: vec![Entity::NULL], // Have 0 as a null entity
+            slots: vec![EntitySlot {
+                generation: 0,
+                alive: true,
+            }],
             available_ids: VecDeque::new(),
+            reserved_entities: AtomicUsize::new(0),
+            command_queue: Mutex::new(Vec::new()),
             components: HashMap::new(),
             resources: HashMap::new(),
+            change_tick: 1,
+            last_change_tick: 0,
         }
     }
 
+    // Tick stamped on components added or mutably accessed from now on
+    pub fn change_tick(&self) -> u32 {
+        SYSTEM_TICKS
+            .get()
+            .map_or(self.change_tick, |(_, tick)| tick)
+    }
+
+    pub fn increment_change_tick(&mut self) -> u32 {
+        self.change_tick = self.change_tick.wrapping_add(1);
+        self.change_tick
+    }
+
+    // `Changed` and `Added` queries match components touched after this tick
+    pub fn last_change_tick(&self) -> u32 {
+        SYSTEM_TICKS
+            .get()
+            .map_or(self.last_change_tick, |(tick, _)| tick)
+    }
+
+    pub fn set_last_change_tick(&mut self, tick: u32) {
+        self.last_change_tick = tick;
+    }
+
+    // Runs `f` with the ticks of one system on the current thread. The previous ticks are restored
+    // afterwards, in case `f` ends up running another system while it waits on the thread pool
+    pub(crate) fn with_system_ticks<R>(last_run: u32, this_run: u32, f: impl FnOnce() -> R) -> R {
+        let previous = SYSTEM_TICKS.replace(Some((last_run, this_run)));
+        let result = f();
+        SYSTEM_TICKS.set(previous);
+        result
+    }
+
+    // Runs `f` as the system on the given lane of a batch, returning what it recorded for this
+    // world instead of queueing it. See `apply_batch`
+    pub(crate) fn with_system_commands<R>(
+        &self,
+        lane: usize,
+        lanes: usize,
+        f: impl FnOnce() -> R,
+    ) -> (R, SystemCommands) {
+        let previous = SYSTEM_COMMANDS.replace(Some(SystemCommands {
+            world: self.address(),
+            lane,
+            lanes,
+            reserved: 0,
+            commands: Vec::new(),
+        }));
+        let result = f();
+        let recorded = SYSTEM_COMMANDS.replace(previous).unwrap();
+        (result, recorded)
+    }
+
+    fn address(&self) -> usize {
+        self as *const World as usize
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.slots
+            .get(entity.id as usize)
+            .is_some_and(|slot| slot.alive && slot.generation == entity.generation)
+    }
+
     pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
+        if !self.is_alive(entity) {
+            log::warn!(
+                "Attempted to add {} to dead entity {:?}",
+                type_name::<T>(),
+                entity
+            );
+            return;
+        }
+        // `Children` follow every `Parent` that is inserted, so they never have to be rebuilt
+        let parent = (&component as &dyn Any)
+            .downcast_ref::<Parent>()
+            .map(|parent| parent.parent);
+        if parent.is_some() {
+            hierarchy::detach(self, entity);
+        }
         let storage = self
             .components
             .entry(TypeId::of::<T>())
-            .or_insert(ComponentStorage::<T>::new());
+            .or_insert_with(|| BorrowCell::new(ComponentStorage::<T>::new()));
         storage
+            .get_mut()
             .as_any_mut()
             .downcast_mut::<ComponentStorage<T>>()
             .unwrap()
-            .add(entity, Box::new(component));
+            .insert(entity, component, self.change_tick);
+        if let Some(parent) = parent {
+            hierarchy::attach(self, entity, parent);
+        }
     }
 
-    pub fn get_component<T: Component>(&self, entity: Entity) -> Option<&T> {
-        self.components
-            .get(&TypeId::of::<T>())?
-            .as_any()
-            .downcast_ref::<ComponentStorage<T>>()?
-            .get(entity)?
-            .downcast_ref::<T>()
+    pub fn get_component<T: Component>(&self, entity: Entity) -> Option<Ref<'_, T>> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        let storage = self.get_storage::<T>()?;
+        storage.contains(entity).then(|| {
+            Ref::map(storage, |storage| {
+                storage.get(entity).expect("Component checked above")
+            })
+        })
     }
 
     pub fn remove_component<T: Component>(&mut self, entity: Entity) -> Option<T> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        if TypeId::of::<T>() == TypeId::of::<Parent>() {
+            hierarchy::detach(self, entity);
+        }
         self.components
             .get_mut(&TypeId::of::<T>())?
+            .get_mut()
             .as_any_mut()
             .downcast_mut::<ComponentStorage<T>>()?
             .remove(entity)
     }
 
-    pub fn get_component_mut<T: Component>(&self, entity: Entity) -> Option<&mut T> {
-        let storage = self
-            .components
-            .get(&TypeId::of::<T>())?
-            .as_any()
-            .downcast_ref::<ComponentStorage<T>>()?;
-        let ptr: *mut ComponentStorage<T> = storage as *const _ as *mut _;
-        unsafe { ptr.as_mut()?.get_mut(entity)?.downcast_mut::<T>() }
+    pub fn get_component_mut<T: Component>(&self, entity: Entity) -> Option<RefMut<'_, T>> {
+        if !self.is_alive(entity) {
+            return None;
+        }
+        let storage = self.get_storage_mut::<T>()?;
+        storage.contains(entity).then(|| {
+            RefMut::map(storage, |storage| {
+                storage
+                    .get_mut_marked(entity, self.change_tick())
+                    .expect("Component checked above")
+            })
+        })
     }
 
     pub fn create_entity(&mut self) -> Entity {
-        let entity_id = self
-            .available_ids
-            .pop_front()
-            .unwrap_or(self.entities.len() as u32);
-        let entity = Entity(entity_id);
+        self.flush_reserved_entities();
+        self.allocate_entity()
+    }
+
+    fn allocate_entity(&mut self) -> Entity {
+        let id = self.next_id();
+        let slot = &mut self.slots[id as usize];
+        slot.alive = true;
+        let entity = Entity::new(id, slot.generation);
         self.entities.push(entity);
         entity
     }
 
+    fn next_id(&mut self) -> u32 {
+        self.available_ids.pop_front().unwrap_or_else(|| {
+            self.slots.push(EntitySlot {
+                generation: 0,
+                alive: false,
+            });
+            self.slots.len() as u32 - 1
+        })
+    }
+
+    // Hands out the id `create_entity` would use next without touching the world, so entities can be
+    // created through `Commands` while the world is borrowed. Systems of a batch use their lane
+    pub fn reserve_entity(&self) -> Entity {
+        let lane_index = SYSTEM_COMMANDS.with_borrow_mut(|recorded| match recorded {
+            Some(recorded) if recorded.world == self.address() => {
+                let index = recorded.lane + recorded.reserved * recorded.lanes;
+                recorded.reserved += 1;
+                Some(index)
+            }
+            _ => None,
+        });
+        let index = match lane_index {
+            Some(index) => self.reserved_entities.load(Ordering::Relaxed) + index,
+            None => self.reserved_entities.fetch_add(1, Ordering::Relaxed),
+        };
+        match self.available_ids.get(index) {
+            Some(&id) => Entity::new(id, self.slots[id as usize].generation),
+            None => Entity::new(
+                (self.slots.len() + index - self.available_ids.len()) as u32,
+                0,
+            ),
+        }
+    }
+
+    fn flush_reserved_entities(&mut self) {
+        let reserved = std::mem::take(self.reserved_entities.get_mut());
+        for _ in 0..reserved {
+            self.allocate_entity();
+        }
+    }
+
+    pub fn commands(&self) -> Commands<'_> {
+        Commands::new(self)
+    }
+
+    pub(crate) fn push_command(&self, command: Command) {
+        let command = SYSTEM_COMMANDS.with_borrow_mut(|recorded| match recorded {
+            Some(recorded) if recorded.world == self.address() => {
+                recorded.commands.push(command);
+                None
+            }
+            _ => Some(command),
+        });
+        if let Some(command) = command {
+            self.command_queue.lock().unwrap().push(command);
+        }
+    }
+
+    // Makes the entities the systems of a batch reserved, in lane order, then applies their
+    // commands in the same order. Ids that no lane got to go back to the free list
+    pub(crate) fn apply_batch(&mut self, batch: Vec<SystemCommands>) {
+        self.flush_reserved_entities();
+        let lanes = batch.len();
+        let reserved: Vec<usize> = batch.iter().map(|recorded| recorded.reserved).collect();
+        let span = reserved
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(lane, &count)| lane + (count - 1) * lanes + 1)
+            .max()
+            .unwrap_or(0);
+        let mut skipped = Vec::new();
+        for index in 0..span {
+            if index / lanes < reserved[index % lanes] {
+                self.allocate_entity();
+            } else {
+                skipped.push(self.next_id());
+            }
+        }
+        self.available_ids.extend(skipped);
+
+        for recorded in batch {
+            self.command_queue
+                .get_mut()
+                .unwrap()
+                .extend(recorded.commands);
+        }
+        self.apply_commands();
+    }
+
+    // Applies recorded commands in order, including ones recorded while applying
+    pub fn apply_commands(&mut self) {
+        self.flush_reserved_entities();
+        loop {
+            let commands = std::mem::take(self.command_queue.get_mut().unwrap());
+            if commands.is_empty() {
+                break;
+            }
+            for command in commands {
+                command(self);
+                self.flush_reserved_entities();
+            }
+        }
+    }
+
     pub fn delete_entity(&mut self, entity: Entity) {
-        if let Some(idx) = self.entities.iter().position(|e| e.0 == entity.0) {
+        // Freed ids go to the back of the queue, so reserved ids must be taken out first
+        self.flush_reserved_entities();
+        if entity.is_null() || !self.is_alive(entity) {
+            return;
+        }
+        if let Some(idx) = self.entities.iter().position(|e| *e == entity) {
+            // Children of the entity keep their `Parent`, see `despawn_recursive`
+            hierarchy::detach(self, entity);
             if let Some(model_component) = self.remove_component::<Model>(entity) {
                 self.mesh_removed(model_component.mesh);
             }
             self.entities.swap_remove(idx);
-            self.available_ids.push_back(entity.0);
             for storage in self.components.values_mut() {
-                storage.remove_entity(entity);
+                storage.get_mut().remove_entity(entity);
             }
+            // Bump the generation so handles to the deleted entity can't alias its successor
+            let slot = &mut self.slots[entity.id as usize];
+            slot.alive = false;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.available_ids.push_back(entity.id);
         }
     }
 
+    // Deletes the entity and everything parented to it, as found through `Children`. A `Parent`
+    // changed in place is only followed once `HierarchySystem` has run
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        for descendant in hierarchy::descendants(self, entity) {
+            self.delete_entity(descendant);
+        }
+        self.delete_entity(entity);
+    }
+
+    // Reparents the entity and updates the `Children` of its old and new parent. A null parent
+    // detaches the entity, which also hides it. Fails if the entity would become its own ancestor
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) -> Result<(), EngineError> {
+        hierarchy::set_parent(self, child, parent)
+    }
+
+    pub fn remove_parent(&mut self, child: Entity) -> Option<Parent> {
+        self.remove_component::<Parent>(child)
+    }
+
+    // Fails with the first cycle of `Parent`s found
+    pub fn validate_hierarchy(&self) -> Result<(), EngineError> {
+        hierarchy::validate(self)
+    }
+
+    // Entities whose `Collider` is under a position in window pixels, front most first. Needs the
+    // camera matrices and `WindowSize` resources
+    pub fn pick(&self, screen_position: Vec2) -> Vec<Entity> {
+        picking::pick(self, screen_position)
+    }
+
     pub fn insert_resource<T: 'static + Any + Send + Sync>(&mut self, resource: T) {
-        self.resources.insert(TypeId::of::<T>(), Box::new(resource));
+        self.resources
+            .insert(TypeId::of::<T>(), BorrowCell::new(Box::new(resource)));
     }
 
-    pub fn get_resource<T: 'static + Any + Send + Sync>(&self) -> Option<&T> {
-        self.resources.get(&TypeId::of::<T>())?.downcast_ref::<T>()
+    pub fn get_resource<T: 'static + Any + Send + Sync>(&self) -> Option<Ref<'_, T>> {
+        let resource = self.resources.get(&TypeId::of::<T>())?.try_borrow();
+        let resource = resource.unwrap_or_else(|| {
+            panic!(
+                "Resource {:?} is already borrowed mutably",
+                type_name::<T>()
+            )
+        });
+        Some(Ref::map(resource, |resource| {
+            resource.downcast_ref::<T>().unwrap()
+        }))
     }
 
-    pub fn expect_resource<T: 'static + Any + Send + Sync>(&self) -> &T {
+    pub fn expect_resource<T: 'static + Any + Send + Sync>(&self) -> Ref<'_, T> {
         self.get_resource::<T>()
             .unwrap_or_else(|| panic!("Resource {:?} not found", type_name::<T>()))
     }
 
-    pub fn expect_resource_mut<T: 'static + Any + Send + Sync>(&mut self) -> &mut T {
+    pub fn expect_resource_mut<T: 'static + Any + Send + Sync>(&self) -> RefMut<'_, T> {
         self.get_resource_mut::<T>()
             .unwrap_or_else(|| panic!("Resource {:?} not found", type_name::<T>()))
     }
 
-    pub fn get_resource_mut<T: 'static + Any + Send + Sync>(&mut self) -> Option<&mut T> {
-        self.resources
-            .get_mut(&TypeId::of::<T>())?
-            .downcast_mut::<T>()
+    pub fn get_resource_mut<T: 'static + Any + Send + Sync>(&self) -> Option<RefMut<'_, T>> {
+        let resource = self.resources.get(&TypeId::of::<T>())?.try_borrow_mut();
+        let resource = resource
+            .unwrap_or_else(|| panic!("Resource {:?} is already borrowed", type_name::<T>()));
+        Some(RefMut::map(resource, |resource| {
+            resource.downcast_mut::<T>().unwrap()
+        }))
     }
 
-    pub fn get_resource_ptr<T: 'static + Any + Send + Sync>(&mut self) -> Option<*mut T> {
-        self.resources
-            .get_mut(&TypeId::of::<T>())?
-            .downcast_mut::<T>()
-            .map(|resource| resource as *mut T)
+    // Takes the resource out of the world while `f` runs, so `f` can mutate both
+    pub fn resource_scope<T: 'static + Any + Send + Sync, R>(
+        &mut self,
+        f: impl FnOnce(&mut World, &mut T) -> R,
+    ) -> R {
+        let mut resource = self
+            .resources
+            .remove(&TypeId::of::<T>())
+            .unwrap_or_else(|| panic!("Resource {:?} not found", type_name::<T>()));
+        let result = f(self, resource.get_mut().downcast_mut::<T>().unwrap());
+        self.resources.insert(TypeId::of::<T>(), resource);
+        result
     }
 
-    pub fn expect_resource_ptr<T: 'static + Any + Send + Sync>(&mut self) -> *mut T {
-        self.get_resource_ptr::<T>()
-            .unwrap_or_else(|| panic!("Resource {:?} not found", type_name::<T>()))
+    pub fn get_storage<T: Component>(&self) -> Option<Ref<'_, ComponentStorage<T>>> {
+        let storage = self.components.get(&TypeId::of::<T>())?.try_borrow();
+        let storage = storage.unwrap_or_else(|| {
+            panic!(
+                "Component storage {:?} is already borrowed mutably",
+                type_name::<T>()
+            )
+        });
+        Some(Ref::map(storage, |storage| {
+            storage
+                .as_any()
+                .downcast_ref::<ComponentStorage<T>>()
+                .unwrap()
+        }))
     }
 
-    pub fn get_storage<T: Component>(&self) -> Option<&ComponentStorage<T>> {
-        self.components
-            .get(&TypeId::of::<T>())?
-            .as_any()
-            .downcast_ref::<ComponentStorage<T>>()
+    pub fn get_storage_mut<T: Component>(&self) -> Option<RefMut<'_, ComponentStorage<T>>> {
+        let storage = self.components.get(&TypeId::of::<T>())?.try_borrow_mut();
+        let storage = storage.unwrap_or_else(|| {
+            panic!(
+                "Component storage {:?} is already borrowed",
+                type_name::<T>()
+            )
+        });
+        Some(RefMut::map(storage, |storage| {
+            storage
+                .as_any_mut()
+                .downcast_mut::<ComponentStorage<T>>()
+                .unwrap()
+        }))
     }
 
-    pub fn get_storage_ptr<T: Component>(&self) -> Option<*const ComponentStorage<T>> {
-        self.components
-            .get(&TypeId::of::<T>())?
-            .as_any()
-            .downcast_ref::<ComponentStorage<T>>()
-            .map(|storage| storage as *const ComponentStorage<T>)
+    pub fn query<Q: Query>(&self) -> QueryBorrow<'_, Q> {
+        QueryBorrow::new(self)
     }
 
-    pub fn get_storage_ptr_mut<T: Component>(&mut self) -> Option<*mut ComponentStorage<T>> {
-        self.components
-            .get_mut(&TypeId::of::<T>())?
-            .as_any_mut()
-            .downcast_mut::<ComponentStorage<T>>()
-            .map(|storage| storage as *mut ComponentStorage<T>)
+    // Writes the components and resources registered in the world's `SnapshotRegistry`
+    pub fn save(&self, format: SnapshotFormat) -> Result<Vec<u8>, EngineError> {
+        snapshot::save(self, format)
+    }
+
+    // Spawns the saved entities as new entities and replaces the saved resources. Entity
+    // references in loaded components are remapped with the returned map
+    pub fn load_snapshot(
+        &mut self,
+        data: &[u8],
+        format: SnapshotFormat,
+    ) -> Result<EntityMap, EngineError> {
+        snapshot::load(self, data, format)
+    }
+
+    // Prefabs are spawned through commands, see `PrefabLibrary` for the file format
+    pub fn spawn_prefab(&self, name: &str) -> Result<Entity, EngineError> {
+        self.instantiate_prefab(name).map(|instance| instance.root)
     }
 
-    pub fn query_mut<'a, T: QueryMut<'a>>(&mut self) -> T::Iterator {
-        T::create_query(self)
+    pub fn instantiate_prefab(&self, name: &str) -> Result<PrefabInstance, EngineError> {
+        let mut resource_manager = self.get_resource_mut::<ResourceManager>();
+        prefab::instantiate(self, name, resource_manager.as_deref_mut())
     }
 
-    pub fn query<'a, T: Query<'a>>(&self) -> T::Iterator {
-        T::create_query(self)
+    // For scene initialization, before the `ResourceManager` is added to the world
+    pub fn instantiate_prefab_with(
+        &self,
+        name: &str,
+        resource_manager: &mut ResourceManager,
+    ) -> Result<PrefabInstance, EngineError> {
+        prefab::instantiate(self, name, Some(resource_manager))
     }
 
     fn mesh_removed(&mut self, mesh: Arc<Mesh>) {
         if let Some(mesh) = Arc::into_inner(mesh) {
-            if let Some(resource_manager) = self.get_resource_mut::<ResourceManager>() {
+            if let Some(mut resource_manager) = self.get_resource_mut::<ResourceManager>() {
                 resource_manager.unload_mesh(mesh);
             }
         }
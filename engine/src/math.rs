@@ -1,3 +1,4 @@
+pub mod mat4;
 pub mod rect;
 pub mod transformation;
 pub mod vec2;
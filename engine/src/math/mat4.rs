@@ -0,0 +1,187 @@
+use std::ops::Mul;
+
+use crate::logic::ecs::component::transform::Transform;
+
+use super::{vec2::Vec2, vec3::Vec3};
+
+// Column major like OpenGL, so `cols[3]` holds the translation and `a * b` applies `b` first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        cols: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub const fn from_cols_array(cols: [[f32; 4]; 4]) -> Self {
+        Self { cols }
+    }
+
+    pub const fn to_cols_array(&self) -> [[f32; 4]; 4] {
+        self.cols
+    }
+
+    pub fn from_translation(position: Vec3) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.cols[3] = [position.x, position.y, position.z, 1.0];
+        matrix
+    }
+
+    // Clockwise for positive angles, like `Vec2::rotated`
+    pub fn from_rotation_z(rotation: f32) -> Self {
+        let (sin, cos) = rotation.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.cols[0] = [cos, -sin, 0.0, 0.0];
+        matrix.cols[1] = [sin, cos, 0.0, 0.0];
+        matrix
+    }
+
+    pub fn from_scale(scale: Vec2) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.cols[0][0] = scale.x;
+        matrix.cols[1][1] = scale.y;
+        matrix
+    }
+
+    // Scales, then rotates, then translates. Flipping is left to the shaders
+    pub fn from_transform(transform: &Transform) -> Self {
+        Self::from_translation(transform.position)
+            * Self::from_rotation_z(transform.rotation)
+            * Self::from_scale(transform.scale)
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut result = [[0.0; 4]; 4];
+        for (col, values) in result.iter_mut().enumerate() {
+            for (row, value) in values.iter_mut().enumerate() {
+                *value = self.cols[row][col];
+            }
+        }
+        Self::from_cols_array(result)
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.cofactors().1
+    }
+
+    // None when the matrix is singular, e.g. for a zero scale
+    pub fn inverse(&self) -> Option<Self> {
+        let (cofactors, determinant) = self.cofactors();
+        if determinant.abs() <= f32::EPSILON * f32::EPSILON {
+            return None;
+        }
+        // The adjugate is the transposed cofactor matrix
+        let mut result = [[0.0; 4]; 4];
+        for (col, values) in result.iter_mut().enumerate() {
+            for (row, value) in values.iter_mut().enumerate() {
+                *value = cofactors[row][col] / determinant;
+            }
+        }
+        Some(Self::from_cols_array(result))
+    }
+
+    // Divides by w, which only matters for projections
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        let [x, y, z, w] = self.mul_vec4([point.x, point.y, point.z, 1.0]);
+        if w != 0.0 && w != 1.0 {
+            Vec3::new(x / w, y / w, z / w)
+        } else {
+            Vec3::new(x, y, z)
+        }
+    }
+
+    // Ignores the translation
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        let [x, y, z, _] = self.mul_vec4([vector.x, vector.y, vector.z, 0.0]);
+        Vec3::new(x, y, z)
+    }
+
+    // Inverse of `from_transform` for the 2D part. A mirrored matrix comes back with a negative
+    // x scale
+    pub fn decompose(&self) -> (Vec3, f32, Vec2) {
+        let [x_axis_x, x_axis_y, ..] = self.cols[0];
+        let [y_axis_x, y_axis_y, ..] = self.cols[1];
+        let mirrored = x_axis_x * y_axis_y - x_axis_y * y_axis_x < 0.0;
+        let scale_x = x_axis_x.hypot(x_axis_y);
+        let scale_y = y_axis_x.hypot(y_axis_y);
+        let rotation = y_axis_x.atan2(y_axis_y);
+        let [x, y, z, _] = self.cols[3];
+        (
+            Vec3::new(x, y, z),
+            rotation,
+            Vec2::new(if mirrored { -scale_x } else { scale_x }, scale_y),
+        )
+    }
+
+    fn mul_vec4(&self, vector: [f32; 4]) -> [f32; 4] {
+        let mut result = [0.0; 4];
+        for (col, component) in self.cols.iter().zip(vector) {
+            for (value, entry) in result.iter_mut().zip(col) {
+                *value += entry * component;
+            }
+        }
+        result
+    }
+
+    // Signed cofactors in the same layout as `cols`, with the determinant expanded along the
+    // first row
+    fn cofactors(&self) -> ([[f32; 4]; 4], f32) {
+        let m = &self.cols;
+        let mut cofactors = [[0.0; 4]; 4];
+        for (col, values) in cofactors.iter_mut().enumerate() {
+            for (row, value) in values.iter_mut().enumerate() {
+                let mut minor = [[0.0; 3]; 3];
+                for (minor_col, source_col) in (0..4).filter(|&c| c != col).enumerate() {
+                    for (minor_row, source_row) in (0..4).filter(|&r| r != row).enumerate() {
+                        minor[minor_col][minor_row] = m[source_col][source_row];
+                    }
+                }
+                let determinant = minor[0][0]
+                    * (minor[1][1] * minor[2][2] - minor[2][1] * minor[1][2])
+                    - minor[1][0] * (minor[0][1] * minor[2][2] - minor[2][1] * minor[0][2])
+                    + minor[2][0] * (minor[0][1] * minor[1][2] - minor[1][1] * minor[0][2]);
+                let sign = if (row + col) % 2 == 0 { 1.0 } else { -1.0 };
+                *value = sign * determinant;
+            }
+        }
+        let determinant = (0..4).map(|col| cofactors[col][0] * m[col][0]).sum();
+        (cofactors, determinant)
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        let mut result = [[0.0; 4]; 4];
+        for (col, values) in result.iter_mut().enumerate() {
+            *values = self.mul_vec4(other.cols[col]);
+        }
+        Mat4::from_cols_array(result)
+    }
+}
+
+impl From<[[f32; 4]; 4]> for Mat4 {
+    fn from(cols: [[f32; 4]; 4]) -> Self {
+        Self::from_cols_array(cols)
+    }
+}
+
+impl From<Mat4> for [[f32; 4]; 4] {
+    fn from(matrix: Mat4) -> Self {
+        matrix.cols
+    }
+}
@@ -1,5 +1,6 @@
 use super::vec2::Vec2;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rect {
     pub bottom_left: Vec2,
     pub top_right: Vec2,
@@ -12,4 +13,39 @@ impl Rect {
             top_right: Vec2::zero(),
         }
     }
+
+    // The smallest rect containing all points, `zero` for none
+    pub fn from_points(points: &[Vec2]) -> Self {
+        let Some(first) = points.first() else {
+            return Self::zero();
+        };
+        points.iter().fold(
+            Rect {
+                bottom_left: *first,
+                top_right: *first,
+            },
+            |rect, point| Rect {
+                bottom_left: Vec2::new(
+                    rect.bottom_left.x.min(point.x),
+                    rect.bottom_left.y.min(point.y),
+                ),
+                top_right: Vec2::new(rect.top_right.x.max(point.x), rect.top_right.y.max(point.y)),
+            },
+        )
+    }
+
+    pub fn grown(&self, amount: f32) -> Self {
+        Rect {
+            bottom_left: self.bottom_left - Vec2::new(amount, amount),
+            top_right: self.top_right + Vec2::new(amount, amount),
+        }
+    }
+
+    // Rects that only touch overlap as well
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.bottom_left.x <= other.top_right.x
+            && other.bottom_left.x <= self.top_right.x
+            && self.bottom_left.y <= other.top_right.y
+            && other.bottom_left.y <= self.top_right.y
+    }
 }
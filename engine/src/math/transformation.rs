@@ -3,7 +3,7 @@ use crate::logic::ecs::{
     entity::entity::Entity,
 };
 
-use super::{vec2::Vec2, vec3::Vec3};
+use super::{mat4::Mat4, vec2::Vec2, vec3::Vec3};
 
 pub fn create_translation_matrix(position: Vec3) -> [[f32; 4]; 4] {
     [
@@ -36,18 +36,6 @@ pub fn create_view_matrix(position: Vec3) -> [[f32; 4]; 4] {
     create_translation_matrix(-position)
 }
 
-fn multiply_matrix(result: &mut [[f32; 4]; 4], other: &[[f32; 4]; 4]) {
-    let mut tmp = [[0.0; 4]; 4];
-    for i in 0..4 {
-        for j in 0..4 {
-            for k in 0..4 {
-                tmp[i][j] += result[i][k] * other[k][j];
-            }
-        }
-    }
-    *result = tmp;
-}
-
 pub fn create_ortographic_projection_matrix(
     aspect_ratio: f32,
     near: f32,
@@ -79,16 +67,18 @@ pub fn create_transform_matrix(
     parent_component: Option<&Transform>,
 ) -> [[f32; 4]; 4] {
     let inherited = calc_inherited_transform(model, parent_component);
+    Mat4::from_transform(&inherited).to_cols_array()
+}
 
-    let translation_matrix = create_translation_matrix(inherited.position);
-    let rotation_matrix = create_rotation_matrix(inherited.rotation);
-    let scale_matrix = create_scale_matrix(inherited.scale);
-
-    let mut model_matrix = scale_matrix;
-    multiply_matrix(&mut model_matrix, &rotation_matrix);
-    multiply_matrix(&mut model_matrix, &translation_matrix);
-
-    model_matrix
+// Blends two model matrices elementwise. Close enough for the small changes of a single step
+pub fn lerp_matrix(from: &[[f32; 4]; 4], to: &[[f32; 4]; 4], alpha: f32) -> [[f32; 4]; 4] {
+    let mut result = [[0.0; 4]; 4];
+    for i in 0..4 {
+        for j in 0..4 {
+            result[i][j] = from[i][j] + (to[i][j] - from[i][j]) * alpha;
+        }
+    }
+    result
 }
 
 pub fn calc_inherited_transform(
@@ -122,17 +112,27 @@ pub fn calc_inherited_transform(
     }
 }
 
+// None if the entity or an ancestor has no transform, or if the ancestors form a cycle, which
+// `World::validate_hierarchy` reports
 pub fn get_world_transform(
     entity: Entity,
     get_transform: &dyn Fn(Entity) -> Option<Transform>,
     get_parent: &dyn Fn(Entity) -> Option<Parent>,
 ) -> Option<Transform> {
-    let transform = get_transform(entity)?;
-    match get_parent(entity) {
-        Some(parent) if parent.parent != Entity(0) => {
-            let parent_world = get_world_transform(parent.parent, get_transform, get_parent)?;
-            Some(calc_inherited_transform(&transform, Some(&parent_world)))
+    let mut chain = vec![entity];
+    while let Some(parent) = get_parent(*chain.last().unwrap()) {
+        if parent.parent.is_null() {
+            break;
+        }
+        if chain.contains(&parent.parent) {
+            return None;
         }
-        _ => Some(transform),
+        chain.push(parent.parent);
+    }
+    // Ancestors are applied from the root down
+    let mut world_transform = get_transform(chain.pop()?)?;
+    while let Some(entity) = chain.pop() {
+        world_transform = calc_inherited_transform(&get_transform(entity)?, Some(&world_transform));
     }
+    Some(world_transform)
 }
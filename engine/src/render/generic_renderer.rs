@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ffi::CString;
+use std::time::Duration;
 
 use gl::types::{GLint, GLuint};
 
@@ -61,11 +62,11 @@ impl GenericRenderer {
             ))
     }
 
-    pub unsafe fn render(&self, entities: Vec<RenderEntity>) {
+    pub unsafe fn render(&self, entities: Vec<RenderEntity>, elapsed: Duration) {
         let mut last_shader: GLuint = 0;
         let mut last_vao: GLuint = 0;
         for renderable in entities {
-            self.render_entity(&renderable, &mut last_shader, &mut last_vao);
+            self.render_entity(&renderable, elapsed, &mut last_shader, &mut last_vao);
         }
 
         gl::DisableVertexAttribArray(0);
@@ -77,6 +78,7 @@ impl GenericRenderer {
     pub unsafe fn render_entity(
         &self,
         renderable: &RenderEntity,
+        elapsed: Duration,
         last_shader: &mut GLuint,
         last_vao: &mut GLuint,
     ) {
@@ -134,7 +136,10 @@ impl GenericRenderer {
             }
             Texture::AnimatedTexture(animated_texture) => {
                 gl::ActiveTexture(gl::TEXTURE0);
-                gl::BindTexture(gl::TEXTURE_2D, animated_texture.current_texture().get_id());
+                gl::BindTexture(
+                    gl::TEXTURE_2D,
+                    animated_texture.current_texture(elapsed).get_id(),
+                );
             }
             Texture::GradientTexture(_) => {}
         }
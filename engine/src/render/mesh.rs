@@ -35,6 +35,17 @@ impl Mesh {
         };
     }
 
+    // A mesh without GPU buffers, handed out when nothing is rendered
+    pub(crate) fn placeholder(vertex_count: usize) -> Self {
+        Self {
+            vao: 0,
+            vert_vbo: 0,
+            uvs_vbo: None,
+            ebo: 0,
+            vertex_count: vertex_count as GLsizei,
+        }
+    }
+
     fn store_data(attribute: u32, dimensions: i32, data: &[f32]) -> GLuint {
         let mut vbo: GLuint = 0;
         unsafe {
@@ -1,6 +1,9 @@
 use std::f32::consts::PI;
 
-use crate::render::model::sprite;
+use crate::{math::vec2::Vec2, render::model::sprite};
+
+// Vertices, line indices and texture coordinates
+pub type Wireframe = (Vec<f32>, Vec<u32>, Vec<f32>);
 
 fn connecting_indices(num: u32) -> Vec<u32> {
     let mut indices = Vec::new();
@@ -52,3 +55,53 @@ pub fn capsule(width: f32, height: f32, segments: u32) -> (Vec<f32>, Vec<u32>, V
 
     (vertices, indices, vec![])
 }
+
+pub fn circle(radius: f32, segments: u32) -> (Vec<f32>, Vec<u32>, Vec<f32>) {
+    let mut vertices = Vec::new();
+    for i in 0..segments {
+        let theta = (i as f32 / segments as f32) * 2.0 * PI;
+        vertices.push(radius * theta.cos());
+        vertices.push(radius * theta.sin());
+        vertices.push(sprite::Z_DEFAULT);
+    }
+    (vertices, connecting_indices(segments), vec![])
+}
+
+pub fn polygon(points: &[Vec2]) -> (Vec<f32>, Vec<u32>, Vec<f32>) {
+    let vertices = points
+        .iter()
+        .flat_map(|point| [point.x, point.y, sprite::Z_DEFAULT])
+        .collect();
+    (vertices, connecting_indices(points.len() as u32), vec![])
+}
+
+// The surface through the heights and the bottom of the solid below it
+pub fn heightfield(width: f32, heights: &[f32]) -> Wireframe {
+    let bottom = heights.iter().copied().fold(f32::INFINITY, f32::min) - width;
+    let step = width / (heights.len().max(2) - 1) as f32;
+    let surface = heights
+        .iter()
+        .enumerate()
+        .map(|(i, height)| Vec2::new(-width / 2.0 + step * i as f32, *height));
+    let outline: Vec<Vec2> = surface
+        .chain([
+            Vec2::new(width / 2.0, bottom),
+            Vec2::new(-width / 2.0, bottom),
+        ])
+        .collect();
+    polygon(&outline)
+}
+
+// One line mesh of several wireframes, each moved by its offset
+pub fn merged(parts: Vec<(Vec2, Wireframe)>) -> Wireframe {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+    for (offset, (part_vertices, part_indices, _)) in parts {
+        let first = (vertices.len() / 3) as u32;
+        indices.extend(part_indices.iter().map(|index| index + first));
+        for vertex in part_vertices.chunks(3) {
+            vertices.extend([vertex[0] + offset.x, vertex[1] + offset.y, vertex[2]]);
+        }
+    }
+    (vertices, indices, vec![])
+}
@@ -4,6 +4,8 @@ use std::ffi::CString;
 use gl::types::{GLsizeiptr, GLuint, GLvoid};
 use glutin::display::{Display, GlDisplay};
 
+use crate::math::transformation;
+use crate::render::resource::shader::material_parameter::MaterialParameter;
 use crate::render::resource::texture::texture::StaticTexture;
 use crate::render::{frame_buffer::Framebuffer, generic_renderer::GenericRenderer};
 use crate::shared::extracted_frame::ExtractedFrame;
@@ -72,7 +74,12 @@ impl Renderer {
             }
             gl::Enable(gl::DEPTH_TEST);
             self.bind_uniform_buffers();
-            self.generic_renderer.render(extracted_frame.entities);
+            let entities = Self::interpolate(
+                extracted_frame.entities,
+                extracted_frame.interpolation_alpha,
+            );
+            self.generic_renderer
+                .render(entities, extracted_frame.elapsed);
             if postprocess_config.is_some() {
                 self.frame_buffer.blit();
                 self.frame_buffer.unbind();
@@ -87,17 +94,36 @@ impl Renderer {
                     self.frame_buffer.get_height() as u32,
                 )
                 .into();
-                self.generic_renderer.render(vec![RenderEntity {
-                    mesh: self.frame_buffer.get_mesh(),
-                    material,
-                    z_index: 0.0,
-                }]);
+                self.generic_renderer.render(
+                    vec![RenderEntity {
+                        mesh: self.frame_buffer.get_mesh(),
+                        material,
+                        z_index: 0.0,
+                        previous_model_matrix: None,
+                    }],
+                    extracted_frame.elapsed,
+                );
             }
             self.unbind_uniform_buffers();
             gl_check_error!();
         };
     }
 
+    fn interpolate(mut entities: Vec<RenderEntity>, alpha: f32) -> Vec<RenderEntity> {
+        for entity in entities.iter_mut() {
+            let Some(previous) = entity.previous_model_matrix else {
+                continue;
+            };
+            if let Some(MaterialParameter::Mat4(current)) =
+                entity.material.get_param("uModelMatrix")
+            {
+                let matrix = transformation::lerp_matrix(&previous, current, alpha);
+                entity.material.set_param("uModelMatrix", matrix);
+            }
+        }
+        entities
+    }
+
     fn refresh_buffers(&mut self, extracted_frame: &ExtractedFrame) {
         // If window resize => it has to be camera update too
         if let Some(new_window_size) = extracted_frame.window_size.clone() {
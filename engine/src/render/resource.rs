@@ -1,3 +1,4 @@
+pub(crate) mod null_resource_loader;
 pub(crate) mod resource_command;
 pub(crate) mod resource_loader;
 pub mod resource_manager;
@@ -0,0 +1,76 @@
+use flume::Receiver;
+use include_assets::NamedArchive;
+
+use crate::render::{
+    mesh::Mesh,
+    resource::{
+        resource_command::ResourceCommand,
+        resource_loader::load_file,
+        shader::shader_program::{ShaderProgram, ShaderProgramHandle},
+        texture::texture::{AnimatedTexture, StaticTexture},
+    },
+};
+
+// Answers resource commands with placeholders instead of touching GL, for running without a window.
+// Data files are still read from the attached archives
+pub struct NullResourceLoader {
+    loader_rx: Receiver<ResourceCommand>,
+    archives: Vec<NamedArchive>,
+}
+
+impl NullResourceLoader {
+    pub fn new(loader_rx: Receiver<ResourceCommand>) -> Self {
+        NullResourceLoader {
+            loader_rx,
+            archives: Vec::new(),
+        }
+    }
+
+    // Blocks until every sender is gone
+    pub fn run(&mut self) {
+        for command in self.loader_rx.iter() {
+            match command {
+                ResourceCommand::LoadStaticTexture { response_tx, .. } => {
+                    let _ = response_tx.send(Ok(StaticTexture::new(0, 1, 1).into()));
+                }
+                ResourceCommand::LoadAnimatedTexture {
+                    texture_names,
+                    animation_time,
+                    response_tx,
+                } => {
+                    let textures = vec![StaticTexture::new(0, 1, 1); texture_names.len()];
+                    let texture = AnimatedTexture::new(textures, animation_time);
+                    let _ = response_tx.send(Ok(texture.into()));
+                }
+                ResourceCommand::AttachArchive { archive } => self.archives.push(archive),
+                ResourceCommand::LoadMesh {
+                    indices,
+                    response_tx,
+                    ..
+                } => {
+                    let _ = response_tx.send(Ok(Mesh::placeholder(indices.len())));
+                }
+                ResourceCommand::UnloadMesh { .. } => {}
+                ResourceCommand::LoadFile {
+                    file_name,
+                    response_tx,
+                } => {
+                    let _ = response_tx.send(load_file(&self.archives, &file_name));
+                }
+                ResourceCommand::LoadShader {
+                    shader_configuration,
+                    response_tx,
+                } => {
+                    let handle = ShaderProgramHandle {
+                        id: 0,
+                        has_tesselation: shader_configuration.tess_control_shader_name.is_some(),
+                    };
+                    let _ = response_tx.send(Ok(ShaderProgram::new(
+                        handle,
+                        shader_configuration.parameter_schema,
+                    )));
+                }
+            }
+        }
+    }
+}
@@ -38,4 +38,9 @@ pub enum ResourceCommand {
     UnloadMesh {
         mesh: Mesh,
     },
+    // Raw contents of a data file, e.g. a prefab
+    LoadFile {
+        file_name: String,
+        response_tx: Sender<Result<Vec<u8>, EngineError>>,
+    },
 }
@@ -115,7 +115,27 @@ impl ResourceLoader {
                     }
                     let _ = response_tx.send(shader_program);
                 }
+                ResourceCommand::LoadFile {
+                    file_name,
+                    response_tx,
+                } => {
+                    let _ = response_tx.send(load_file(&self.archives, &file_name));
+                }
             }
         }
     }
 }
+
+// Searches the archive attached last first, like textures and shaders
+pub(crate) fn load_file(
+    archives: &[NamedArchive],
+    file_name: &str,
+) -> Result<Vec<u8>, EngineError> {
+    let path = file_name.replace('/', "\\");
+    archives
+        .iter()
+        .rev()
+        .find_map(|archive| archive.get(&path))
+        .map(<[u8]>::to_vec)
+        .ok_or(EngineError::FileNotFound(path))
+}
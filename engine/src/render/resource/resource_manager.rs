@@ -5,6 +5,7 @@ use include_assets::NamedArchive;
 
 use crate::{
     logic::ecs::component::collider::ColliderShape,
+    math::vec2::Vec2,
     render::{
         mesh::Mesh,
         model::sprite,
@@ -12,8 +13,12 @@ use crate::{
             parameter_schema::ParameterSchema, shader_configuration::ShaderConfiguration,
             shader_parameter_type::ShaderParameterType, shader_program::ShaderProgram,
         },
-        resource::{resource_command::ResourceCommand, texture::texture::StaticTexture},
+        resource::{
+            null_resource_loader::NullResourceLoader, resource_command::ResourceCommand,
+            texture::texture::StaticTexture,
+        },
     },
+    shared::engine_error::EngineError,
 };
 
 use super::{resource_provider::ResourceProvider, texture::texture::Texture};
@@ -22,6 +27,10 @@ use super::{resource_provider::ResourceProvider, texture::texture::Texture};
 pub enum ColliderShapeKey {
     Capsule2D(u32, u32),
     Rect(u32, u32),
+    Circle(u32),
+    Polygon(Vec<(i32, i32)>),
+    Compound(Vec<((i32, i32), ColliderShapeKey)>),
+    Heightfield(u32, Vec<i32>),
 }
 
 impl ColliderShapeKey {
@@ -33,8 +42,29 @@ impl ColliderShapeKey {
             ColliderShape::Rect { width, height } => {
                 ColliderShapeKey::Rect((*width * 100.0) as u32, (*height * 100.0) as u32)
             }
+            ColliderShape::Circle { radius } => ColliderShapeKey::Circle((*radius * 100.0) as u32),
+            ColliderShape::Polygon { points } => {
+                ColliderShapeKey::Polygon(points.iter().map(Self::point_key).collect())
+            }
+            ColliderShape::Compound { parts } => ColliderShapeKey::Compound(
+                parts
+                    .iter()
+                    .map(|part| (Self::point_key(&part.offset), Self::from_shape(&part.shape)))
+                    .collect(),
+            ),
+            ColliderShape::Heightfield { width, heights } => ColliderShapeKey::Heightfield(
+                (*width * 100.0) as u32,
+                heights
+                    .iter()
+                    .map(|height| (*height * 100.0) as i32)
+                    .collect(),
+            ),
         }
     }
+
+    fn point_key(point: &Vec2) -> (i32, i32) {
+        ((point.x * 100.0) as i32, (point.y * 100.0) as i32)
+    }
 }
 
 pub struct ResourceManager {
@@ -72,6 +102,13 @@ impl ResourceManager {
         }
     }
 
+    // Backed by placeholder resources on a thread of its own, so no window or GL context is needed
+    pub fn headless() -> Self {
+        let (loader_tx, loader_rx) = flume::unbounded();
+        std::thread::spawn(move || NullResourceLoader::new(loader_rx).run());
+        Self::new(loader_tx)
+    }
+
     pub fn get_collider_mesh(&self, key: ColliderShapeKey) -> Option<&Arc<Mesh>> {
         self.collider_meshes.get(&key)
     }
@@ -309,6 +346,16 @@ impl ResourceManager {
         self.send_resource_command(ResourceCommand::UnloadMesh { mesh });
     }
 
+    pub fn load_file(&mut self, file_name: &str) -> Result<Vec<u8>, EngineError> {
+        let (tx, rx) = flume::bounded(1);
+        self.send_resource_command(ResourceCommand::LoadFile {
+            file_name: file_name.to_string(),
+            response_tx: tx,
+        });
+        rx.recv()
+            .unwrap_or_else(|_| Err(EngineError::FileNotFound(file_name.to_string())))
+    }
+
     pub fn load_mesh_from_texture(&mut self, texture: &StaticTexture) -> Option<Mesh> {
         let (width, height) = texture.get_normalized_dimensions();
         let (vertices, indices, uvs) = sprite::rectangle(width, height);
@@ -1,9 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    math::{vec2::Vec2, vec3::Vec3},
+    math::{mat4::Mat4, vec2::Vec2, vec3::Vec3},
     render::resource::shader::shader_parameter_type::ShaderParameterType,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MaterialParameter {
     Float(f32),
     Vec2(Vec2),
@@ -60,6 +62,12 @@ impl From<[[f32; 4]; 4]> for MaterialParameter {
     }
 }
 
+impl From<Mat4> for MaterialParameter {
+    fn from(v: Mat4) -> Self {
+        MaterialParameter::Mat4(v.to_cols_array())
+    }
+}
+
 impl From<i32> for MaterialParameter {
     fn from(v: i32) -> Self {
         MaterialParameter::Int(v)
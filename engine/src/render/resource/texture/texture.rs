@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::time::Duration;
 
 use crate::math::vec3::Vec3;
 
@@ -94,7 +94,8 @@ impl StaticTexture {
 pub struct AnimatedTexture {
     pub textures: Vec<StaticTexture>,
     pub animation_time: u128,
-    pub animation_start_time: Instant,
+    // Scene time the animation started at
+    pub animation_start_time: Duration,
 }
 
 impl AnimatedTexture {
@@ -102,16 +103,16 @@ impl AnimatedTexture {
         AnimatedTexture {
             textures,
             animation_time,
-            animation_start_time: Instant::now(),
+            animation_start_time: Duration::ZERO,
         }
     }
-    pub fn current_texture(&self) -> StaticTexture {
+    pub fn current_texture(&self, elapsed: Duration) -> StaticTexture {
         let texture_count = self.textures.len();
         if self.animation_time == 0 {
             return *self.textures.get(0).unwrap();
         }
-        let texture_index = ((Instant::now()
-            .duration_since(self.animation_start_time)
+        let texture_index = ((elapsed
+            .saturating_sub(self.animation_start_time)
             .as_millis()
             % self.animation_time) as f32
             / (self.animation_time as f32 / texture_count as f32))
@@ -1,10 +1,18 @@
 use std::fmt::Display;
 
+use crate::logic::ecs::entity::entity::Entity;
+
 #[derive(Debug)]
 pub enum EngineError {
     Generic(String),
     FileNotFound(String),
     ShaderCompilation(String, String), // (shader path, error log)
+    DuplicateSystem(String),
+    UnknownSystem(String, String), // (system, system it is ordered against)
+    SystemCycle(Vec<String>),
+    HierarchyCycle(Vec<Entity>), // Starts and ends with the same entity
+    Io(String, String),          // (path, error)
+    Parse(String, String),       // (source, error)
 }
 
 impl Display for EngineError {
@@ -15,6 +23,23 @@ impl Display for EngineError {
             EngineError::ShaderCompilation(path, err) => {
                 write!(f, "Shader compilation failed: '{}': {}", path, err)
             }
+            EngineError::DuplicateSystem(name) => {
+                write!(f, "System '{}' is registered more than once", name)
+            }
+            EngineError::UnknownSystem(name, other) => write!(
+                f,
+                "System '{}' is ordered against '{}', which isn't in the same stage",
+                name, other
+            ),
+            EngineError::SystemCycle(cycle) => {
+                write!(f, "System ordering cycle: {}", cycle.join(" -> "))
+            }
+            EngineError::HierarchyCycle(cycle) => {
+                let ids: Vec<String> = cycle.iter().map(|entity| entity.id.to_string()).collect();
+                write!(f, "Parent cycle between entities: {}", ids.join(" -> "))
+            }
+            EngineError::Io(path, err) => write!(f, "I/O error: '{}': {}", path, err),
+            EngineError::Parse(source, err) => write!(f, "Failed to parse {}: {}", source, err),
         }
     }
 }
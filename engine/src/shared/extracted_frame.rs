@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::shared::{
     postprocess_config::PostprocessConfig, render_entity::RenderEntity,
     uniform_buffer_render_data::UniformBufferRenderData, window_size::WindowSize,
@@ -9,4 +11,8 @@ pub struct ExtractedFrame {
     pub uniform_buffers: Vec<UniformBufferRenderData>,
     pub window_size: Option<WindowSize>,
     pub postprocess_pass: Option<PostprocessConfig>,
+    // How far rendering is between the previous and the latest fixed step
+    pub interpolation_alpha: f32,
+    // Scene time the frame was extracted at, for animated textures
+    pub elapsed: Duration,
 }
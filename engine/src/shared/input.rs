@@ -1,3 +1,6 @@
+pub mod action_map;
+pub mod gamepad;
 pub mod input_event;
 pub mod input_handler;
+pub mod input_recording;
 pub mod input_state;
@@ -0,0 +1,201 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
+
+use serde::{Deserialize, Serialize};
+use winit::{event::MouseButton, keyboard::Key};
+
+use crate::shared::{
+    engine_error::EngineError,
+    input::{
+        gamepad::{GamepadAxis, GamepadButton},
+        input_state::InputState,
+    },
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(Key),
+    Mouse(MouseButton),
+    // Any finger on the screen
+    Touch,
+    // On any connected gamepad
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    fn is_pressed(&self, input: &InputState) -> bool {
+        match self {
+            Binding::Key(key) => input.is_pressed(key.clone()),
+            Binding::Mouse(button) => input.is_mouse_pressed(*button),
+            Binding::Touch => input.is_touched(),
+            Binding::Gamepad(button) => input.is_gamepad_pressed(*button),
+        }
+    }
+
+    // Keys are stored lowercase by `InputState`
+    fn normalized(self) -> Self {
+        match self {
+            Binding::Key(Key::Character(c)) => {
+                Binding::Key(Key::Character(c.to_lowercase().into()))
+            }
+            binding => binding,
+        }
+    }
+}
+
+// -1 while only a negative binding is held, 1 while only a positive one is. Otherwise the analog
+// axis furthest from its center, so a half tilted stick gives 0.5
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Axis {
+    #[serde(default)]
+    pub negative: Vec<Binding>,
+    #[serde(default)]
+    pub positive: Vec<Binding>,
+    #[serde(default)]
+    pub analog: Vec<GamepadAxis>,
+}
+
+// The config file format, e.g.
+// (actions: { "sprint": [Key(Named(Shift))] }, axes: { "swim_x": (negative: [...], positive: [...]) })
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bindings {
+    #[serde(default)]
+    pub actions: BTreeMap<String, Vec<Binding>>,
+    #[serde(default)]
+    pub axes: BTreeMap<String, Axis>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct ActionState {
+    pressed: bool,
+    just_pressed: bool,
+    just_released: bool,
+}
+
+// Named actions and axes bound to inputs. `ActionSystem` samples `InputState` once per frame, so
+// `just_pressed` and `just_released` hold for exactly one frame
+#[derive(Default)]
+pub struct ActionMap {
+    bindings: Bindings,
+    states: HashMap<String, ActionState>,
+    axes: HashMap<String, f32>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, EngineError> {
+        let mut action_map = Self::new();
+        action_map.load_bindings(source)?;
+        Ok(action_map)
+    }
+
+    // Bindings of actions and axes in the source replace the ones they had, others are kept
+    pub fn load_bindings(&mut self, source: &str) -> Result<(), EngineError> {
+        let bindings: Bindings = ron::from_str(source)
+            .map_err(|err| EngineError::Parse("input bindings".to_string(), err.to_string()))?;
+        for (action, bindings) in bindings.actions {
+            self.set_bindings(&action, bindings);
+        }
+        for (name, axis) in bindings.axes {
+            self.set_axis(&name, axis);
+        }
+        Ok(())
+    }
+
+    pub fn load_file(&mut self, path: &str) -> Result<(), EngineError> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| EngineError::Io(path.to_string(), err.to_string()))?;
+        self.load_bindings(&source).map_err(|err| match err {
+            EngineError::Parse(_, err) => EngineError::Parse(path.to_string(), err),
+            err => err,
+        })
+    }
+
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        let bindings = self.bindings.actions.entry(action.to_string()).or_default();
+        let binding = binding.normalized();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn set_bindings(&mut self, action: &str, bindings: Vec<Binding>) {
+        let bindings = bindings.into_iter().map(Binding::normalized).collect();
+        self.bindings.actions.insert(action.to_string(), bindings);
+    }
+
+    pub fn set_axis(&mut self, name: &str, axis: Axis) {
+        let axis = Axis {
+            negative: axis.negative.into_iter().map(Binding::normalized).collect(),
+            positive: axis.positive.into_iter().map(Binding::normalized).collect(),
+            analog: axis.analog,
+        };
+        self.bindings.axes.insert(name.to_string(), axis);
+    }
+
+    pub fn bindings(&self) -> &Bindings {
+        &self.bindings
+    }
+
+    pub fn update(&mut self, input: &InputState) {
+        for (action, bindings) in &self.bindings.actions {
+            let pressed = bindings.iter().any(|binding| binding.is_pressed(input));
+            let state = self.states.entry(action.clone()).or_default();
+            *state = ActionState {
+                pressed,
+                just_pressed: pressed && !state.pressed,
+                just_released: !pressed && state.pressed,
+            };
+        }
+        self.states
+            .retain(|action, _| self.bindings.actions.contains_key(action));
+        self.axes = self
+            .bindings
+            .axes
+            .iter()
+            .map(|(name, axis)| {
+                let held = |bindings: &[Binding]| bindings.iter().any(|b| b.is_pressed(input));
+                let digital = held(&axis.positive) as i32 - held(&axis.negative) as i32;
+                let analog = axis
+                    .analog
+                    .iter()
+                    .map(|&analog| input.gamepad_axis(analog))
+                    .fold(0.0f32, |max, value| match value.abs() > max.abs() {
+                        true => value,
+                        false => max,
+                    });
+                let value = match digital {
+                    0 => analog,
+                    digital => digital as f32,
+                };
+                (name.clone(), value)
+            })
+            .collect();
+    }
+
+    pub fn pressed(&self, action: &str) -> bool {
+        self.state(action).pressed
+    }
+
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.state(action).just_pressed
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        self.state(action).just_released
+    }
+
+    // 0 for unknown axes
+    pub fn axis(&self, name: &str) -> f32 {
+        self.axes.get(name).copied().unwrap_or(0.0)
+    }
+
+    fn state(&self, action: &str) -> ActionState {
+        self.states.get(action).copied().unwrap_or_default()
+    }
+}
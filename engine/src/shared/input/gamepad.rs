@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::input::input_event::InputEvent;
+
+// Sticks within this distance of their center read as 0
+pub const GAMEPAD_DEAD_ZONE: f32 = 0.15;
+
+// Named by position, so `South` is A on an Xbox and Cross on a PlayStation controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftShoulder,
+    RightShoulder,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+// Sticks go from -1 to 1 with y pointing up, triggers from 0 to 1
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GamepadState {
+    buttons: HashSet<GamepadButton>,
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl GamepadState {
+    pub fn set_pressed(&mut self, button: GamepadButton, pressed: bool) {
+        if pressed {
+            self.buttons.insert(button);
+        } else {
+            self.buttons.remove(&button);
+        }
+    }
+
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    pub fn set_axis(&mut self, axis: GamepadAxis, value: f32) {
+        let value = value.clamp(-1.0, 1.0);
+        let value = match value.abs() < GAMEPAD_DEAD_ZONE {
+            true => 0.0,
+            false => value,
+        };
+        self.axes.insert(axis, value);
+    }
+
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+pub(crate) fn from_gilrs_event(event: gilrs::Event) -> Option<InputEvent> {
+    let id = usize::from(event.id);
+    match event.event {
+        gilrs::EventType::Connected => Some(InputEvent::GamepadConnected(id)),
+        gilrs::EventType::Disconnected => Some(InputEvent::GamepadDisconnected(id)),
+        gilrs::EventType::ButtonPressed(button, _) => Some(InputEvent::GamepadButton {
+            id,
+            button: from_gilrs_button(button)?,
+            pressed: true,
+        }),
+        gilrs::EventType::ButtonReleased(button, _) => Some(InputEvent::GamepadButton {
+            id,
+            button: from_gilrs_button(button)?,
+            pressed: false,
+        }),
+        // Analog triggers are reported as button values
+        gilrs::EventType::ButtonChanged(gilrs::Button::LeftTrigger2, value, _) => {
+            Some(InputEvent::GamepadAxis {
+                id,
+                axis: GamepadAxis::LeftTrigger,
+                value,
+            })
+        }
+        gilrs::EventType::ButtonChanged(gilrs::Button::RightTrigger2, value, _) => {
+            Some(InputEvent::GamepadAxis {
+                id,
+                axis: GamepadAxis::RightTrigger,
+                value,
+            })
+        }
+        gilrs::EventType::AxisChanged(axis, value, _) => Some(InputEvent::GamepadAxis {
+            id,
+            axis: from_gilrs_axis(axis)?,
+            value,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn from_gilrs_button(button: gilrs::Button) -> Option<GamepadButton> {
+    match button {
+        gilrs::Button::South => Some(GamepadButton::South),
+        gilrs::Button::East => Some(GamepadButton::East),
+        gilrs::Button::North => Some(GamepadButton::North),
+        gilrs::Button::West => Some(GamepadButton::West),
+        gilrs::Button::LeftTrigger => Some(GamepadButton::LeftShoulder),
+        gilrs::Button::RightTrigger => Some(GamepadButton::RightShoulder),
+        gilrs::Button::LeftTrigger2 => Some(GamepadButton::LeftTrigger),
+        gilrs::Button::RightTrigger2 => Some(GamepadButton::RightTrigger),
+        gilrs::Button::Select => Some(GamepadButton::Select),
+        gilrs::Button::Start => Some(GamepadButton::Start),
+        gilrs::Button::Mode => Some(GamepadButton::Mode),
+        gilrs::Button::LeftThumb => Some(GamepadButton::LeftStick),
+        gilrs::Button::RightThumb => Some(GamepadButton::RightStick),
+        gilrs::Button::DPadUp => Some(GamepadButton::DPadUp),
+        gilrs::Button::DPadDown => Some(GamepadButton::DPadDown),
+        gilrs::Button::DPadLeft => Some(GamepadButton::DPadLeft),
+        gilrs::Button::DPadRight => Some(GamepadButton::DPadRight),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn from_gilrs_axis(axis: gilrs::Axis) -> Option<GamepadAxis> {
+    match axis {
+        gilrs::Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        gilrs::Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        gilrs::Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        gilrs::Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        gilrs::Axis::LeftZ => Some(GamepadAxis::LeftTrigger),
+        gilrs::Axis::RightZ => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
@@ -1,11 +1,38 @@
+use serde::{Deserialize, Serialize};
 use winit::{event::MouseButton, keyboard::Key};
 
-use crate::math::vec2::Vec2;
+use crate::{
+    math::vec2::Vec2,
+    shared::input::gamepad::{GamepadAxis, GamepadButton},
+};
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum InputEvent {
     KeyDown(Key),
     KeyUp(Key),
-    MouseEvent { button: MouseButton, pressed: bool },
+    MouseEvent {
+        button: MouseButton,
+        pressed: bool,
+    },
     MouseMove(Vec2),
-    WindowResize { width: i32, height: i32 },
+    // Also handled as a left click
+    Touch {
+        pressed: bool,
+    },
+    WindowResize {
+        width: i32,
+        height: i32,
+    },
+    GamepadConnected(usize),
+    GamepadDisconnected(usize),
+    GamepadButton {
+        id: usize,
+        button: GamepadButton,
+        pressed: bool,
+    },
+    GamepadAxis {
+        id: usize,
+        axis: GamepadAxis,
+        value: f32,
+    },
 }
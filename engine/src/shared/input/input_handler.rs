@@ -14,7 +14,7 @@ impl InputHandler {
     pub fn handle_input_event(world: &mut World, event: InputEvent) {
         match event {
             InputEvent::WindowResize { width, height } => {
-                let window_size = world.expect_resource_mut::<WindowSize>();
+                let mut window_size = world.expect_resource_mut::<WindowSize>();
                 window_size.width = width;
                 window_size.height = height;
             }
@@ -31,6 +31,10 @@ impl InputHandler {
                     .update_key_state(key, false);
             }
             InputEvent::MouseEvent { button, pressed } => {
+                world
+                    .get_resource_mut::<InputState>()
+                    .unwrap()
+                    .set_mouse_pressed(button, pressed);
                 match button {
                     MouseButton::Left => {
                         world
@@ -47,10 +51,43 @@ impl InputHandler {
                     _ => {}
                 };
             }
+            InputEvent::Touch { pressed } => {
+                world
+                    .get_resource_mut::<InputState>()
+                    .unwrap()
+                    .set_touched(pressed);
+                Self::handle_input_event(
+                    world,
+                    InputEvent::MouseEvent {
+                        button: MouseButton::Left,
+                        pressed,
+                    },
+                );
+            }
             InputEvent::MouseMove(vec2) => world
                 .get_resource_mut::<InputState>()
                 .unwrap()
                 .update_mouse_position(vec2),
+            InputEvent::GamepadConnected(id) => world
+                .get_resource_mut::<InputState>()
+                .unwrap()
+                .connect_gamepad(id),
+            InputEvent::GamepadDisconnected(id) => world
+                .get_resource_mut::<InputState>()
+                .unwrap()
+                .disconnect_gamepad(id),
+            InputEvent::GamepadButton {
+                id,
+                button,
+                pressed,
+            } => world
+                .get_resource_mut::<InputState>()
+                .unwrap()
+                .set_gamepad_pressed(id, button, pressed),
+            InputEvent::GamepadAxis { id, axis, value } => world
+                .get_resource_mut::<InputState>()
+                .unwrap()
+                .set_gamepad_axis(id, axis, value),
         };
     }
 }
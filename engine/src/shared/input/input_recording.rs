@@ -0,0 +1,137 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    logic::scene::world::World,
+    shared::{
+        engine_error::EngineError,
+        input::{input_event::InputEvent, input_handler::InputHandler},
+    },
+};
+
+// One line of a recording file. Frame numbers count `Scene::update`s, so events of frame n are
+// handled right before the update that makes it frame n + 1
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InputRecord {
+    // Seed of the recorded run's `StdRng`
+    Seed(u64),
+    Event { frame: u64, event: InputEvent },
+    // Delta time the update of the frame ran with
+    Frame { frame: u64, delta_time: f32 },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputRecording {
+    pub records: Vec<InputRecord>,
+}
+
+impl InputRecording {
+    pub fn load(path: &str) -> Result<Self, EngineError> {
+        let source = fs::read_to_string(path)
+            .map_err(|err| EngineError::Io(path.to_string(), err.to_string()))?;
+        Self::parse(&source).map_err(|err| match err {
+            EngineError::Parse(line, err) => EngineError::Parse(format!("{}:{}", path, line), err),
+            err => err,
+        })
+    }
+
+    // One RON record per line, blank lines are skipped
+    pub fn parse(source: &str) -> Result<Self, EngineError> {
+        let mut records = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = ron::from_str(line).map_err(|err| {
+                EngineError::Parse(format!("line {}", index + 1), err.to_string())
+            })?;
+            records.push(record);
+        }
+        Ok(Self { records })
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.records.iter().find_map(|record| match record {
+            InputRecord::Seed(seed) => Some(*seed),
+            _ => None,
+        })
+    }
+}
+
+// Appends records to a file as they happen, so a recording survives a crash
+pub struct InputRecorder {
+    writer: Box<dyn Write + Send>,
+}
+
+impl InputRecorder {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+
+    pub fn create(path: &str) -> Result<Self, EngineError> {
+        let file =
+            File::create(path).map_err(|err| EngineError::Io(path.to_string(), err.to_string()))?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+
+    pub fn record(&mut self, record: &InputRecord) -> Result<(), EngineError> {
+        let line = ron::to_string(record).map_err(|err| EngineError::Generic(err.to_string()))?;
+        writeln!(self.writer, "{}", line)
+            .and_then(|_| self.writer.flush())
+            .map_err(|err| EngineError::Io("input recording".to_string(), err.to_string()))
+    }
+}
+
+// Feeds a recording back frame by frame
+pub struct InputReplay {
+    records: Vec<InputRecord>,
+    cursor: usize,
+}
+
+impl InputReplay {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            records: recording.records,
+            cursor: 0,
+        }
+    }
+
+    // Handles the events recorded up to `frame` and returns the delta time its update ran with,
+    // if that was recorded
+    pub fn apply(&mut self, world: &mut World, frame: u64) -> Option<f32> {
+        while let Some(record) = self.records.get(self.cursor) {
+            match record {
+                InputRecord::Seed(_) => {}
+                InputRecord::Event {
+                    frame: event_frame,
+                    event,
+                } if *event_frame <= frame => {
+                    InputHandler::handle_input_event(world, event.clone());
+                }
+                InputRecord::Frame {
+                    frame: recorded_frame,
+                    delta_time,
+                } if *recorded_frame <= frame => {
+                    self.cursor += 1;
+                    if *recorded_frame == frame {
+                        return Some(*delta_time);
+                    }
+                    continue;
+                }
+                _ => return None,
+            }
+            self.cursor += 1;
+        }
+        None
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.cursor == self.records.len()
+    }
+}
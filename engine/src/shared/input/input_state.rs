@@ -1,14 +1,21 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 
-use winit::keyboard::Key;
+use winit::{event::MouseButton, keyboard::Key};
 
-use crate::math::vec2::Vec2;
+use crate::{
+    math::vec2::Vec2,
+    shared::input::gamepad::{GamepadAxis, GamepadButton, GamepadState},
+};
 
 pub struct InputState {
     key_states: HashMap<Key, bool>,
     l_mouse: Option<bool>,
     r_mouse: Option<bool>,
+    // Buttons held down, unlike the clicks above which are taken by `handle_*_mouse`
+    mouse_buttons: HashSet<MouseButton>,
+    touched: bool,
     mouse_position: Vec2,
+    gamepads: BTreeMap<usize, GamepadState>,
 }
 
 impl InputState {
@@ -17,7 +24,10 @@ impl InputState {
             key_states: HashMap::new(),
             l_mouse: None,
             r_mouse: None,
+            mouse_buttons: HashSet::new(),
+            touched: false,
             mouse_position: Vec2::zero(),
+            gamepads: BTreeMap::new(),
         }
     }
 
@@ -61,6 +71,26 @@ impl InputState {
         self.r_mouse = Some(state);
     }
 
+    pub fn set_mouse_pressed(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            self.mouse_buttons.insert(button);
+        } else {
+            self.mouse_buttons.remove(&button);
+        }
+    }
+
+    pub fn is_mouse_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.contains(&button)
+    }
+
+    pub fn set_touched(&mut self, touched: bool) {
+        self.touched = touched;
+    }
+
+    pub fn is_touched(&self) -> bool {
+        self.touched
+    }
+
     pub fn update_mouse_position(&mut self, mouse_position: Vec2) {
         self.mouse_position = mouse_position;
     }
@@ -76,4 +106,51 @@ impl InputState {
         )
             .into()
     }
+
+    pub fn connect_gamepad(&mut self, id: usize) {
+        self.gamepads.entry(id).or_default();
+    }
+
+    // Drops whatever the gamepad was holding
+    pub fn disconnect_gamepad(&mut self, id: usize) {
+        self.gamepads.remove(&id);
+    }
+
+    pub fn gamepads(&self) -> impl Iterator<Item = usize> + '_ {
+        self.gamepads.keys().copied()
+    }
+
+    pub fn gamepad(&self, id: usize) -> Option<&GamepadState> {
+        self.gamepads.get(&id)
+    }
+
+    // Events of gamepads we missed the connection of still count
+    pub fn set_gamepad_pressed(&mut self, id: usize, button: GamepadButton, pressed: bool) {
+        self.gamepads
+            .entry(id)
+            .or_default()
+            .set_pressed(button, pressed);
+    }
+
+    pub fn set_gamepad_axis(&mut self, id: usize, axis: GamepadAxis, value: f32) {
+        self.gamepads.entry(id).or_default().set_axis(axis, value);
+    }
+
+    // Held on any gamepad
+    pub fn is_gamepad_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepads
+            .values()
+            .any(|gamepad| gamepad.is_pressed(button))
+    }
+
+    // The value furthest from the center over all gamepads
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepads
+            .values()
+            .map(|gamepad| gamepad.axis(axis))
+            .fold(0.0, |max, value| match value.abs() > max.abs() {
+                true => value,
+                false => max,
+            })
+    }
 }
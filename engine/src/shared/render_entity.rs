@@ -7,4 +7,6 @@ pub struct RenderEntity {
     pub mesh: Arc<Mesh>,
     pub material: Material,
     pub z_index: f32,
+    // `uModelMatrix` before the latest fixed step, blended towards the current one when rendering
+    pub previous_model_matrix: Option<[[f32; 4]; 4]>,
 }
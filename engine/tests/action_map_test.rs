@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod action_map_test {
+    use lumina_engine::logic::scene::debug_config::DebugConfig;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::shared::engine_error::EngineError;
+    use lumina_engine::shared::input::action_map::{ActionMap, Axis, Binding};
+    use lumina_engine::shared::input::input_event::InputEvent;
+    use lumina_engine::shared::input::input_handler::InputHandler;
+    use lumina_engine::shared::input::input_state::InputState;
+    use lumina_engine::HeadlessRunner;
+    use winit::event::MouseButton;
+    use winit::keyboard::{Key, NamedKey};
+
+    const BINDINGS: &str = r#"(
+        actions: {
+            "sprint": [Key(Named(Shift))],
+            "collect": [Mouse(Left), Touch],
+        },
+        axes: {
+            "swim_x": (negative: [Key(Character("A"))], positive: [Key(Character("d"))]),
+        },
+    )"#;
+
+    fn world() -> World {
+        let mut world = World::load();
+        world.insert_resource(InputState::init());
+        world
+    }
+
+    fn send(world: &mut World, action_map: &mut ActionMap, events: Vec<InputEvent>) {
+        for event in events {
+            InputHandler::handle_input_event(world, event);
+        }
+        action_map.update(&world.expect_resource::<InputState>());
+    }
+
+    #[test]
+    fn test_pressed_and_just_pressed() {
+        let mut world = world();
+        let mut actions = ActionMap::from_ron(BINDINGS).unwrap();
+        let shift = || Key::Named(NamedKey::Shift);
+
+        send(&mut world, &mut actions, vec![InputEvent::KeyDown(shift())]);
+        assert!(actions.pressed("sprint") && actions.just_pressed("sprint"));
+        send(&mut world, &mut actions, vec![]);
+        assert!(actions.pressed("sprint") && !actions.just_pressed("sprint"));
+        send(&mut world, &mut actions, vec![InputEvent::KeyUp(shift())]);
+        assert!(!actions.pressed("sprint") && actions.just_released("sprint"));
+        send(&mut world, &mut actions, vec![]);
+        assert!(!actions.just_released("sprint"));
+        assert!(!actions.pressed("unknown"));
+    }
+
+    #[test]
+    fn test_mouse_touch_and_axes() {
+        let mut world = world();
+        let mut actions = ActionMap::from_ron(BINDINGS).unwrap();
+        send(
+            &mut world,
+            &mut actions,
+            vec![InputEvent::Touch { pressed: true }],
+        );
+        assert!(actions.just_pressed("collect"));
+        // A touch is a left click as well
+        assert_eq!(
+            world.expect_resource_mut::<InputState>().handle_l_mouse(),
+            Some(true)
+        );
+        send(
+            &mut world,
+            &mut actions,
+            vec![
+                InputEvent::Touch { pressed: false },
+                InputEvent::MouseEvent {
+                    button: MouseButton::Left,
+                    pressed: true,
+                },
+            ],
+        );
+        assert!(actions.pressed("collect") && !actions.just_pressed("collect"));
+
+        // Characters are matched regardless of case
+        send(
+            &mut world,
+            &mut actions,
+            vec![InputEvent::KeyDown(Key::Character("a".into()))],
+        );
+        assert_eq!(actions.axis("swim_x"), -1.0);
+        send(
+            &mut world,
+            &mut actions,
+            vec![InputEvent::KeyDown(Key::Character("D".into()))],
+        );
+        assert_eq!(actions.axis("swim_x"), 0.0);
+        send(
+            &mut world,
+            &mut actions,
+            vec![InputEvent::KeyUp(Key::Character("a".into()))],
+        );
+        assert_eq!(actions.axis("swim_x"), 1.0);
+        assert_eq!(actions.axis("unknown"), 0.0);
+    }
+
+    #[test]
+    fn test_rebinding() {
+        let mut actions = ActionMap::from_ron(BINDINGS).unwrap();
+        actions
+            .load_bindings(r#"(actions: { "sprint": [Key(Named(Space))] })"#)
+            .unwrap();
+        assert_eq!(
+            actions.bindings().actions["sprint"],
+            vec![Binding::Key(Key::Named(NamedKey::Space))]
+        );
+        // Untouched bindings stay
+        assert_eq!(actions.bindings().actions["collect"].len(), 2);
+        actions.bind("sprint", Binding::Key(Key::Named(NamedKey::Space)));
+        assert_eq!(actions.bindings().actions["sprint"].len(), 1);
+        actions.set_axis("swim_x", Axis::default());
+        assert!(actions.bindings().axes["swim_x"].positive.is_empty());
+
+        assert!(matches!(
+            actions.load_bindings("(actions: { \"sprint\": [Joystick] })"),
+            Err(EngineError::Parse(_, _))
+        ));
+        assert!(matches!(
+            actions.load_file("/nonexistent/input.ron"),
+            Err(EngineError::Io(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_debug_toggles_on_default_binding() {
+        let mut runner = HeadlessRunner::new(|_, _| {});
+        let enabled = |runner: &HeadlessRunner| {
+            runner
+                .scene()
+                .get_world()
+                .expect_resource::<DebugConfig>()
+                .enabled
+        };
+        runner.send_input(InputEvent::KeyDown(Key::Named(NamedKey::F3)));
+        runner.tick();
+        assert!(enabled(&runner));
+        // Held down, so it doesn't toggle again
+        runner.run(3);
+        assert!(enabled(&runner));
+        runner.send_input(InputEvent::KeyUp(Key::Named(NamedKey::F3)));
+        runner.tick();
+        runner.send_input(InputEvent::KeyDown(Key::Named(NamedKey::F3)));
+        runner.tick();
+        assert!(!enabled(&runner));
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod broad_phase_test {
+    use lumina_engine::logic::ecs::broad_phase::sweep_and_prune;
+    use lumina_engine::logic::ecs::collision_result::CollisionResult;
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderShape};
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::collision_system::CollisionSystem;
+    use lumina_engine::math::rect::Rect;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+    use proptest::prelude::*;
+
+    fn brute_force(colliders: &[(Entity, Transform, Collider)]) -> Vec<CollisionResult> {
+        let mut collisions = vec![];
+        for i in 0..colliders.len() {
+            for j in (i + 1)..colliders.len() {
+                let (entity_a, transform_a, collider_a) = &colliders[i];
+                let (entity_b, transform_b, collider_b) = &colliders[j];
+                if let Some((penetration_depth, collision_normal)) = collider_a.intersect(
+                    (
+                        transform_a.position.xy() + collider_a.offset,
+                        transform_a.scale,
+                        transform_a.rotation,
+                    ),
+                    collider_b,
+                    (
+                        transform_b.position.xy() + collider_b.offset,
+                        transform_b.scale,
+                        transform_b.rotation,
+                    ),
+                ) {
+                    collisions.push(CollisionResult {
+                        entity_a: *entity_a,
+                        entity_b: *entity_b,
+                        collision_normal,
+                        penetration_depth,
+                    });
+                }
+            }
+        }
+        collisions
+    }
+
+    fn collider() -> impl Strategy<Value = (Transform, Collider)> {
+        (
+            (-10.0f32..10.0, -10.0f32..10.0, -7.0f32..7.0),
+            (0.2f32..2.0, 0.2f32..2.0, 0.5f32..3.0, 0.5f32..3.0),
+            any::<bool>(),
+            (-0.5f32..0.5, -0.5f32..0.5),
+        )
+            .prop_map(
+                |((x, y, rotation), (width, height, scale_x, scale_y), capsule, (dx, dy))| {
+                    let shape = match capsule {
+                        // Capsules are at least as tall as they are wide
+                        true => ColliderShape::Capsule2D {
+                            width,
+                            height: width + height,
+                        },
+                        false => ColliderShape::Rect { width, height },
+                    };
+                    let transform = Transform {
+                        position: Vec3::new(x, y, 0.0),
+                        rotation,
+                        scale: Vec2::new(scale_x, scale_y),
+                        is_flipped: false,
+                    };
+                    (
+                        transform,
+                        Collider::new(shape).with_offset(Vec2::new(dx, dy)),
+                    )
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_brute_force(colliders in prop::collection::vec(collider(), 0..60)) {
+            let colliders: Vec<(Entity, Transform, Collider)> = colliders
+                .into_iter()
+                .enumerate()
+                .map(|(id, (transform, collider))| (Entity::new(id as u32, 0), transform, collider))
+                .collect();
+            prop_assert_eq!(
+                CollisionSystem::detect_collisions(&colliders),
+                brute_force(&colliders)
+            );
+        }
+
+        #[test]
+        fn test_bounds_contain_collider(
+            (transform, collider) in collider(),
+            (u, v) in (-1.0f32..1.0, -1.0f32..1.0),
+        ) {
+            let pos = transform.position.xy() + collider.offset;
+            let bounds = collider.bounds(pos, transform.scale, transform.rotation);
+            let size = bounds.top_right - bounds.bottom_left;
+            // Samples reach past the bounds on every side
+            let point = pos + Vec2::new(u * size.x, v * size.y);
+            if collider.contains_point(point, pos, transform.scale, transform.rotation) {
+                prop_assert!(bounds.grown(1e-4).overlaps(&Rect::from_points(&[point])));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sweep_and_prune_pairs() {
+        let rect = |x: f32, y: f32| Rect {
+            bottom_left: Vec2::new(x, y),
+            top_right: Vec2::new(x + 1.0, y + 1.0),
+        };
+        let bounds = [
+            rect(5.0, 0.0),
+            rect(0.0, 0.0),
+            rect(0.5, 0.5),
+            rect(0.5, 3.0),
+            rect(1.0, 0.0),
+        ];
+        assert_eq!(sweep_and_prune(&bounds), vec![(1, 2), (1, 4), (2, 4)]);
+        assert!(sweep_and_prune(&[]).is_empty());
+    }
+}
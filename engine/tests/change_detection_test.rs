@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod change_detection_test {
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::component_storage::{ComponentStorage, ComponentTicks};
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::query::Query;
+    use lumina_engine::logic::ecs::query_filter::{Added, Changed};
+    use lumina_engine::logic::ecs::system::schedule::SystemConfig;
+    use lumina_engine::logic::ecs::system::system::System;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position(i32);
+
+    fn matching<Q: Query>(world: &World) -> Vec<Entity> {
+        let mut query = world.query::<Q>();
+        let mut entities: Vec<Entity> = query.iter().map(|(entity, _)| entity).collect();
+        entities.sort_by_key(|entity| entity.id);
+        entities
+    }
+
+    // Simulates a system that last ran at the current tick
+    fn next_run(world: &mut World) {
+        let tick = world.change_tick();
+        world.set_last_change_tick(tick);
+        world.increment_change_tick();
+    }
+
+    #[test]
+    fn test_ticks_survive_wrapping() {
+        let ticks = ComponentTicks::new(2);
+        assert!(ticks.is_changed(u32::MAX - 1, 3));
+        assert!(!ticks.is_changed(2, 3));
+        assert!(ComponentTicks::new(u32::MAX).is_added(u32::MAX - 1, 1));
+    }
+
+    #[test]
+    fn test_storage_insert_updates_ticks() {
+        let mut storage = ComponentStorage::<Position>::new();
+        let entity = Entity::new(1, 0);
+        storage.insert(entity, Position(0), 1);
+        storage.insert(entity, Position(1), 5);
+        assert_eq!(
+            storage.ticks(entity),
+            Some(ComponentTicks {
+                added: 1,
+                changed: 5
+            })
+        );
+        // A recycled id counts as newly added
+        storage.insert(Entity::new(1, 1), Position(2), 7);
+        assert_eq!(
+            storage.ticks(Entity::new(1, 1)),
+            Some(ComponentTicks::new(7))
+        );
+        assert!(storage.get_mut(Entity::new(1, 1)).is_some());
+        assert_eq!(storage.ticks(Entity::new(1, 1)).unwrap().changed, 7);
+        storage.get_mut_marked(Entity::new(1, 1), 9);
+        assert_eq!(storage.ticks(Entity::new(1, 1)).unwrap().changed, 9);
+    }
+
+    #[test]
+    fn test_added_only_matches_new_components() {
+        let mut world = World::load();
+        let old = world.create_entity();
+        world.add_component(old, Position(0));
+        assert_eq!(matching::<(Added<Position>,)>(&world), vec![old]);
+
+        next_run(&mut world);
+        let new = world.create_entity();
+        world.add_component(new, Position(1));
+        assert_eq!(matching::<(Added<Position>,)>(&world), vec![new]);
+
+        next_run(&mut world);
+        assert!(matching::<(Added<Position>,)>(&world).is_empty());
+    }
+
+    #[test]
+    fn test_mutable_query_marks_changed() {
+        let mut world = World::load();
+        let a = world.create_entity();
+        let b = world.create_entity();
+        world.add_component(a, Position(0));
+        world.add_component(b, Position(0));
+        next_run(&mut world);
+        assert!(matching::<(Changed<Position>,)>(&world).is_empty());
+
+        world.query::<(&Position,)>().iter().count();
+        assert!(matching::<(Changed<Position>,)>(&world).is_empty());
+
+        world.query::<(&mut Position,)>().get(b);
+        assert_eq!(matching::<(&Position, Changed<Position>)>(&world), vec![b]);
+        assert!(matching::<(Added<Position>,)>(&world).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be combined with `&mut T`")]
+    fn test_changed_with_mutable_access_is_rejected() {
+        let mut world = World::load();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(0));
+        world.query::<(&mut Position, Changed<Position>)>();
+    }
+
+    #[test]
+    fn test_changed_entities_can_be_mutated_afterwards() {
+        let mut world = World::load();
+        let a = world.create_entity();
+        let b = world.create_entity();
+        world.add_component(a, Position(0));
+        world.add_component(b, Position(0));
+        next_run(&mut world);
+        world.get_component_mut::<Position>(b);
+
+        for entity in matching::<(Changed<Position>,)>(&world) {
+            world.get_component_mut::<Position>(entity).unwrap().0 += 1;
+        }
+        assert_eq!(*world.get_component::<Position>(a).unwrap(), Position(0));
+        assert_eq!(*world.get_component::<Position>(b).unwrap(), Position(1));
+    }
+
+    #[test]
+    fn test_get_component_mut_marks_changed() {
+        let mut world = World::load();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(0));
+        next_run(&mut world);
+
+        world.get_component_mut::<Position>(entity).unwrap().0 = 1;
+        assert_eq!(matching::<(Changed<Position>,)>(&world), vec![entity]);
+        next_run(&mut world);
+        assert!(matching::<(Changed<Position>,)>(&world).is_empty());
+    }
+
+    struct MoveSystem;
+
+    impl System for MoveSystem {
+        fn run(&mut self, world: &mut World, _: f32) {
+            for (_, (position,)) in world.query::<(&mut Position,)>().iter() {
+                position.0 += 1;
+            }
+        }
+    }
+
+    struct ChangedCounter(Arc<Mutex<Vec<usize>>>);
+
+    impl System for ChangedCounter {
+        fn run(&mut self, world: &mut World, _: f32) {
+            let changed = matching::<(Changed<Position>,)>(world).len();
+            self.0.lock().unwrap().push(changed);
+        }
+    }
+
+    #[test]
+    fn test_scene_tracks_last_run_per_system() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        let counts = Arc::new(Mutex::new(Vec::new()));
+        scene.register_system(SystemConfig::new(
+            "changed_counter",
+            ChangedCounter(counts.clone()),
+        ));
+        let entity = scene.get_world_mut().create_entity();
+        scene.get_world_mut().add_component(entity, Position(0));
+
+        scene.update(0.016);
+        scene.update(0.016);
+        scene.register_system(SystemConfig::new("move", MoveSystem));
+        scene.update(0.016);
+        scene.update(0.016);
+        // Added before the first run, untouched, then moved after the counter ran each frame
+        assert_eq!(*counts.lock().unwrap(), vec![1, 0, 0, 1]);
+
+        scene.get_world_mut().get_component_mut::<Position>(entity);
+        scene.update(0.016);
+        assert_eq!(counts.lock().unwrap().last(), Some(&1));
+    }
+}
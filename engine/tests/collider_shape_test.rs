@@ -0,0 +1,290 @@
+#[cfg(test)]
+mod collider_shape_test {
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderPart, ColliderShape};
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::render::model::wireframe;
+    use lumina_engine::render::resource::resource_manager::ColliderShapeKey;
+    use proptest::prelude::*;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    const EPSILON: f32 = 1e-3;
+
+    fn circle(radius: f32) -> Collider {
+        Collider::new(ColliderShape::Circle { radius })
+    }
+
+    fn rect_polygon(width: f32, height: f32) -> Collider {
+        let (x, y) = (width / 2.0, height / 2.0);
+        Collider::new(ColliderShape::Polygon {
+            points: vec![
+                Vec2::new(-x, y),
+                Vec2::new(x, y),
+                Vec2::new(x, -y),
+                Vec2::new(-x, -y),
+            ],
+        })
+    }
+
+    fn intersect(a: &Collider, pos_a: Vec2, b: &Collider, pos_b: Vec2) -> Option<(f32, Vec2)> {
+        a.intersect((pos_a, Vec2::unit(), 0.0), b, (pos_b, Vec2::unit(), 0.0))
+    }
+
+    fn placement() -> impl Strategy<Value = (Vec2, Vec2, f32)> {
+        (
+            (-1.5f32..1.5, -1.5f32..1.5),
+            (0.5f32..2.0, 0.5f32..2.0),
+            -3.2f32..3.2,
+        )
+            .prop_map(|((x, y), (scale_x, scale_y), rotation)| {
+                (Vec2::new(x, y), Vec2::new(scale_x, scale_y), rotation)
+            })
+    }
+
+    // Both agree on whether the colliders overlap unless they barely touch
+    fn assert_same_collision(
+        expected: Option<(f32, Vec2)>,
+        actual: Option<(f32, Vec2)>,
+    ) -> Result<(), TestCaseError> {
+        match (expected, actual) {
+            (Some((expected, _)), Some((actual, _))) => {
+                prop_assert!(
+                    (expected - actual).abs() < EPSILON,
+                    "{} != {}",
+                    expected,
+                    actual
+                )
+            }
+            (Some((depth, _)), None) | (None, Some((depth, _))) => prop_assert!(depth < EPSILON),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_polygon_matches_rect(
+            (width_a, height_a, width_b, height_b) in (0.2f32..1.5, 0.2f32..1.5, 0.2f32..1.5, 0.2f32..1.5),
+            (pos_a, scale_a, rot_a) in placement(),
+            (pos_b, scale_b, rot_b) in placement(),
+        ) {
+            let rect_a = Collider::new(ColliderShape::Rect { width: width_a, height: height_a });
+            let rect_b = Collider::new(ColliderShape::Rect { width: width_b, height: height_b });
+            let expected = rect_a.intersect((pos_a, scale_a, rot_a), &rect_b, (pos_b, scale_b, rot_b));
+            let actual = rect_polygon(width_a, height_a).intersect(
+                (pos_a, scale_a, rot_a),
+                &rect_polygon(width_b, height_b),
+                (pos_b, scale_b, rot_b),
+            );
+            assert_same_collision(expected, actual)?;
+            if let (Some((_, expected)), Some((_, actual))) = (expected, actual) {
+                prop_assert!((expected - actual).length() < EPSILON);
+            }
+        }
+
+        #[test]
+        fn test_circle_matches_round_capsule(
+            radius in 0.1f32..1.0,
+            (pos_a, _, rot_a) in placement(),
+            (width, height) in (0.2f32..1.0, 0.0f32..1.0),
+            (pos_b, scale_b, rot_b) in placement(),
+        ) {
+            // A capsule as tall as it is wide is a circle, only the depth of deep overlaps differs
+            let round_capsule = Collider::new(ColliderShape::Capsule2D { width: radius * 2.0, height: radius * 2.0 });
+            let capsule = Collider::new(ColliderShape::Capsule2D { width, height: width + height });
+            let scale_a = Vec2::unit();
+            let expected = round_capsule.intersect((pos_a, scale_a, rot_a), &capsule, (pos_b, scale_b, rot_b));
+            let actual = circle(radius).intersect((pos_a, scale_a, rot_a), &capsule, (pos_b, scale_b, rot_b));
+            if expected.is_some() != actual.is_some() {
+                assert_same_collision(expected, actual)?;
+            }
+        }
+
+        #[test]
+        fn test_bounds_and_contains_point(
+            (pos, scale, rot) in placement(),
+            (u, v) in (-1.0f32..1.0, -1.0f32..1.0),
+        ) {
+            let collider = Collider::new(ColliderShape::Compound {
+                parts: vec![
+                    ColliderPart { offset: Vec2::new(0.3, 0.2), shape: ColliderShape::Circle { radius: 0.25 } },
+                    ColliderPart { offset: Vec2::new(-0.2, -0.1), shape: rect_polygon(0.4, 0.6).shape },
+                ],
+            });
+            let bounds = collider.bounds(pos, scale, rot);
+            let size = bounds.top_right - bounds.bottom_left;
+            let point = pos + Vec2::new(u * size.x, v * size.y);
+            if collider.contains_point(point, pos, scale, rot) {
+                let inside = bounds.grown(EPSILON);
+                prop_assert!(inside.bottom_left.x <= point.x && point.x <= inside.top_right.x);
+                prop_assert!(inside.bottom_left.y <= point.y && point.y <= inside.top_right.y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_circles() {
+        let (depth, normal) = intersect(
+            &circle(1.0),
+            Vec2::new(1.5, 0.0),
+            &circle(1.0),
+            Vec2::zero(),
+        )
+        .unwrap();
+        assert!((depth - 0.5).abs() < EPSILON);
+        assert!((normal - Vec2::new(1.0, 0.0)).length() < EPSILON);
+        assert!(intersect(
+            &circle(1.0),
+            Vec2::new(2.1, 0.0),
+            &circle(1.0),
+            Vec2::zero()
+        )
+        .is_none());
+
+        // Near a corner the circle is separated diagonally, although the edges overlap
+        let square = rect_polygon(2.0, 2.0);
+        assert!(intersect(&circle(0.5), Vec2::new(1.4, 1.4), &square, Vec2::zero()).is_none());
+        let (depth, normal) =
+            intersect(&circle(0.5), Vec2::new(1.3, 1.3), &square, Vec2::zero()).unwrap();
+        let diagonal = Vec2::new(1.0, 1.0).normalized();
+        assert!((depth - (0.5 - 0.3 * 2f32.sqrt())).abs() < EPSILON);
+        assert!((normal - diagonal).length() < EPSILON);
+        assert!(circle(0.5).contains_point(
+            Vec2::new(1.2, 1.3),
+            Vec2::new(1.0, 1.0),
+            Vec2::unit(),
+            0.0
+        ));
+    }
+
+    #[test]
+    fn test_polygons() {
+        let triangle = Collider::new(ColliderShape::Polygon {
+            points: vec![
+                Vec2::new(0.0, 1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(-1.0, -1.0),
+            ],
+        });
+        assert!(triangle.contains_point(Vec2::new(0.0, 0.0), Vec2::zero(), Vec2::unit(), 0.0));
+        assert!(!triangle.contains_point(Vec2::new(0.8, 0.8), Vec2::zero(), Vec2::unit(), 0.0));
+        // Rotated half a turn the triangle points down
+        assert!(triangle.contains_point(Vec2::new(0.0, -0.9), Vec2::zero(), Vec2::unit(), PI));
+
+        let (depth, normal) =
+            intersect(&triangle, Vec2::new(0.0, 1.9), &triangle, Vec2::zero()).unwrap();
+        assert!((depth - 0.1).abs() < EPSILON);
+        assert!((normal - Vec2::new(0.0, 1.0)).length() < EPSILON);
+    }
+
+    #[test]
+    fn test_compound() {
+        let dumbbell = Collider::new(ColliderShape::Compound {
+            parts: vec![
+                ColliderPart {
+                    offset: Vec2::new(-1.0, 0.0),
+                    shape: ColliderShape::Circle { radius: 0.5 },
+                },
+                ColliderPart {
+                    offset: Vec2::new(1.0, 0.0),
+                    shape: ColliderShape::Circle { radius: 0.5 },
+                },
+            ],
+        });
+        // Nothing between the two parts
+        assert!(intersect(&circle(0.2), Vec2::zero(), &dumbbell, Vec2::zero()).is_none());
+        assert!(!dumbbell.contains_point(Vec2::zero(), Vec2::zero(), Vec2::unit(), 0.0));
+        // Offsets scale and rotate with the collider
+        let scale = Vec2::new(2.0, 2.0);
+        assert!(dumbbell.contains_point(Vec2::new(0.0, 2.0), Vec2::zero(), scale, FRAC_PI_2));
+
+        let (depth, normal) =
+            intersect(&circle(0.2), Vec2::new(1.6, 0.0), &dumbbell, Vec2::zero()).unwrap();
+        assert!((depth - 0.1).abs() < EPSILON);
+        assert!((normal - Vec2::new(1.0, 0.0)).length() < EPSILON);
+        let (_, normal) =
+            intersect(&dumbbell, Vec2::zero(), &circle(0.2), Vec2::new(1.6, 0.0)).unwrap();
+        assert!((normal - Vec2::new(-1.0, 0.0)).length() < EPSILON);
+
+        let bounds = dumbbell.bounds(Vec2::zero(), Vec2::unit(), 0.0);
+        assert!((bounds.bottom_left - Vec2::new(-1.5, -0.5)).length() < EPSILON);
+        assert!((bounds.top_right - Vec2::new(1.5, 0.5)).length() < EPSILON);
+
+        let mut dumbbell = dumbbell;
+        dumbbell.compute_boundary_points(Vec2::zero(), Vec2::unit(), 0.0);
+        assert_eq!(dumbbell.boundary_points().len(), 16);
+    }
+
+    #[test]
+    fn test_compound_from_ron() {
+        let collider: Collider = ron::from_str(
+            "(shape: Compound(parts: [
+                (offset: (x: 0.0, y: 0.5), shape: Circle(radius: 0.5)),
+                (shape: Polygon(points: [(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 0.0, y: 1.0)])),
+            ]))",
+        )
+        .unwrap();
+        let ColliderShape::Compound { parts } = &collider.shape else {
+            panic!("Not a compound collider");
+        };
+        assert_eq!(parts[1].offset, Vec2::zero());
+        assert_eq!(
+            ColliderShapeKey::from_shape(&collider.shape),
+            ColliderShapeKey::from_shape(&collider.clone().shape)
+        );
+    }
+
+    #[test]
+    fn test_degenerate_polygons_are_rejected() {
+        let polygon = |points: Vec<Vec2>| ColliderShape::Polygon { points };
+        assert!(polygon(vec![]).validate().is_err());
+        assert!(polygon(vec![Vec2::zero(), Vec2::new(1.0, 0.0)])
+            .validate()
+            .is_err());
+        assert!(
+            polygon(vec![Vec2::zero(), Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0)])
+                .validate()
+                .is_err()
+        );
+        let compound = ColliderShape::Compound {
+            parts: vec![ColliderPart {
+                offset: Vec2::zero(),
+                shape: polygon(vec![]),
+            }],
+        };
+        assert!(compound.validate().is_err());
+        assert!(rect_polygon(1.0, 1.0).shape.validate().is_ok());
+
+        let parsed = ron::from_str::<Collider>("(shape: Polygon(points: []))");
+        assert!(parsed.unwrap_err().to_string().contains("Degenerate"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid collider")]
+    fn test_empty_polygon_collider_panics() {
+        Collider::new(ColliderShape::Polygon { points: vec![] });
+    }
+
+    #[test]
+    fn test_wireframes() {
+        let (vertices, indices, _) = wireframe::circle(1.0, 8);
+        assert_eq!((vertices.len(), indices.len()), (24, 16));
+        let (vertices, indices, _) = wireframe::polygon(&[
+            Vec2::new(0.0, 1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(-1.0, -1.0),
+        ]);
+        assert_eq!(indices, vec![0, 1, 1, 2, 2, 0]);
+        assert_eq!(&vertices[..2], &[0.0, 1.0]);
+
+        let (vertices, indices, _) = wireframe::merged(vec![
+            (Vec2::zero(), wireframe::rectangle(1.0, 1.0)),
+            (
+                Vec2::new(2.0, 0.0),
+                wireframe::polygon(&[Vec2::zero(), Vec2::unit()]),
+            ),
+        ]);
+        assert_eq!(vertices.len(), 18);
+        assert_eq!(&indices[8..], &[4, 5, 5, 4]);
+        assert_eq!(&vertices[12..14], &[2.0, 0.0]);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod collision_event_test {
+    use lumina_engine::logic::ecs::collision_event::{
+        CollisionEvent, CollisionEventKind, CollisionEvents,
+    };
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderShape};
+    use lumina_engine::logic::ecs::component::force::Force;
+    use lumina_engine::logic::ecs::component::movement::Movement;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::collision_system::CollisionSystem;
+    use lumina_engine::logic::ecs::system::schedule::SystemConfig;
+    use lumina_engine::logic::ecs::system::transform_propagation_system::TransformPropagationSystem;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+
+    fn scene() -> Scene {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(SystemConfig::new(
+            "transform_propagation",
+            TransformPropagationSystem,
+        ));
+        scene.register_system(
+            SystemConfig::parallel("collision", CollisionSystem).after("transform_propagation"),
+        );
+        scene
+    }
+
+    fn spawn(world: &mut World, x: f32, collider: Collider) -> Entity {
+        let entity = world.create_entity();
+        world.add_component(
+            entity,
+            Transform {
+                position: Vec3::new(x, 0.0, 0.0),
+                scale: Vec2::unit(),
+                ..Default::default()
+            },
+        );
+        world.add_component(entity, collider);
+        world.add_component(entity, Movement::default());
+        world.add_component(entity, Force::new(1.0));
+        entity
+    }
+
+    fn square() -> Collider {
+        Collider::new(ColliderShape::Rect {
+            width: 1.0,
+            height: 1.0,
+        })
+    }
+
+    fn events(scene: &Scene) -> Vec<CollisionEvent> {
+        scene
+            .get_world()
+            .expect_resource::<CollisionEvents>()
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    fn x(scene: &Scene, entity: Entity) -> f32 {
+        scene
+            .get_world()
+            .get_component::<Transform>(entity)
+            .unwrap()
+            .position
+            .x
+    }
+
+    #[test]
+    fn test_layers_and_masks() {
+        let player = square().with_layer(2);
+        let trash = square().with_layer(4).with_mask(1 | 2);
+        assert!(player.collides_with(&trash) && trash.collides_with(&player));
+        assert!(!trash.collides_with(&trash.clone()));
+        assert!(square().collides_with(&trash));
+
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let a = spawn(world, 0.0, trash.clone());
+        let b = spawn(world, 0.5, trash);
+        scene.update(0.1);
+        assert!(events(&scene).is_empty());
+        assert_eq!((x(&scene, a), x(&scene, b)), (0.0, 0.5));
+    }
+
+    #[test]
+    fn test_sensors_report_without_pushing() {
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let body = spawn(world, 0.0, square());
+        let sensor = spawn(world, 0.5, square().as_sensor());
+        scene.update(0.1);
+        assert_eq!(
+            events(&scene),
+            vec![CollisionEvent {
+                kind: CollisionEventKind::Started,
+                entity_a: body,
+                entity_b: sensor,
+                is_sensor: true,
+            }]
+        );
+        assert_eq!(events(&scene)[0].other(sensor), Some(body));
+        assert_eq!((x(&scene, body), x(&scene, sensor)), (0.0, 0.5));
+    }
+
+    #[test]
+    fn test_started_stayed_and_ended() {
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let a = spawn(world, 0.0, square().as_sensor());
+        let b = spawn(world, 2.0, square());
+        let kinds = |scene: &Scene| -> Vec<CollisionEventKind> {
+            events(scene).iter().map(|event| event.kind).collect()
+        };
+
+        scene.update(0.1);
+        assert!(kinds(&scene).is_empty());
+
+        scene
+            .get_world_mut()
+            .get_component_mut::<Transform>(b)
+            .unwrap()
+            .position
+            .x = 0.5;
+        scene.update(0.1);
+        assert_eq!(kinds(&scene), vec![CollisionEventKind::Started]);
+        assert!(scene
+            .get_world()
+            .expect_resource::<CollisionEvents>()
+            .is_touching(b, a));
+        scene.update(0.1);
+        assert_eq!(kinds(&scene), vec![CollisionEventKind::Stayed]);
+
+        // A despawned entity ends its collisions
+        scene.get_world_mut().delete_entity(b);
+        scene.update(0.1);
+        let events = events(&scene);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, CollisionEventKind::Ended);
+        assert_eq!((events[0].entity_a, events[0].entity_b), (a, b));
+        scene.update(0.1);
+        assert!(kinds(&scene).is_empty());
+    }
+}
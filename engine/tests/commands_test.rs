@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod commands_test {
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::schedule::SystemConfig;
+    use lumina_engine::logic::ecs::system::system::System;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::spawn_entity;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Value(i32);
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(u32);
+
+    #[test]
+    fn test_spawn_is_deferred_until_applied() {
+        let mut world = World::load();
+        let entity = spawn_entity!(world.commands(), Value(1), Marker);
+        assert!(!world.is_alive(entity));
+        assert!(world.get_component::<Value>(entity).is_none());
+
+        world.apply_commands();
+        assert!(world.is_alive(entity));
+        assert_eq!(
+            world.get_component::<Value>(entity).as_deref(),
+            Some(&Value(1))
+        );
+        assert!(world.get_component::<Marker>(entity).is_some());
+    }
+
+    #[test]
+    fn test_reserved_entities_match_create_entity() {
+        let mut world = World::load();
+        let e1 = world.create_entity();
+        let e2 = world.create_entity();
+        world.delete_entity(e1);
+
+        let mut commands = world.commands();
+        let reused = commands.create_entity();
+        let fresh = commands.create_entity();
+        assert_eq!(reused, Entity::new(e1.id, 1));
+        assert_eq!(fresh, Entity::new(e2.id + 1, 0));
+
+        world.apply_commands();
+        assert!(world.is_alive(reused));
+        assert!(world.is_alive(fresh));
+        assert_eq!(world.create_entity(), Entity::new(e2.id + 2, 0));
+    }
+
+    #[test]
+    fn test_create_entity_after_reserve_does_not_collide() {
+        let mut world = World::load();
+        let reserved = world.commands().create_entity();
+        let created = world.create_entity();
+        assert_ne!(reserved, created);
+        assert!(world.is_alive(reserved));
+    }
+
+    #[test]
+    fn test_commands_recorded_during_query() {
+        let mut world = World::load();
+        for i in 0..4 {
+            spawn_entity!(world, Value(i));
+        }
+
+        let mut commands = world.commands();
+        for (entity, value) in world.query::<&Value>().iter() {
+            if value.0 % 2 == 0 {
+                commands.delete_entity(entity);
+            } else {
+                commands.add_component(entity, Marker);
+            }
+        }
+        world.apply_commands();
+
+        let mut query = world.query::<(&Value, &Marker)>();
+        let mut values: Vec<i32> = query.iter().map(|(_, (value, _))| value.0).collect();
+        values.sort();
+        assert_eq!(values, vec![1, 3]);
+        assert_eq!(world.entities.len(), 3);
+    }
+
+    #[test]
+    fn test_commands_apply_in_order() {
+        let mut world = World::load();
+        let entity = world.create_entity();
+        let mut commands = world.commands();
+        commands.add_component(entity, Value(1));
+        commands.remove_component::<Value>(entity);
+        commands.add_component(entity, Value(2));
+        commands.insert_resource(Counter(0));
+        commands.add(|world| world.expect_resource_mut::<Counter>().0 += 1);
+        world.apply_commands();
+
+        assert_eq!(
+            world.get_component::<Value>(entity).as_deref(),
+            Some(&Value(2))
+        );
+        assert_eq!(*world.expect_resource::<Counter>(), Counter(1));
+    }
+
+    #[test]
+    fn test_commands_recorded_while_applying() {
+        let mut world = World::load();
+        world.commands().add(|world| {
+            world.commands().insert_resource(Counter(7));
+        });
+        world.apply_commands();
+        assert_eq!(*world.expect_resource::<Counter>(), Counter(7));
+    }
+
+    struct SpawnSystem;
+
+    impl System for SpawnSystem {
+        fn run(&mut self, world: &mut World, _: f32) {
+            world.commands().add_component(Entity::new(1, 0), Value(0));
+            spawn_entity!(world.commands(), Value(10));
+        }
+    }
+
+    struct ReadSystem {
+        seen: usize,
+    }
+
+    impl System for ReadSystem {
+        fn run(&mut self, world: &mut World, _: f32) {
+            self.seen = world.query::<&Value>().iter().count();
+        }
+    }
+
+    #[test]
+    fn test_scene_applies_commands_after_each_system() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(SystemConfig::new("spawn", SpawnSystem));
+        scene.update(0.016);
+        assert_eq!(scene.get_world().query::<&Value>().iter().count(), 2);
+        scene.update(0.016);
+        assert_eq!(scene.get_world().query::<&Value>().iter().count(), 3);
+    }
+
+    #[test]
+    fn test_later_systems_see_applied_commands() {
+        let mut world = World::load();
+        world.create_entity();
+        let mut spawn = SpawnSystem;
+        let mut read = ReadSystem { seen: 0 };
+        spawn.run(&mut world, 0.0);
+        read.run(&mut world, 0.0);
+        assert_eq!(read.seen, 0);
+        world.apply_commands();
+        read.run(&mut world, 0.0);
+        assert_eq!(read.seen, 2);
+    }
+}
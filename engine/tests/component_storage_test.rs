@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod component_storage_test {
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::component_storage::ComponentStorage;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::scene::world::World;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Value(i32);
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut storage = ComponentStorage::<Value>::new();
+        let entity = Entity::new(3, 0);
+        storage.insert(entity, Value(7), 0);
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.get(entity), Some(&Value(7)));
+        assert!(storage.contains(entity));
+    }
+
+    #[test]
+    fn test_insert_overwrites() {
+        let mut storage = ComponentStorage::<Value>::new();
+        let entity = Entity::new(1, 0);
+        storage.insert(entity, Value(1), 0);
+        storage.insert(entity, Value(2), 0);
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.get(entity), Some(&Value(2)));
+    }
+
+    #[test]
+    fn test_get_rejects_other_generation() {
+        let mut storage = ComponentStorage::<Value>::new();
+        storage.insert(Entity::new(1, 0), Value(1), 0);
+        assert!(storage.get(Entity::new(1, 1)).is_none());
+        assert!(storage.remove(Entity::new(1, 1)).is_none());
+        assert_eq!(storage.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_replaces_stale_generation() {
+        let mut storage = ComponentStorage::<Value>::new();
+        storage.insert(Entity::new(1, 0), Value(1), 0);
+        storage.insert(Entity::new(1, 1), Value(2), 0);
+        assert_eq!(storage.len(), 1);
+        assert!(storage.get(Entity::new(1, 0)).is_none());
+        assert_eq!(storage.get(Entity::new(1, 1)), Some(&Value(2)));
+    }
+
+    #[test]
+    fn test_remove_keeps_remaining_components_addressable() {
+        let mut storage = ComponentStorage::<Value>::new();
+        let entities: Vec<Entity> = (1..=4).map(|id| Entity::new(id, 0)).collect();
+        for (i, entity) in entities.iter().enumerate() {
+            storage.insert(*entity, Value(i as i32), 0);
+        }
+
+        assert_eq!(storage.remove(entities[0]), Some(Value(0)));
+        assert!(storage.get(entities[0]).is_none());
+        assert_eq!(storage.len(), 3);
+        for (i, entity) in entities.iter().enumerate().skip(1) {
+            assert_eq!(storage.get(*entity), Some(&Value(i as i32)));
+        }
+    }
+
+    #[test]
+    fn test_entities_are_densely_packed() {
+        let mut storage = ComponentStorage::<Value>::new();
+        storage.insert(Entity::new(100, 0), Value(1), 0);
+        storage.insert(Entity::new(5, 0), Value(2), 0);
+        storage.remove(Entity::new(100, 0));
+        assert_eq!(storage.entities(), &[Entity::new(5, 0)]);
+    }
+
+    #[test]
+    fn test_iter_mut_modifies_components() {
+        let mut storage = ComponentStorage::<Value>::new();
+        storage.insert(Entity::new(1, 0), Value(1), 0);
+        storage.insert(Entity::new(2, 0), Value(2), 0);
+        for (_, value) in storage.iter_mut() {
+            value.0 *= 10;
+        }
+        let values: Vec<i32> = storage.iter().map(|(_, value)| value.0).collect();
+        assert_eq!(values, vec![10, 20]);
+    }
+
+    #[test]
+    fn test_query_only_visits_matching_entities() {
+        let mut world = World::load();
+        let mut tagged = Vec::new();
+        for i in 0..100 {
+            let entity = world.create_entity();
+            world.add_component(entity, Value(i));
+            if i % 25 == 0 {
+                world.add_component(entity, Marker);
+                tagged.push(entity);
+            }
+        }
+
+        let mut results: Vec<Entity> = world
+            .query::<(&Value, &Marker)>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect();
+        results.sort_by_key(|entity| entity.id);
+        assert_eq!(results, tagged);
+    }
+
+    #[test]
+    fn test_query_after_component_removal() {
+        let mut world = World::load();
+        let e1 = world.create_entity();
+        let e2 = world.create_entity();
+        world.add_component(e1, Value(1));
+        world.add_component(e2, Value(2));
+        world.remove_component::<Value>(e1);
+
+        let mut query = world.query::<(&Value,)>();
+        let results: Vec<_> = query.iter().collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, e2);
+        assert_eq!(results[0].1 .0, &Value(2));
+    }
+}
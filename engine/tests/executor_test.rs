@@ -0,0 +1,316 @@
+#[cfg(test)]
+mod executor_test {
+    use lumina_engine::logic::ecs::access::Access;
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::query_filter::{Changed, With};
+    use lumina_engine::logic::ecs::system::schedule::{
+        ExecutorMode, Schedule, Stage, SystemConfig,
+    };
+    use lumina_engine::logic::ecs::system::system::{ParallelSystem, System};
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::spawn_entity;
+    use std::sync::mpsc::{channel, Receiver, Sender};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Component)]
+    struct Velocity(i32);
+
+    #[derive(Component)]
+    struct Fish;
+
+    struct Score;
+
+    struct Job<F: FnMut(&World) + Send + Sync>(Access, F);
+
+    impl<F: FnMut(&World) + Send + Sync> ParallelSystem for Job<F> {
+        fn access(&self) -> Access {
+            self.0.clone()
+        }
+
+        fn run(&mut self, world: &World, _: f32) {
+            (self.1)(world)
+        }
+    }
+
+    fn job(
+        name: &str,
+        access: Access,
+        f: impl FnMut(&World) + Send + Sync + 'static,
+    ) -> SystemConfig {
+        SystemConfig::parallel(name, Job(access, f))
+    }
+
+    struct Exclusive;
+
+    impl System for Exclusive {
+        fn run(&mut self, _: &mut World, _: f32) {}
+    }
+
+    #[test]
+    fn test_access_compatibility() {
+        let read = Access::new().read::<Position>();
+        let write = Access::new().write::<Position>();
+        assert!(read.is_compatible(&read));
+        assert!(!read.is_compatible(&write));
+        assert!(!write.is_compatible(&read));
+        assert!(!write.is_compatible(&write));
+        assert!(write.is_compatible(&Access::new().write::<Velocity>()));
+
+        let score = Access::new().write_resource::<Score>();
+        assert!(!score.is_compatible(&Access::new().read_resource::<Score>()));
+        assert!(score.is_compatible(&Access::new().write::<Position>()));
+    }
+
+    #[test]
+    fn test_access_from_query() {
+        let access = Access::new().query::<(&mut Position, Option<&Velocity>, With<Fish>)>();
+        assert!(!access.is_compatible(&Access::new().read::<Position>()));
+        assert!(!access.is_compatible(&Access::new().write::<Velocity>()));
+        assert!(!access.is_compatible(&Access::new().write::<Fish>()));
+        assert!(access.is_compatible(&Access::new().read::<Velocity>()));
+        assert!(access.is_compatible(&Access::new().read_resource::<Score>()));
+    }
+
+    #[test]
+    fn test_batches_group_compatible_systems() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(job("a", Access::new().write::<Position>(), |_| {}));
+        schedule.add_system(job("b", Access::new().write::<Velocity>(), |_| {}));
+        schedule.add_system(job("c", Access::new().read::<Position>(), |_| {}));
+        schedule.add_system(job("d", Access::new().read::<Fish>(), |_| {}).after("c"));
+        schedule.add_system(SystemConfig::new("e", Exclusive));
+        schedule.add_system(job("f", Access::new(), |_| {}));
+        assert_eq!(
+            schedule.batches(Stage::Update).unwrap(),
+            vec![vec!["a", "b"], vec!["c"], vec!["d"], vec!["e"], vec!["f"]]
+        );
+    }
+
+    fn rendezvous(
+        tx: Sender<()>,
+        rx: Receiver<()>,
+        met: Arc<Mutex<Vec<bool>>>,
+    ) -> impl FnMut(&World) {
+        let rx = Mutex::new(rx);
+        move |_| {
+            tx.send(()).unwrap();
+            let other_ran = rx
+                .lock()
+                .unwrap()
+                .recv_timeout(Duration::from_secs(5))
+                .is_ok();
+            met.lock().unwrap().push(other_ran);
+        }
+    }
+
+    #[test]
+    fn test_compatible_systems_run_concurrently() {
+        let (tx_a, rx_a) = channel();
+        let (tx_b, rx_b) = channel();
+        let met = Arc::new(Mutex::new(Vec::new()));
+        let mut schedule = Schedule::new();
+        let a = Mutex::new(rendezvous(tx_a, rx_b, met.clone()));
+        let b = Mutex::new(rendezvous(tx_b, rx_a, met.clone()));
+        schedule.add_system(job("a", Access::new(), move |world| {
+            (a.lock().unwrap())(world)
+        }));
+        schedule.add_system(job("b", Access::new(), move |world| {
+            (b.lock().unwrap())(world)
+        }));
+
+        // The global pool may only have one thread on small machines
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        pool.install(|| schedule.run_stage(Stage::Update, &mut World::load(), 0.016));
+        // Each system only finishes after seeing the other one start
+        assert_eq!(*met.lock().unwrap(), vec![true, true]);
+    }
+
+    #[test]
+    fn test_serial_mode_runs_in_schedule_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut schedule = Schedule::new();
+        schedule.set_executor_mode(ExecutorMode::Serial);
+        for name in ["a", "b", "c", "d"] {
+            let log = log.clone();
+            schedule.add_system(job(name, Access::new(), move |_| {
+                log.lock().unwrap().push(name)
+            }));
+        }
+        let mut world = World::load();
+        for _ in 0..10 {
+            schedule.run_stage(Stage::Update, &mut world, 0.016);
+        }
+        let expected: Vec<&str> = ["a", "b", "c", "d"].repeat(10);
+        assert_eq!(*log.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_commands_of_parallel_systems_are_applied_after_batch() {
+        for mode in [ExecutorMode::Parallel, ExecutorMode::Serial] {
+            let mut schedule = Schedule::new();
+            schedule.set_executor_mode(mode);
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            for (name, value) in [("a", 1), ("b", 2)] {
+                let seen = seen.clone();
+                schedule.add_system(job(name, Access::new().read::<Position>(), move |world| {
+                    seen.lock()
+                        .unwrap()
+                        .push(world.query::<&Position>().iter().count());
+                    spawn_entity!(world.commands(), Position(value));
+                }));
+            }
+            let mut world = World::load();
+            schedule.run_stage(Stage::Update, &mut world, 0.016);
+
+            assert_eq!(*seen.lock().unwrap(), vec![0, 0]);
+            let mut query = world.query::<&Position>();
+            let mut values: Vec<i32> = query.iter().map(|(_, position)| position.0).collect();
+            values.sort();
+            assert_eq!(values, vec![1, 2]);
+        }
+    }
+
+    // Entities and their positions after the systems, given as (delay, spawned) pairs, run twice
+    fn spawned_entities(mode: ExecutorMode, systems: &[(u64, i32)]) -> Vec<(Entity, i32)> {
+        let mut schedule = Schedule::new();
+        schedule.set_executor_mode(mode);
+        for (index, &(delay, count)) in systems.iter().enumerate() {
+            let name = format!("spawner {}", index);
+            schedule.add_system(job(&name, Access::new(), move |world| {
+                std::thread::sleep(Duration::from_millis(delay));
+                for value in 0..count {
+                    spawn_entity!(world.commands(), Position(index as i32 * 10 + value));
+                }
+            }));
+        }
+        let mut world = World::load();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(systems.len())
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            pool.install(|| schedule.run_stage(Stage::Update, &mut world, 0.016));
+        }
+        let mut query = world.query::<&Position>();
+        let mut entities: Vec<(Entity, i32)> = query
+            .iter()
+            .map(|(entity, position)| (entity, position.0))
+            .collect();
+        entities.sort_by_key(|&(entity, _)| entity.id);
+        entities
+    }
+
+    #[test]
+    fn test_entity_ids_of_a_batch_do_not_depend_on_timing() {
+        let expected = spawned_entities(ExecutorMode::Serial, &[(0, 3), (0, 0), (0, 1)]);
+        assert_eq!(expected.len(), 8);
+        for systems in [[(30, 3), (20, 0), (0, 1)], [(0, 3), (10, 0), (30, 1)]] {
+            assert_eq!(spawned_entities(ExecutorMode::Parallel, &systems), expected);
+        }
+    }
+
+    #[test]
+    fn test_ids_skipped_by_a_batch_are_reused() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(job("a", Access::new(), |world| {
+            for value in 0..3 {
+                spawn_entity!(world.commands(), Position(value));
+            }
+        }));
+        schedule.add_system(job("b", Access::new(), |_| {}));
+        let mut world = World::load();
+        schedule.run_stage(Stage::Update, &mut world, 0.016);
+        // The system on the first of two lanes gets every other id
+        let mut ids: Vec<u32> = world
+            .query::<&Position>()
+            .iter()
+            .map(|(e, _)| e.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 3, 5]);
+
+        assert_eq!(world.create_entity().id, 2);
+        assert_eq!(world.create_entity().id, 4);
+        assert_eq!(world.create_entity().id, 6);
+    }
+
+    #[test]
+    fn test_commands_of_a_batch_are_applied_in_registration_order() {
+        let mut schedule = Schedule::new();
+        for (name, delay) in [("a", 30), ("b", 20), ("c", 10), ("d", 0)] {
+            schedule.add_system(job(name, Access::new(), move |world| {
+                // Later systems record their commands first
+                std::thread::sleep(Duration::from_millis(delay));
+                world.commands().add(move |world| {
+                    world.expect_resource_mut::<Vec<&str>>().push(name);
+                });
+            }));
+        }
+        let mut world = World::load();
+        world.insert_resource(Vec::<&str>::new());
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        pool.install(|| schedule.run_stage(Stage::Update, &mut world, 0.016));
+        assert_eq!(*world.expect_resource::<Vec<&str>>(), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_parallel_systems_track_their_own_change_ticks() {
+        let counts = Arc::new(Mutex::new(Vec::new()));
+        let mut schedule = Schedule::new();
+        let counter = counts.clone();
+        schedule.add_system(job(
+            "count",
+            Access::new().query::<(&Position, Changed<Position>)>(),
+            move |world| {
+                let changed = world
+                    .query::<(&Position, Changed<Position>)>()
+                    .iter()
+                    .count();
+                counter.lock().unwrap().push(changed);
+            },
+        ));
+        schedule.add_system(job("move", Access::new().write::<Velocity>(), |world| {
+            for (_, velocity) in world.query::<&mut Velocity>().iter() {
+                velocity.0 += 1;
+            }
+        }));
+        assert_eq!(
+            schedule.batches(Stage::Update).unwrap(),
+            vec![vec!["count", "move"]]
+        );
+
+        let mut world = World::load();
+        let entity = spawn_entity!(world, Position(0), Velocity(0));
+        schedule.run_stage(Stage::Update, &mut world, 0.016);
+        schedule.run_stage(Stage::Update, &mut world, 0.016);
+        world.increment_change_tick();
+        world.get_component_mut::<Position>(entity);
+        schedule.run_stage(Stage::Update, &mut world, 0.016);
+        assert_eq!(*counts.lock().unwrap(), vec![1, 0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_undeclared_conflicting_access_panics() {
+        let mut schedule = Schedule::new();
+        schedule.set_executor_mode(ExecutorMode::Serial);
+        schedule.add_system(job("a", Access::new(), |world| {
+            let _score = world.expect_resource_mut::<Score>();
+            world.expect_resource_mut::<Score>();
+        }));
+        let mut world = World::load();
+        world.insert_resource(Score);
+        schedule.run_stage(Stage::Update, &mut world, 0.016);
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod fixed_timestep_test {
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::component::previous_transform::PreviousTransform;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::system::previous_transform_system::PreviousTransformSystem;
+    use lumina_engine::logic::ecs::system::schedule::{Stage, SystemConfig};
+    use lumina_engine::logic::ecs::system::system::System;
+    use lumina_engine::logic::scene::fixed_time::FixedTime;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::transformation::lerp_matrix;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+    use lumina_engine::spawn_entity;
+    use std::sync::{Arc, Mutex};
+
+    type Log = Arc<Mutex<Vec<(&'static str, f32)>>>;
+
+    struct Record(&'static str, Log);
+
+    impl System for Record {
+        fn run(&mut self, _: &mut World, delta_time: f32) {
+            self.1.lock().unwrap().push((self.0, delta_time));
+        }
+    }
+
+    #[derive(Component)]
+    struct Velocity(f32);
+
+    struct Step;
+
+    impl System for Step {
+        fn run(&mut self, world: &mut World, delta_time: f32) {
+            for (_, (transform, velocity)) in world.query::<(&mut Transform, &Velocity)>().iter() {
+                transform.position.x += velocity.0 * delta_time;
+            }
+        }
+    }
+
+    fn scene_with_log(fixed_time: FixedTime) -> (Scene, Log) {
+        let log = Log::default();
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.get_world_mut().insert_resource(fixed_time);
+        scene.register_system(SystemConfig::new("update", Record("update", log.clone())));
+        scene.register_system(
+            SystemConfig::new("fixed", Record("fixed", log.clone())).in_stage(Stage::FixedUpdate),
+        );
+        (scene, log)
+    }
+
+    fn transform(x: f32) -> Transform {
+        Transform {
+            position: Vec3::new(x, 0.0, 0.0),
+            rotation: 0.0,
+            scale: Vec2::new(1.0, 1.0),
+            is_flipped: false,
+        }
+    }
+
+    #[test]
+    fn test_accumulates_whole_steps() {
+        let mut fixed_time = FixedTime::from_hz(10.0);
+        assert_eq!(fixed_time.accumulate(0.05), 0);
+        assert!((fixed_time.alpha() - 0.5).abs() < 1e-4);
+        assert_eq!(fixed_time.accumulate(0.08), 1);
+        assert!((fixed_time.alpha() - 0.3).abs() < 1e-4);
+        assert_eq!(fixed_time.accumulate(0.25), 2);
+        assert!((fixed_time.alpha() - 0.8).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_max_substeps_drops_backlog() {
+        let mut fixed_time = FixedTime::from_hz(10.0).with_max_substeps(3);
+        assert_eq!(fixed_time.accumulate(1.05), 3);
+        assert!((fixed_time.alpha() - 0.5).abs() < 1e-4);
+        assert_eq!(fixed_time.accumulate(0.0), 0);
+    }
+
+    #[test]
+    fn test_fixed_stage_runs_with_constant_delta_time() {
+        let (mut scene, log) = scene_with_log(FixedTime::from_hz(50.0));
+        scene.update(0.05);
+        assert_eq!(
+            *log.lock().unwrap(),
+            [("fixed", 0.02), ("fixed", 0.02), ("update", 0.05)]
+        );
+        log.lock().unwrap().clear();
+        scene.update(0.005);
+        assert_eq!(*log.lock().unwrap(), [("update", 0.005)]);
+    }
+
+    #[test]
+    fn test_simulation_does_not_depend_on_frame_rate() {
+        // Binary fractions, so the accumulated time is exact
+        for frame_time in [1.0 / 32.0, 1.0 / 64.0, 1.0 / 128.0] {
+            let mut scene = Scene::new();
+            scene.schedule.clear();
+            scene
+                .get_world_mut()
+                .insert_resource(FixedTime::from_hz(64.0));
+            scene.register_system(SystemConfig::new("step", Step).in_stage(Stage::FixedUpdate));
+            let entity = spawn_entity!(scene.get_world_mut(), transform(0.0), Velocity(1.0));
+            for _ in 0..(2.0 / frame_time) as usize {
+                scene.update(frame_time);
+            }
+            let x = scene
+                .get_world()
+                .get_component::<Transform>(entity)
+                .unwrap()
+                .position
+                .x;
+            assert_eq!(x, 2.0);
+        }
+    }
+
+    #[test]
+    fn test_previous_transform_is_state_before_step() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(
+            SystemConfig::parallel("previous_transform", PreviousTransformSystem)
+                .in_stage(Stage::FixedUpdate),
+        );
+        scene.register_system(
+            SystemConfig::new("step", Step)
+                .in_stage(Stage::FixedUpdate)
+                .after("previous_transform"),
+        );
+        let entity = spawn_entity!(
+            scene.get_world_mut(),
+            transform(0.0),
+            Velocity(60.0),
+            PreviousTransform::default()
+        );
+        scene.update(0.01);
+        assert!(scene
+            .get_world()
+            .get_component::<PreviousTransform>(entity)
+            .unwrap()
+            .0
+            .is_none());
+
+        scene.update(0.01);
+        let world = scene.get_world();
+        let previous = world.get_component::<PreviousTransform>(entity).unwrap();
+        assert_eq!(previous.0.as_ref().unwrap().position.x, 0.0);
+        assert!((world.get_component::<Transform>(entity).unwrap().position.x - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_extracted_frame_has_alpha() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene
+            .get_world_mut()
+            .insert_resource(FixedTime::from_hz(10.0));
+        scene.update(0.125);
+        assert!((scene.extract().interpolation_alpha - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_lerp_matrix() {
+        let from = [[0.0; 4]; 4];
+        let to = [[4.0; 4]; 4];
+        assert_eq!(lerp_matrix(&from, &to, 0.0), from);
+        assert_eq!(lerp_matrix(&from, &to, 1.0), to);
+        assert_eq!(lerp_matrix(&from, &to, 0.25), [[1.0; 4]; 4]);
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod gamepad_test {
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::shared::input::action_map::ActionMap;
+    use lumina_engine::shared::input::gamepad::{GamepadAxis, GamepadButton};
+    use lumina_engine::shared::input::input_event::InputEvent;
+    use lumina_engine::shared::input::input_handler::InputHandler;
+    use lumina_engine::shared::input::input_state::InputState;
+
+    const BINDINGS: &str = r#"(
+        actions: {
+            "sprint": [Key(Named(Shift)), Gamepad(South)],
+        },
+        axes: {
+            "swim_x": (
+                negative: [Key(Character("a"))],
+                positive: [Key(Character("d"))],
+                analog: [LeftStickX],
+            ),
+        },
+    )"#;
+
+    fn world() -> World {
+        let mut world = World::load();
+        world.insert_resource(InputState::init());
+        world
+    }
+
+    fn send(world: &mut World, events: Vec<InputEvent>) {
+        for event in events {
+            InputHandler::handle_input_event(world, event);
+        }
+    }
+
+    fn stick(id: usize, value: f32) -> InputEvent {
+        InputEvent::GamepadAxis {
+            id,
+            axis: GamepadAxis::LeftStickX,
+            value,
+        }
+    }
+
+    #[test]
+    fn test_connect_and_disconnect() {
+        let mut world = world();
+        send(
+            &mut world,
+            vec![
+                InputEvent::GamepadConnected(0),
+                InputEvent::GamepadConnected(1),
+                InputEvent::GamepadButton {
+                    id: 1,
+                    button: GamepadButton::East,
+                    pressed: true,
+                },
+                stick(1, 0.5),
+            ],
+        );
+        {
+            let input = world.expect_resource::<InputState>();
+            assert_eq!(input.gamepads().collect::<Vec<_>>(), vec![0, 1]);
+            assert!(input.is_gamepad_pressed(GamepadButton::East));
+            assert!(!input.gamepad(0).unwrap().is_pressed(GamepadButton::East));
+            assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 0.5);
+        }
+
+        // A disconnected gamepad no longer holds anything down
+        send(&mut world, vec![InputEvent::GamepadDisconnected(1)]);
+        let input = world.expect_resource::<InputState>();
+        assert_eq!(input.gamepads().collect::<Vec<_>>(), vec![0]);
+        assert!(!input.is_gamepad_pressed(GamepadButton::East));
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 0.0);
+    }
+
+    #[test]
+    fn test_dead_zone_and_strongest_gamepad() {
+        let mut world = world();
+        send(&mut world, vec![stick(0, 0.05), stick(1, -0.1)]);
+        assert_eq!(
+            world
+                .expect_resource::<InputState>()
+                .gamepad_axis(GamepadAxis::LeftStickX),
+            0.0
+        );
+        send(
+            &mut world,
+            vec![stick(0, 0.3), stick(1, -0.8), stick(2, 3.0)],
+        );
+        let input = world.expect_resource::<InputState>();
+        assert_eq!(
+            input.gamepad(1).unwrap().axis(GamepadAxis::LeftStickX),
+            -0.8
+        );
+        // Out of range values are clamped
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 1.0);
+    }
+
+    #[test]
+    fn test_action_map_bindings() {
+        let mut world = world();
+        let mut actions = ActionMap::from_ron(BINDINGS).unwrap();
+        send(
+            &mut world,
+            vec![
+                InputEvent::GamepadButton {
+                    id: 0,
+                    button: GamepadButton::South,
+                    pressed: true,
+                },
+                stick(0, -0.4),
+            ],
+        );
+        actions.update(&world.expect_resource::<InputState>());
+        assert!(actions.just_pressed("sprint"));
+        // The stick keeps its magnitude
+        assert_eq!(actions.axis("swim_x"), -0.4);
+
+        // Keys win over the stick
+        send(
+            &mut world,
+            vec![InputEvent::KeyDown(winit::keyboard::Key::Character(
+                "d".into(),
+            ))],
+        );
+        actions.update(&world.expect_resource::<InputState>());
+        assert_eq!(actions.axis("swim_x"), 1.0);
+    }
+
+    #[test]
+    fn test_events_round_trip_through_ron() {
+        let event = InputEvent::GamepadAxis {
+            id: 2,
+            axis: GamepadAxis::RightTrigger,
+            value: 0.25,
+        };
+        let source = ron::to_string(&event).unwrap();
+        assert_eq!(ron::from_str::<InputEvent>(&source).unwrap(), event);
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod headless_test {
+    use lumina_engine::logic::ecs::component::material::Material;
+    use lumina_engine::logic::ecs::component::model::Model;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+    use lumina_engine::render::resource::resource_manager::ResourceManager;
+    use lumina_engine::render::resource::resource_provider::ResourceProvider;
+    use lumina_engine::render::resource::texture::texture::Texture;
+    use lumina_engine::shared::window_size::WindowSize;
+    use lumina_engine::{spawn_entity, HeadlessRunner};
+
+    #[test]
+    fn test_null_resources() {
+        let mut resource_manager = ResourceManager::headless();
+        resource_manager.load_default_meshes();
+        resource_manager.load_default_shaders();
+        assert_eq!(resource_manager.get_mesh("square").get_vertex_count(), 6);
+        assert!(matches!(
+            resource_manager.load_static_texture("missing.png"),
+            Some(Texture::StaticTexture(_))
+        ));
+        assert!(matches!(
+            resource_manager.load_animated_texture(&["a.png", "b.png"], 100),
+            Some(Texture::AnimatedTexture(texture)) if texture.textures.len() == 2
+        ));
+        let shader = resource_manager.get_shader("model");
+        let material = Material::new(Texture::None, shader);
+        assert!(material.get_param("uModelMatrix").is_some());
+    }
+
+    #[test]
+    fn test_runs_ticks_and_extracts() {
+        let mut runner = HeadlessRunner::new(|scene, resource_manager| {
+            spawn_entity!(
+                scene.get_world_mut(),
+                Transform {
+                    position: Vec3::new(0.0, 0.0, 0.0),
+                    rotation: 0.0,
+                    scale: Vec2::new(1.0, 1.0),
+                    is_flipped: false,
+                },
+                Model::from(resource_manager.get_mesh("square")),
+                Material::new(Texture::None, resource_manager.get_shader("model"))
+            );
+        });
+        assert_eq!(
+            runner
+                .scene()
+                .get_world()
+                .expect_resource::<WindowSize>()
+                .width,
+            1200
+        );
+        for _ in 0..100 {
+            assert_eq!(runner.tick().entities.len(), 1);
+        }
+        assert_eq!(runner.frame(), 100);
+    }
+
+    #[test]
+    fn test_seeded_by_default() {
+        let runner = HeadlessRunner::new(|_, _| {});
+        assert_eq!(runner.seed(), HeadlessRunner::new(|_, _| {}).seed());
+        assert_eq!(HeadlessRunner::seeded(7, |_, _| {}).seed(), 7);
+    }
+}
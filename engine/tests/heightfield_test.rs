@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod heightfield_test {
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderShape};
+    use lumina_engine::logic::ecs::component::force::{
+        AppliedForce, Force, ForceEffect, ForceMode,
+    };
+    use lumina_engine::logic::ecs::component::global_transform::GlobalTransform;
+    use lumina_engine::logic::ecs::component::movement::Movement;
+    use lumina_engine::logic::ecs::component::rigid_body::{BodyType, RigidBody};
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::collision_system::CollisionSystem;
+    use lumina_engine::logic::ecs::system::movement_system::MovementSystem;
+    use lumina_engine::logic::ecs::system::schedule::SystemConfig;
+    use lumina_engine::logic::ecs::system::system::ParallelSystem;
+    use lumina_engine::logic::ecs::system::transform_propagation_system::TransformPropagationSystem;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+
+    const EPSILON: f32 = 1e-3;
+
+    // A hill from (-1, 0) over (0, 1) to (1, 0)
+    fn hill() -> Collider {
+        Collider::new(ColliderShape::Heightfield {
+            width: 2.0,
+            heights: vec![0.0, 1.0, 0.0],
+        })
+    }
+
+    fn ball() -> Collider {
+        Collider::new(ColliderShape::Circle { radius: 0.1 })
+    }
+
+    fn intersect(a: &Collider, pos_a: Vec2, b: &Collider, pos_b: Vec2) -> Option<(f32, Vec2)> {
+        a.intersect((pos_a, Vec2::unit(), 0.0), b, (pos_b, Vec2::unit(), 0.0))
+    }
+
+    fn spawn(
+        world: &mut World,
+        position: Vec2,
+        collider: Collider,
+        rigid_body: RigidBody,
+    ) -> Entity {
+        let entity = world.create_entity();
+        let transform = Transform {
+            position: Vec3::from_vec2(position, 0.0),
+            scale: Vec2::unit(),
+            ..Default::default()
+        };
+        world.add_component(
+            entity,
+            GlobalTransform {
+                transform: transform.clone(),
+                previous: transform.clone(),
+            },
+        );
+        world.add_component(entity, transform);
+        world.add_component(entity, collider);
+        world.add_component(entity, Movement::default());
+        world.add_component(entity, Force::new(1.0));
+        world.add_component(entity, rigid_body);
+        entity
+    }
+
+    #[test]
+    fn test_contact_normal_follows_slope() {
+        let slope_normal = Vec2::new(-1.0, 1.0).normalized();
+        // Centered on the left slope
+        let (depth, normal) =
+            intersect(&ball(), Vec2::new(-0.5, 0.5), &hill(), Vec2::zero()).unwrap();
+        assert!((depth - 0.1).abs() < EPSILON);
+        assert!((normal - slope_normal).length() < EPSILON);
+        let (_, normal) = intersect(&hill(), Vec2::zero(), &ball(), Vec2::new(-0.5, 0.5)).unwrap();
+        assert!((normal + slope_normal).length() < EPSILON);
+        assert!(intersect(&ball(), Vec2::new(-0.5, 0.7), &hill(), Vec2::zero()).is_none());
+
+        // Above the top the ball is within reach of both slopes extended, but not of the hill
+        assert!(intersect(&ball(), Vec2::new(0.0, 1.12), &hill(), Vec2::zero()).is_none());
+        let (depth, _) = intersect(&ball(), Vec2::new(0.0, 1.05), &hill(), Vec2::zero()).unwrap();
+        assert!(depth > 0.0);
+        // Placed and scaled like any other collider
+        let (_, normal) = ball()
+            .intersect(
+                (Vec2::new(2.5, 1.0), Vec2::unit(), 0.0),
+                &hill(),
+                (Vec2::new(3.0, 0.0), Vec2::new(1.0, 2.0), 0.0),
+            )
+            .unwrap();
+        assert!((normal - Vec2::new(-2.0, 1.0).normalized()).length() < EPSILON);
+    }
+
+    #[test]
+    fn test_heightfield_shape() {
+        assert!(intersect(&hill(), Vec2::zero(), &hill(), Vec2::new(0.5, 0.0)).is_none());
+        assert!(hill().contains_point(Vec2::new(0.5, 0.0), Vec2::zero(), Vec2::unit(), 0.0));
+        assert!(hill().contains_point(Vec2::new(0.0, -0.9), Vec2::zero(), Vec2::unit(), 0.0));
+        assert!(!hill().contains_point(Vec2::new(0.6, 0.5), Vec2::zero(), Vec2::unit(), 0.0));
+        assert!(!hill().contains_point(Vec2::new(0.0, -2.1), Vec2::zero(), Vec2::unit(), 0.0));
+
+        let bounds = hill().bounds(Vec2::zero(), Vec2::unit(), 0.0);
+        assert_eq!(bounds.bottom_left, Vec2::new(-1.0, -2.0));
+        assert_eq!(bounds.top_right, Vec2::new(1.0, 1.0));
+        let mut hill = hill();
+        hill.compute_boundary_points(Vec2::zero(), Vec2::unit(), 0.0);
+        assert_eq!(
+            hill.boundary_points(),
+            &[
+                Vec2::new(-1.0, 0.0),
+                Vec2::new(0.0, 1.0),
+                Vec2::new(1.0, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_landing_cancels_velocity_into_slope() {
+        let mut world = World::load();
+        spawn(
+            &mut world,
+            Vec2::zero(),
+            hill(),
+            RigidBody::new(BodyType::Static).with_friction(0.0),
+        );
+        let body = spawn(
+            &mut world,
+            Vec2::new(-0.5, 0.55),
+            ball(),
+            RigidBody::default(),
+        );
+        world.get_component_mut::<Movement>(body).unwrap().velocity = Vec3::new(0.0, -1.0, 0.0);
+        CollisionSystem.run(&world, 0.1);
+
+        // Only the velocity along the slope is left
+        let velocity = world.get_component::<Movement>(body).unwrap().velocity.xy();
+        assert!((velocity - Vec2::new(-0.5, -0.5)).length() < EPSILON);
+        let position = world
+            .get_component::<Transform>(body)
+            .unwrap()
+            .position
+            .xy();
+        assert!(position.y - position.x > 1.0);
+    }
+
+    #[test]
+    fn test_body_slides_down_hill() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(SystemConfig::parallel("movement", MovementSystem));
+        scene.register_system(
+            SystemConfig::new("transform_propagation", TransformPropagationSystem)
+                .after("movement"),
+        );
+        scene.register_system(
+            SystemConfig::parallel("collision", CollisionSystem).after("transform_propagation"),
+        );
+        let world = scene.get_world_mut();
+        spawn(
+            world,
+            Vec2::zero(),
+            hill(),
+            RigidBody::new(BodyType::Static).with_friction(0.0),
+        );
+        let body = spawn(world, Vec2::new(0.37, 0.77), ball(), RigidBody::default());
+        world
+            .get_component_mut::<Force>(body)
+            .unwrap()
+            .apply_force(AppliedForce {
+                id: "gravity".to_string(),
+                effect: ForceEffect::Linear(Vec3::new(0.0, -10.0, 0.0)),
+                mode: ForceMode::Continuous,
+            });
+
+        let mut previous_x = 0.37;
+        for _ in 0..20 {
+            scene.update(1.0 / 60.0);
+            let position = scene
+                .get_world()
+                .get_component::<Transform>(body)
+                .unwrap()
+                .position
+                .xy();
+            // Resting on the right slope while sliding down it
+            let above_slope = (position.x + position.y - 1.0) / 2f32.sqrt();
+            assert!((above_slope - 0.1).abs() < 0.02, "{}", above_slope);
+            assert!(position.x >= previous_x);
+            previous_x = position.x;
+        }
+        assert!(previous_x > 0.6);
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod hierarchy_test {
+    use lumina_engine::logic::ecs::component::children::Children;
+    use lumina_engine::logic::ecs::component::parent::Parent;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::hierarchy_system::HierarchySystem;
+    use lumina_engine::logic::ecs::system::schedule::{Stage, SystemConfig};
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::transformation::get_world_transform;
+    use lumina_engine::math::vec3::Vec3;
+    use lumina_engine::shared::engine_error::EngineError;
+
+    fn children(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get_component::<Children>(entity)
+            .map(|children| children.iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn world_transform(world: &World, entity: Entity) -> Option<Transform> {
+        get_world_transform(
+            entity,
+            &|e| world.get_component::<Transform>(e).as_deref().cloned(),
+            &|e| world.get_component::<Parent>(e).as_deref().cloned(),
+        )
+    }
+
+    #[test]
+    fn test_set_parent_maintains_children() {
+        let mut world = World::load();
+        let [root, other, a, b] = [(); 4].map(|_| world.create_entity());
+        world.set_parent(a, root).unwrap();
+        world.set_parent(b, root).unwrap();
+        assert_eq!(children(&world, root), vec![a, b]);
+        assert_eq!(world.get_component::<Parent>(a).unwrap().parent, root);
+
+        world.set_parent(a, other).unwrap();
+        assert_eq!(children(&world, root), vec![b]);
+        assert_eq!(children(&world, other), vec![a]);
+
+        // A null parent keeps the `Parent`, which hides the entity
+        world.set_parent(b, Entity::NULL).unwrap();
+        assert!(world.get_component::<Children>(root).is_none());
+        assert!(world.get_component::<Parent>(b).unwrap().parent.is_null());
+
+        assert_eq!(world.remove_parent(a).unwrap().parent, other);
+        assert!(world.get_component::<Children>(other).is_none());
+    }
+
+    #[test]
+    fn test_set_parent_rejects_cycles() {
+        let mut world = World::load();
+        let [a, b, c] = [(); 3].map(|_| world.create_entity());
+        world.set_parent(b, a).unwrap();
+        world.set_parent(c, b).unwrap();
+        assert!(matches!(
+            world.set_parent(a, c),
+            Err(EngineError::HierarchyCycle(cycle)) if cycle == vec![a, c, b, a]
+        ));
+        assert!(matches!(
+            world.set_parent(a, a),
+            Err(EngineError::HierarchyCycle(_))
+        ));
+        assert!(world.get_component::<Parent>(a).is_none());
+        assert!(world.validate_hierarchy().is_ok());
+    }
+
+    #[test]
+    fn test_despawn_recursive() {
+        let mut world = World::load();
+        let [root, child, grandchild, sibling] = [(); 4].map(|_| world.create_entity());
+        world.set_parent(child, root).unwrap();
+        world.set_parent(sibling, root).unwrap();
+        // Parents added as components are picked up as well
+        world.add_component(grandchild, Parent::from(child));
+
+        world.despawn_recursive(child);
+        assert!(!world.is_alive(child));
+        assert!(!world.is_alive(grandchild));
+        assert!(world.is_alive(sibling));
+        assert_eq!(children(&world, root), vec![sibling]);
+
+        world.despawn_recursive(root);
+        assert_eq!(world.entities, vec![Entity::NULL]);
+    }
+
+    #[test]
+    fn test_children_follow_parent_components() {
+        let mut world = World::load();
+        let [a, b, child] = [(); 3].map(|_| world.create_entity());
+        world.add_component(child, Parent::from(a));
+        assert_eq!(children(&world, a), vec![child]);
+        world.add_component(child, Parent::from(b));
+        assert!(world.get_component::<Children>(a).is_none());
+        assert_eq!(children(&world, b), vec![child]);
+
+        world.commands().remove_component::<Parent>(child);
+        world.apply_commands();
+        assert!(world.get_component::<Children>(b).is_none());
+
+        // No longer a descendant, so it outlives its old parent
+        world.despawn_recursive(b);
+        assert!(world.is_alive(child));
+    }
+
+    #[test]
+    fn test_hierarchy_system_follows_parent_changes() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(
+            SystemConfig::new("hierarchy", HierarchySystem).in_stage(Stage::PostUpdate),
+        );
+        let world = scene.get_world_mut();
+        let [a, b, child] = [(); 3].map(|_| world.create_entity());
+        world.add_component(child, Parent::from(a));
+        scene.update(0.0);
+        assert_eq!(children(scene.get_world(), a), vec![child]);
+
+        let world = scene.get_world_mut();
+        world.get_component_mut::<Parent>(child).unwrap().parent = b;
+        scene.update(0.0);
+        let world = scene.get_world();
+        assert!(world.get_component::<Children>(a).is_none());
+        assert_eq!(children(world, b), vec![child]);
+    }
+
+    #[test]
+    fn test_cycles_have_no_world_transform() {
+        let mut world = World::load();
+        let [a, b, c] = [(); 3].map(|_| world.create_entity());
+        for entity in [a, b, c] {
+            let transform = Transform {
+                position: Vec3::new(1.0, 0.0, 0.0),
+                ..Default::default()
+            };
+            world.add_component(entity, transform);
+        }
+        world.add_component(b, Parent::from(a));
+        world.add_component(c, Parent::from(b));
+        assert_eq!(world_transform(&world, c).unwrap().position.x, 3.0);
+
+        world.add_component(a, Parent::from(c));
+        assert!(world_transform(&world, c).is_none());
+        assert!(matches!(
+            world.validate_hierarchy(),
+            Err(EngineError::HierarchyCycle(cycle)) if cycle.len() == 4
+        ));
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod input_recording_test {
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::shared::engine_error::EngineError;
+    use lumina_engine::shared::input::input_event::InputEvent;
+    use lumina_engine::shared::input::input_recording::{
+        InputRecord, InputRecorder, InputRecording, InputReplay,
+    };
+    use lumina_engine::shared::input::input_state::InputState;
+    use lumina_engine::shared::window_size::WindowSize;
+    use winit::event::MouseButton;
+    use winit::keyboard::{Key, NamedKey};
+
+    fn key_down(frame: u64, key: &str) -> InputRecord {
+        InputRecord::Event {
+            frame,
+            event: InputEvent::KeyDown(Key::Character(key.into())),
+        }
+    }
+
+    fn is_pressed(world: &World, key: &str) -> bool {
+        world
+            .expect_resource::<InputState>()
+            .is_pressed(Key::Character(key.into()))
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let path = std::env::temp_dir().join(format!("lumina_input_{}.ron", std::process::id()));
+        let path = path.to_str().unwrap();
+        let records = vec![
+            InputRecord::Seed(7),
+            InputRecord::Event {
+                frame: 0,
+                event: InputEvent::WindowResize {
+                    width: 800,
+                    height: 600,
+                },
+            },
+            InputRecord::Frame {
+                frame: 0,
+                delta_time: 0.016,
+            },
+            InputRecord::Event {
+                frame: 1,
+                event: InputEvent::KeyUp(Key::Named(NamedKey::Shift)),
+            },
+            InputRecord::Event {
+                frame: 1,
+                event: InputEvent::MouseEvent {
+                    button: MouseButton::Left,
+                    pressed: true,
+                },
+            },
+            InputRecord::Event {
+                frame: 1,
+                event: InputEvent::MouseMove(Vec2::new(12.5, -3.0)),
+            },
+        ];
+        let mut recorder = InputRecorder::create(path).unwrap();
+        for record in &records {
+            recorder.record(record).unwrap();
+        }
+        drop(recorder);
+
+        let recording = InputRecording::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(recording.records, records);
+        assert_eq!(recording.seed(), Some(7));
+    }
+
+    #[test]
+    fn test_parse_reports_line() {
+        let source = "Seed(1)\n\nFrame(frame: 0, delta_time: 0.1)\nJump(frame: 1)\n";
+        let err = InputRecording::parse(source).unwrap_err();
+        assert!(
+            matches!(&err, EngineError::Parse(line, _) if line == "line 4"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_replay_applies_events_of_each_frame() {
+        let recording = InputRecording {
+            records: vec![
+                InputRecord::Seed(1),
+                InputRecord::Frame {
+                    frame: 0,
+                    delta_time: 0.5,
+                },
+                key_down(1, "a"),
+                key_down(1, "b"),
+                InputRecord::Frame {
+                    frame: 1,
+                    delta_time: 0.25,
+                },
+                key_down(3, "c"),
+            ],
+        };
+        let mut world = World::load();
+        world.insert_resource(InputState::init());
+        world.insert_resource(WindowSize {
+            width: 0,
+            height: 0,
+        });
+        let mut replay = InputReplay::new(recording);
+
+        assert_eq!(replay.apply(&mut world, 0), Some(0.5));
+        assert!(!is_pressed(&world, "a"));
+        assert_eq!(replay.apply(&mut world, 1), Some(0.25));
+        assert!(is_pressed(&world, "a") && is_pressed(&world, "b"));
+        // Frames without a recorded delta time
+        assert_eq!(replay.apply(&mut world, 2), None);
+        assert!(!is_pressed(&world, "c"));
+        assert!(!replay.is_finished());
+        assert_eq!(replay.apply(&mut world, 3), None);
+        assert!(is_pressed(&world, "c"));
+        assert!(replay.is_finished());
+    }
+}
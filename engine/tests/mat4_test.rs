@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod mat4_test {
+    use lumina_engine::logic::ecs::component::camera::Camera;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::math::mat4::Mat4;
+    use lumina_engine::math::transformation::*;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+    use lumina_engine::render::resource::shader::material_parameter::MaterialParameter;
+    use proptest::prelude::*;
+
+    const EPSILON: f32 = 1e-3;
+
+    // How `create_transform_matrix` used to combine its matrices
+    fn multiply_rows(a: &[[f32; 4]; 4], b: &[[f32; 4]; 4]) -> [[f32; 4]; 4] {
+        let mut result = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                for k in 0..4 {
+                    result[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        result
+    }
+
+    fn assert_matrix_eq(a: Mat4, b: Mat4) -> Result<(), TestCaseError> {
+        for (col_a, col_b) in a.cols.iter().zip(b.cols.iter()) {
+            for (x, y) in col_a.iter().zip(col_b) {
+                prop_assert!((x - y).abs() < EPSILON, "{:?} != {:?}", a, b);
+            }
+        }
+        Ok(())
+    }
+
+    fn assert_point_eq(a: Vec3, b: Vec3) -> Result<(), TestCaseError> {
+        prop_assert!((a - b).length() < EPSILON, "{:?} != {:?}", a, b);
+        Ok(())
+    }
+
+    fn vec3() -> impl Strategy<Value = Vec3> {
+        (-10.0f32..10.0, -10.0f32..10.0, -1.0f32..1.0).prop_map(|(x, y, z)| Vec3::new(x, y, z))
+    }
+
+    fn transform() -> impl Strategy<Value = Transform> {
+        (
+            vec3(),
+            -7.0f32..7.0,
+            0.1f32..5.0,
+            0.1f32..5.0,
+            any::<bool>(),
+        )
+            .prop_map(
+                |(position, rotation, scale_x, scale_y, is_flipped)| Transform {
+                    position,
+                    rotation,
+                    scale: Vec2::new(scale_x, scale_y),
+                    is_flipped,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn test_matches_transformation_functions(transform in transform()) {
+            assert_matrix_eq(
+                Mat4::from_translation(transform.position),
+                create_translation_matrix(transform.position).into(),
+            )?;
+            assert_matrix_eq(
+                Mat4::from_rotation_z(transform.rotation),
+                create_rotation_matrix(transform.rotation).into(),
+            )?;
+            assert_matrix_eq(
+                Mat4::from_scale(transform.scale),
+                create_scale_matrix(transform.scale).into(),
+            )?;
+            let expected = multiply_rows(
+                &multiply_rows(
+                    &create_scale_matrix(transform.scale),
+                    &create_rotation_matrix(transform.rotation),
+                ),
+                &create_translation_matrix(transform.position),
+            );
+            assert_matrix_eq(create_transform_matrix(&transform, None).into(), expected.into())?;
+            assert_matrix_eq(Mat4::from_transform(&transform), expected.into())?;
+        }
+
+        #[test]
+        fn test_transform_point_matches_inherited_transform(parent in transform(), point in vec3()) {
+            // Children are placed without the parent's scale
+            let parent = Transform { scale: Vec2::unit(), is_flipped: false, ..parent };
+            let child = Transform { position: point, ..Default::default() };
+            assert_point_eq(
+                Mat4::from_transform(&parent).transform_point(point),
+                calc_inherited_transform(&child, Some(&parent)).position,
+            )?;
+        }
+
+        #[test]
+        fn test_transform_point_and_vector(transform in transform(), point in vec3()) {
+            let matrix = Mat4::from_transform(&transform);
+            let scaled = Vec2::new(point.x * transform.scale.x, point.y * transform.scale.y);
+            let expected = Vec3::from_vec2(scaled.rotated(transform.rotation), point.z);
+            assert_point_eq(matrix.transform_vector(point), expected)?;
+            assert_point_eq(matrix.transform_point(point), expected + transform.position)?;
+        }
+
+        #[test]
+        fn test_inverse(transform in transform(), point in vec3()) {
+            let matrix = Mat4::from_transform(&transform);
+            let inverse = matrix.inverse().unwrap();
+            assert_matrix_eq(inverse * matrix, Mat4::IDENTITY)?;
+            assert_matrix_eq(matrix * inverse, Mat4::IDENTITY)?;
+            assert_point_eq(inverse.transform_point(matrix.transform_point(point)), point)?;
+            prop_assert!((matrix.determinant() * inverse.determinant() - 1.0).abs() < EPSILON);
+        }
+
+        #[test]
+        fn test_composition(a in transform(), b in transform(), point in vec3()) {
+            let (a, b) = (Mat4::from_transform(&a), Mat4::from_transform(&b));
+            assert_point_eq(
+                (a * b).transform_point(point),
+                a.transform_point(b.transform_point(point)),
+            )?;
+            assert_matrix_eq((a * b).transpose(), b.transpose() * a.transpose())?;
+        }
+
+        #[test]
+        fn test_decompose(transform in transform()) {
+            let (position, rotation, scale) = Mat4::from_transform(&transform).decompose();
+            assert_point_eq(position, transform.position)?;
+            prop_assert!((rotation.sin() - transform.rotation.sin()).abs() < EPSILON);
+            prop_assert!((rotation.cos() - transform.rotation.cos()).abs() < EPSILON);
+            prop_assert!((scale - transform.scale).length() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_singular_matrix_has_no_inverse() {
+        assert!(Mat4::from_scale(Vec2::new(0.0, 1.0)).inverse().is_none());
+        assert_eq!(Mat4::IDENTITY.inverse(), Some(Mat4::IDENTITY));
+    }
+
+    #[test]
+    fn test_mirrored_decompose() {
+        let (_, _, scale) = Mat4::from_scale(Vec2::new(-2.0, 3.0)).decompose();
+        assert_eq!((scale.x, scale.y), (-2.0, 3.0));
+    }
+
+    #[test]
+    fn test_camera_matrices() {
+        let camera = Camera {
+            position: Vec3::new(1.0, -2.0, 3.0),
+            move_speed: 0.0,
+            zoom_speed: 0.0,
+            near: 0.1,
+            far: 100.0,
+        };
+        let view = camera.get_view_matrix();
+        assert_eq!(view.to_cols_array(), create_view_matrix(camera.position));
+        assert_eq!(
+            view.inverse().unwrap().transform_point(Vec3::zero()),
+            camera.position
+        );
+        assert_eq!(
+            camera.get_projection_matrix(1.5).to_cols_array(),
+            create_ortographic_projection_matrix(1.5, 0.1, 100.0)
+        );
+        assert!(matches!(
+            MaterialParameter::from(view),
+            MaterialParameter::Mat4(cols) if cols == view.cols
+        ));
+    }
+}
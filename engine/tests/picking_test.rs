@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod picking_test {
+    use lumina_engine::logic::ecs::component::camera::Camera;
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderShape};
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::scene::matrix_uniform_buffer::MatrixUniformBuffer;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+    use lumina_engine::render::uniform_buffer_source::UniformBufferSource;
+    use lumina_engine::shared::window_size::WindowSize;
+    use std::f32::consts::PI;
+
+    const WINDOW: WindowSize = WindowSize {
+        width: 800,
+        height: 600,
+    };
+
+    fn matrices(position: Vec3) -> MatrixUniformBuffer {
+        let camera = Camera {
+            position,
+            move_speed: 0.0,
+            zoom_speed: 0.0,
+            near: 0.1,
+            far: 100.0,
+        };
+        MatrixUniformBuffer {
+            projection_matrix: camera.get_projection_matrix(4.0 / 3.0).to_cols_array(),
+            view_matrix: camera.get_view_matrix().to_cols_array(),
+        }
+    }
+
+    fn world_with_camera(position: Vec3) -> World {
+        let mut world = World::load();
+        world.insert_resource(UniformBufferSource::new(0, matrices(position)));
+        world.insert_resource(WINDOW);
+        world
+    }
+
+    #[test]
+    fn test_screen_to_world_round_trip() {
+        for camera in [Vec3::zero(), Vec3::new(1.5, -0.5, -0.4)] {
+            let matrices = matrices(camera);
+            // The middle of the window is straight in front of the camera at every depth
+            for z in [-0.3, 0.0, 0.2] {
+                let center =
+                    Camera::screen_to_world(&matrices, &WINDOW, Vec2::new(400.0, 300.0), z)
+                        .unwrap();
+                assert!((center.xy() - camera.xy()).length() < 1e-5);
+                assert_eq!(center.z, z);
+
+                let point = Vec3::new(camera.x + 0.3, camera.y - 0.2, z);
+                let screen = Camera::world_to_screen(&matrices, &WINDOW, point);
+                let back = Camera::screen_to_world(&matrices, &WINDOW, screen, z).unwrap();
+                assert!((back - point).length() < 1e-4, "{:?} != {:?}", back, point);
+            }
+        }
+        // Screen coordinates grow downwards
+        let matrices = matrices(Vec3::zero());
+        let top_left = Camera::screen_to_world(&matrices, &WINDOW, Vec2::zero(), 0.0).unwrap();
+        assert!(top_left.x < 0.0 && top_left.y > 0.0);
+        assert!((top_left.y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_collider_contains_point() {
+        let rect = Collider::new(ColliderShape::Rect {
+            width: 2.0,
+            height: 1.0,
+        });
+        let position = Vec2::new(1.0, 1.0);
+        assert!(rect.contains_point(Vec2::new(1.9, 1.4), position, Vec2::unit(), 0.0));
+        assert!(!rect.contains_point(Vec2::new(1.9, 1.6), position, Vec2::unit(), 0.0));
+        // Turned upright and made twice as wide
+        assert!(rect.contains_point(Vec2::new(1.0, 1.9), position, Vec2::unit(), PI / 2.0));
+        assert!(!rect.contains_point(Vec2::new(1.9, 1.0), position, Vec2::unit(), PI / 2.0));
+        assert!(rect.contains_point(Vec2::new(2.9, 1.0), position, Vec2::new(2.0, 1.0), 0.0));
+
+        let capsule = Collider::new(ColliderShape::Capsule2D {
+            width: 1.0,
+            height: 3.0,
+        });
+        assert!(capsule.contains_point(Vec2::new(0.0, 1.4), Vec2::zero(), Vec2::unit(), 0.0));
+        assert!(capsule.contains_point(Vec2::new(0.45, 1.0), Vec2::zero(), Vec2::unit(), 0.0));
+        // Outside the rounded corner of the bounding box
+        assert!(!capsule.contains_point(Vec2::new(0.45, 1.45), Vec2::zero(), Vec2::unit(), 0.0));
+    }
+
+    #[test]
+    fn test_pick_front_most_first() {
+        let mut world = world_with_camera(Vec3::zero());
+        let [back, front, elsewhere, no_collider] = [(); 4].map(|_| world.create_entity());
+        for (entity, x, z) in [(back, 0.0, -0.2), (front, 0.05, 0.1), (elsewhere, 0.4, 0.0)] {
+            world.add_component(
+                entity,
+                Transform {
+                    position: Vec3::new(x, 0.0, z),
+                    scale: Vec2::unit(),
+                    ..Default::default()
+                },
+            );
+            world.add_component(
+                entity,
+                Collider::new(ColliderShape::Rect {
+                    width: 0.2,
+                    height: 0.2,
+                }),
+            );
+        }
+        world.add_component(no_collider, Transform::default());
+
+        let screen = Camera::world_to_screen(
+            &world
+                .get_resource::<UniformBufferSource<MatrixUniformBuffer>>()
+                .unwrap()
+                .data,
+            &WINDOW,
+            Vec3::new(0.02, 0.0, 0.0),
+        );
+        assert_eq!(world.pick(screen), vec![front, back]);
+        assert!(world.pick(Vec2::new(0.0, 0.0)).is_empty());
+    }
+}
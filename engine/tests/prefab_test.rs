@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod prefab_test {
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::component::force::Force;
+    use lumina_engine::logic::ecs::component::movement::Movement;
+    use lumina_engine::logic::ecs::component::parent::Parent;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::scene::prefab::PrefabLibrary;
+    use lumina_engine::logic::scene::snapshot::SnapshotRegistry;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::shared::engine_error::EngineError;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Component, Clone, Serialize, Deserialize)]
+    #[component(serialize)]
+    struct Target(Entity);
+
+    // Skipped fields can't be read back from bincode, which needs every field
+    #[derive(Component, Clone, Serialize, Deserialize)]
+    #[component(serialize)]
+    struct Label {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    }
+
+    const SINKING: &str = r#"(
+        components: {
+            "Transform": (scale: (x: 0.5, y: 0.5)),
+            "Force": (mass: 2.0, forces: [(id: "gravity", effect: Drag(0.5), mode: Continuous)]),
+        },
+        children: [(components: { "Transform": (rotation: 1.0) })],
+    )"#;
+
+    const BOAT: &str = r#"(
+        prefab: "sinking",
+        id: 1,
+        components: {
+            "Transform": (position: (x: 1.0, y: 2.0, z: 0.0), scale: (x: 3.0, y: 3.0)),
+            "Movement": (),
+        },
+        children: [
+            (id: 2, components: { "Target": ((id: 3, generation: 0)) }),
+            (
+                id: 3,
+                components: {
+                    "Target": ((id: 1, generation: 0)),
+                    "Parent": (parent: (id: 0, generation: 0)),
+                },
+            ),
+        ],
+    )"#;
+
+    fn world_with(prefabs: &[(&str, &str)]) -> World {
+        let mut world = World::load();
+        let mut registry = SnapshotRegistry::default();
+        registry.register_component::<Target>();
+        registry.register_component::<Label>();
+        world.insert_resource(registry);
+        let mut library = PrefabLibrary::new();
+        for (name, source) in prefabs {
+            library.insert(name, *source);
+        }
+        world.insert_resource(library);
+        world
+    }
+
+    #[test]
+    fn test_spawn_prefab_with_base_and_children() {
+        let mut world = world_with(&[("sinking", SINKING), ("boat", BOAT)]);
+        let instance = world.instantiate_prefab("boat").unwrap();
+        // Spawned through commands
+        assert!(world.get_component::<Transform>(instance.root).is_none());
+        world.apply_commands();
+
+        let root = instance.root;
+        assert_eq!(instance.entities.map(Entity::new(1, 0)), root);
+        let transform = world.get_component::<Transform>(root).unwrap();
+        assert_eq!(transform.position.y, 2.0);
+        assert_eq!(transform.scale.x, 3.0);
+        assert_eq!(world.get_component::<Force>(root).unwrap().mass, 2.0);
+        assert!(world.get_component::<Movement>(root).is_some());
+        assert!(world.get_component::<Parent>(root).is_none());
+
+        let mut children: Vec<Entity> = world
+            .query::<&Parent>()
+            .iter()
+            .filter(|(_, parent)| parent.parent == root)
+            .map(|(entity, _)| entity)
+            .collect();
+        children.sort_by_key(|entity| entity.id);
+        assert_eq!(children.len(), 2);
+        // Children of the base come first
+        assert_eq!(
+            world
+                .get_component::<Transform>(children[0])
+                .unwrap()
+                .rotation,
+            1.0
+        );
+        let (first, second) = (
+            instance.entities.map(Entity::new(2, 0)),
+            instance.entities.map(Entity::new(3, 0)),
+        );
+        assert_eq!(children[1], first);
+        assert_eq!(world.get_component::<Target>(first).unwrap().0, second);
+        assert_eq!(world.get_component::<Target>(second).unwrap().0, root);
+        assert!(world
+            .get_component::<Parent>(second)
+            .unwrap()
+            .parent
+            .is_null());
+    }
+
+    #[test]
+    fn test_prefabs_are_independent() {
+        let mut world = world_with(&[("sinking", SINKING)]);
+        let first = world.spawn_prefab("sinking").unwrap();
+        let second = world.spawn_prefab("sinking").unwrap();
+        world.apply_commands();
+        assert_ne!(first, second);
+        world.get_component_mut::<Transform>(first).unwrap().scale.x = 1.0;
+        assert_eq!(
+            world.get_component::<Transform>(second).unwrap().scale.x,
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_components_are_cloned_for_every_spawn() {
+        let mut world = world_with(&[("label", r#"(components: { "Label": () })"#)]);
+        let spawned = [(); 2].map(|_| world.spawn_prefab("label").unwrap());
+        world.apply_commands();
+        for entity in spawned {
+            assert!(world.get_component::<Label>(entity).unwrap().text.is_none());
+        }
+    }
+
+    #[test]
+    fn test_base_cycle_is_an_error() {
+        let world = world_with(&[
+            ("a", r#"(prefab: "b")"#),
+            ("b", r#"(children: [(prefab: "a")])"#),
+        ]);
+        let err = world.spawn_prefab("a").unwrap_err();
+        assert!(
+            matches!(&err, EngineError::Generic(message) if message.contains("a -> b -> a")),
+            "{}",
+            err
+        );
+        assert_eq!(world.entities.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_prefabs_are_errors() {
+        let world = world_with(&[
+            ("unknown", r#"(components: { "Unknown": () })"#),
+            ("textured", r#"(model: "square")"#),
+            ("duplicate", r#"(id: 1, children: [(id: 1)])"#),
+            ("duplicate_name", r#"(name: "a", children: [(name: "a")])"#),
+        ]);
+        assert!(matches!(
+            world.spawn_prefab("unknown"),
+            Err(EngineError::Parse(_, message)) if message.contains("unknown component 'Unknown'")
+        ));
+        // Without a resource manager there is nothing to load models or files with
+        assert!(matches!(
+            world.spawn_prefab("textured"),
+            Err(EngineError::Generic(_))
+        ));
+        assert!(matches!(
+            world.spawn_prefab("missing"),
+            Err(EngineError::Generic(_))
+        ));
+        assert!(matches!(
+            world.spawn_prefab("duplicate"),
+            Err(EngineError::Generic(_))
+        ));
+        assert!(matches!(
+            world.spawn_prefab("duplicate_name"),
+            Err(EngineError::Generic(_))
+        ));
+    }
+
+    #[test]
+    fn test_named_entities() {
+        let mut world = world_with(&[
+            ("sinking", SINKING),
+            (
+                "diver",
+                r#"(prefab: "sinking", name: "diver", children: [(name: "head")])"#,
+            ),
+        ]);
+        let instance = world.instantiate_prefab("diver").unwrap();
+        world.apply_commands();
+        assert_eq!(instance.named("diver"), Some(instance.root));
+        let head = instance.named("head").unwrap();
+        assert_eq!(
+            world.get_component::<Parent>(head).unwrap().parent,
+            instance.root
+        );
+        assert_eq!(instance.named("tank"), None);
+    }
+
+    #[test]
+    fn test_replacing_a_prefab_replaces_its_parsed_copy() {
+        let mut world = world_with(&[("sinking", SINKING), ("boat", BOAT)]);
+        world.spawn_prefab("boat").unwrap();
+        world.get_resource_mut::<PrefabLibrary>().unwrap().insert(
+            "sinking",
+            r#"(components: { "Transform": (scale: (x: 4.0, y: 4.0)) })"#,
+        );
+        let boat = world.spawn_prefab("boat").unwrap();
+        world.apply_commands();
+        // The boat is merged with the new base
+        assert!(world.get_component::<Force>(boat).is_none());
+        assert_eq!(world.get_component::<Transform>(boat).unwrap().scale.x, 3.0);
+    }
+}
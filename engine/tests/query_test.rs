@@ -0,0 +1,234 @@
+#[cfg(test)]
+mod query_test {
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::query_filter::{With, Without};
+    use lumina_engine::logic::scene::world::World;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Velocity(i32);
+
+    #[derive(Component)]
+    struct Frozen;
+
+    #[derive(Component)]
+    struct Unused;
+
+    fn create_world() -> World {
+        let mut world = World::load();
+        for i in 0..3 {
+            let entity = world.create_entity();
+            world.add_component(entity, Position(i));
+            world.add_component(entity, Velocity(1));
+        }
+        world
+    }
+
+    #[test]
+    fn test_query_writes_through_mutable_borrow() {
+        let world = create_world();
+        for (_, (position, velocity)) in world.query::<(&mut Position, &mut Velocity)>().iter() {
+            position.0 += velocity.0;
+            velocity.0 = 0;
+        }
+        let mut query = world.query::<(&Position, &Velocity)>();
+        let positions: Vec<i32> = query.iter().map(|(_, (position, _))| position.0).collect();
+        assert_eq!(positions, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_shared_queries_coexist() {
+        let world = create_world();
+        let mut outer = world.query::<(&Position,)>();
+        let mut inner = world.query::<(&Position,)>();
+        assert_eq!(outer.iter().count(), inner.iter().count());
+    }
+
+    #[test]
+    fn test_query_can_access_other_components_while_iterating() {
+        let world = create_world();
+        for (entity, (position,)) in world.query::<(&mut Position,)>().iter() {
+            position.0 += world.get_component::<Velocity>(entity).unwrap().0;
+        }
+        let mut query = world.query::<(&Position,)>();
+        let sum: i32 = query.iter().map(|(_, (position,))| position.0).sum();
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_mutable_query_conflicts_with_read() {
+        let world = create_world();
+        let _read = world.query::<(&Position,)>();
+        world.query::<(&mut Position,)>();
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_same_component_twice_in_query_panics() {
+        let world = create_world();
+        world.query::<(&mut Position, &Position)>();
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_get_component_mut_conflicts_with_query() {
+        let world = create_world();
+        for (entity, (_position,)) in world.query::<(&Position,)>().iter() {
+            world.get_component_mut::<Position>(entity);
+        }
+    }
+
+    #[test]
+    fn test_borrows_released_when_query_dropped() {
+        let world = create_world();
+        {
+            let mut query = world.query::<(&mut Position,)>();
+            assert_eq!(query.iter().count(), 3);
+        }
+        let mut query = world.query::<(&mut Position,)>();
+        assert_eq!(query.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_query_get() {
+        let mut world = create_world();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(10));
+
+        let mut query = world.query::<(&mut Position, &Velocity)>();
+        assert!(query.get(entity).is_none());
+        let first = query.iter().next().map(|(entity, _)| entity).unwrap();
+        let (position, velocity) = query.get(first).unwrap();
+        position.0 = 5;
+        assert_eq!(*velocity, Velocity(1));
+    }
+
+    fn create_mixed_world() -> (World, Entity, Entity, Entity) {
+        let mut world = World::load();
+        let moving = world.create_entity();
+        world.add_component(moving, Position(0));
+        world.add_component(moving, Velocity(2));
+        let frozen = world.create_entity();
+        world.add_component(frozen, Position(10));
+        world.add_component(frozen, Velocity(2));
+        world.add_component(frozen, Frozen);
+        let still = world.create_entity();
+        world.add_component(still, Position(20));
+        (world, moving, frozen, still)
+    }
+
+    fn entities<Q: lumina_engine::logic::ecs::query::Query>(world: &World) -> Vec<Entity> {
+        let mut query = world.query::<Q>();
+        let mut entities: Vec<Entity> = query.iter().map(|(entity, _)| entity).collect();
+        entities.sort_by_key(|entity| entity.id);
+        entities
+    }
+
+    #[test]
+    fn test_mixed_read_write_tuple() {
+        let (world, moving, frozen, _) = create_mixed_world();
+        for (_, (position, velocity)) in world.query::<(&mut Position, &Velocity)>().iter() {
+            position.0 += velocity.0;
+        }
+        assert_eq!(
+            *world.get_component::<Position>(moving).unwrap(),
+            Position(2)
+        );
+        assert_eq!(
+            *world.get_component::<Position>(frozen).unwrap(),
+            Position(12)
+        );
+    }
+
+    #[test]
+    fn test_with_filter() {
+        let (world, _, frozen, _) = create_mixed_world();
+        assert_eq!(entities::<(&Position, With<Frozen>)>(&world), vec![frozen]);
+    }
+
+    #[test]
+    fn test_without_filter() {
+        let (world, moving, _, still) = create_mixed_world();
+        assert_eq!(
+            entities::<(&Position, Without<Frozen>)>(&world),
+            vec![moving, still]
+        );
+        assert_eq!(
+            entities::<(&Position, &Velocity, Without<Frozen>)>(&world),
+            vec![moving]
+        );
+    }
+
+    #[test]
+    fn test_without_missing_storage_excludes_nothing() {
+        let (world, moving, frozen, still) = create_mixed_world();
+        assert_eq!(
+            entities::<(&Position, Without<Velocity>, Without<Unused>)>(&world),
+            vec![still]
+        );
+        assert_eq!(
+            entities::<(&Position,)>(&world),
+            vec![moving, frozen, still]
+        );
+    }
+
+    #[test]
+    fn test_with_missing_storage_matches_nothing() {
+        let mut world = World::load();
+        let entity = world.create_entity();
+        world.add_component(entity, Position(0));
+        assert!(entities::<(&Position, With<Frozen>)>(&world).is_empty());
+    }
+
+    #[test]
+    fn test_optional_component() {
+        let (world, moving, _, still) = create_mixed_world();
+        let mut query = world.query::<(&Position, Option<&Velocity>, Without<Frozen>)>();
+        let mut results: Vec<(Entity, Option<i32>)> = query
+            .iter()
+            .map(|(entity, (_, velocity, _))| (entity, velocity.map(|velocity| velocity.0)))
+            .collect();
+        results.sort_by_key(|(entity, _)| entity.id);
+        assert_eq!(results, vec![(moving, Some(2)), (still, None)]);
+    }
+
+    #[test]
+    fn test_optional_mutable_component() {
+        let (world, moving, _, still) = create_mixed_world();
+        for (_, (velocity, _)) in world.query::<(Option<&mut Velocity>, &Position)>().iter() {
+            if let Some(velocity) = velocity {
+                velocity.0 = 0;
+            }
+        }
+        assert_eq!(
+            *world.get_component::<Velocity>(moving).unwrap(),
+            Velocity(0)
+        );
+        assert!(world.get_component::<Velocity>(still).is_none());
+    }
+
+    #[test]
+    fn test_only_optional_components_visit_every_entity() {
+        let (mut world, moving, frozen, still) = create_mixed_world();
+        let empty = world.create_entity();
+        assert_eq!(
+            entities::<(Option<&Velocity>,)>(&world),
+            vec![moving, frozen, still, empty]
+        );
+        assert_eq!(
+            entities::<(Without<Frozen>,)>(&world),
+            vec![moving, still, empty]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_filter_conflicts_with_mutable_access() {
+        let (world, _, _, _) = create_mixed_world();
+        world.query::<(&mut Position, Without<Position>)>();
+    }
+}
@@ -0,0 +1,263 @@
+#[cfg(test)]
+mod rigid_body_test {
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderShape};
+    use lumina_engine::logic::ecs::component::force::{
+        AppliedForce, Force, ForceEffect, ForceMode,
+    };
+    use lumina_engine::logic::ecs::component::global_transform::GlobalTransform;
+    use lumina_engine::logic::ecs::component::movement::Movement;
+    use lumina_engine::logic::ecs::component::parent::Parent;
+    use lumina_engine::logic::ecs::component::rigid_body::{BodyType, RigidBody};
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::collision_system::CollisionSystem;
+    use lumina_engine::logic::ecs::system::movement_system::MovementSystem;
+    use lumina_engine::logic::ecs::system::schedule::SystemConfig;
+    use lumina_engine::logic::ecs::system::system::ParallelSystem;
+    use lumina_engine::logic::ecs::system::transform_propagation_system::TransformPropagationSystem;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+    use std::f32::consts::PI;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn spawn(
+        world: &mut World,
+        position: Vec2,
+        velocity: Vec2,
+        rigid_body: Option<RigidBody>,
+    ) -> Entity {
+        let entity = world.create_entity();
+        let transform = Transform {
+            position: Vec3::from_vec2(position, 0.0),
+            scale: Vec2::unit(),
+            ..Default::default()
+        };
+        world.add_component(
+            entity,
+            GlobalTransform {
+                transform: transform.clone(),
+                previous: transform.clone(),
+            },
+        );
+        world.add_component(entity, transform);
+        world.add_component(
+            entity,
+            Collider::new(ColliderShape::Rect {
+                width: 1.0,
+                height: 1.0,
+            }),
+        );
+        world.add_component(
+            entity,
+            Movement {
+                velocity: Vec3::from_vec2(velocity, 0.0),
+                ..Default::default()
+            },
+        );
+        world.add_component(entity, Force::new(1.0));
+        if let Some(rigid_body) = rigid_body {
+            world.add_component(entity, rigid_body);
+        }
+        entity
+    }
+
+    fn position(world: &World, entity: Entity) -> Vec2 {
+        world
+            .get_component::<Transform>(entity)
+            .unwrap()
+            .position
+            .xy()
+    }
+
+    fn velocity(world: &World, entity: Entity) -> Vec2 {
+        world
+            .get_component::<Movement>(entity)
+            .unwrap()
+            .velocity
+            .xy()
+    }
+
+    #[test]
+    fn test_static_body_stops_dynamic_body() {
+        let mut world = World::load();
+        let ground = spawn(
+            &mut world,
+            Vec2::zero(),
+            Vec2::zero(),
+            Some(RigidBody::new(BodyType::Static)),
+        );
+        let body = spawn(&mut world, Vec2::new(0.0, 0.9), Vec2::new(0.0, -1.0), None);
+        CollisionSystem.run(&world, 0.1);
+
+        assert_eq!(position(&world, ground), Vec2::zero());
+        assert_eq!(velocity(&world, ground), Vec2::zero());
+        assert!(position(&world, body).y > 0.9);
+        assert!(velocity(&world, body).length() < EPSILON);
+    }
+
+    #[test]
+    fn test_child_body_is_resolved_in_its_parent_space() {
+        let mut world = World::load();
+        spawn(
+            &mut world,
+            Vec2::zero(),
+            Vec2::zero(),
+            Some(RigidBody::new(BodyType::Static)),
+        );
+        // Upside down, so the child's local axes point the other way
+        let parent = world.create_entity();
+        world.add_component(
+            parent,
+            GlobalTransform {
+                transform: Transform {
+                    rotation: PI,
+                    scale: Vec2::unit(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let body = spawn(&mut world, Vec2::new(0.0, 0.9), Vec2::zero(), None);
+        world.add_component(body, Parent::from(parent));
+        world.get_component_mut::<Transform>(body).unwrap().position = Vec3::new(0.0, -0.9, 0.0);
+        world.get_component_mut::<Movement>(body).unwrap().velocity = Vec3::new(0.0, 1.0, 0.0);
+        CollisionSystem.run(&world, 0.1);
+
+        // Pushed up and stopped in world space, which is down in the parent's space
+        assert!(position(&world, body).y < -0.9);
+        assert!(position(&world, body).x.abs() < EPSILON);
+        assert!(velocity(&world, body).length() < EPSILON);
+    }
+
+    #[test]
+    fn test_restitution_and_mass() {
+        let mut world = World::load();
+        let bouncy = RigidBody::default().with_restitution(1.0);
+        let a = spawn(
+            &mut world,
+            Vec2::new(-0.45, 0.0),
+            Vec2::new(1.0, 0.0),
+            Some(bouncy.clone()),
+        );
+        let b = spawn(
+            &mut world,
+            Vec2::new(0.45, 0.0),
+            Vec2::new(-1.0, 0.0),
+            Some(bouncy),
+        );
+        CollisionSystem.run(&world, 0.1);
+        // Equal masses swap their velocities in an elastic collision
+        assert!((velocity(&world, a) - Vec2::new(-1.0, 0.0)).length() < EPSILON);
+        assert!((velocity(&world, b) - Vec2::new(1.0, 0.0)).length() < EPSILON);
+        assert!((position(&world, a).x + position(&world, b).x).abs() < EPSILON);
+
+        // A ten times heavier body hardly slows down
+        let mut world = World::load();
+        let heavy = spawn(&mut world, Vec2::new(-0.45, 0.0), Vec2::new(1.0, 0.0), None);
+        let light = spawn(&mut world, Vec2::new(0.45, 0.0), Vec2::zero(), None);
+        world.get_component_mut::<Force>(heavy).unwrap().mass = 10.0;
+        CollisionSystem.run(&world, 0.1);
+        let momentum = velocity(&world, heavy) * 10.0 + velocity(&world, light);
+        assert!((momentum - Vec2::new(10.0, 0.0)).length() < EPSILON);
+        assert!((velocity(&world, heavy).x - velocity(&world, light).x).abs() < EPSILON);
+        // And is pushed out of the overlap ten times less
+        let light_moved = position(&world, light).x - 0.45;
+        let heavy_moved = -0.45 - position(&world, heavy).x;
+        assert!((light_moved - heavy_moved * 10.0).abs() < EPSILON && heavy_moved > 0.0);
+    }
+
+    #[test]
+    fn test_kinematic_body_pushes_without_being_pushed() {
+        let mut world = World::load();
+        let kinematic = spawn(
+            &mut world,
+            Vec2::zero(),
+            Vec2::new(1.0, 0.0),
+            Some(RigidBody::new(BodyType::Kinematic)),
+        );
+        let body = spawn(&mut world, Vec2::new(0.9, 0.0), Vec2::zero(), None);
+        CollisionSystem.run(&world, 0.1);
+        assert_eq!(position(&world, kinematic), Vec2::zero());
+        assert_eq!(velocity(&world, kinematic), Vec2::new(1.0, 0.0));
+        assert!((velocity(&world, body) - Vec2::new(1.0, 0.0)).length() < EPSILON);
+
+        // Two bodies that can't be pushed ignore each other
+        let other = spawn(
+            &mut world,
+            Vec2::new(-0.5, 0.0),
+            Vec2::zero(),
+            Some(RigidBody::new(BodyType::Static)),
+        );
+        CollisionSystem.run(&world, 0.1);
+        assert_eq!(position(&world, other), Vec2::new(-0.5, 0.0));
+    }
+
+    #[test]
+    fn test_friction_slows_sliding() {
+        let velocity_after = |friction: f32| {
+            let mut world = World::load();
+            spawn(
+                &mut world,
+                Vec2::zero(),
+                Vec2::zero(),
+                Some(RigidBody::new(BodyType::Static).with_friction(friction)),
+            );
+            let body = spawn(
+                &mut world,
+                Vec2::new(0.0, 0.95),
+                Vec2::new(1.0, -1.0),
+                Some(RigidBody::default().with_friction(friction)),
+            );
+            CollisionSystem.run(&world, 0.1);
+            velocity(&world, body)
+        };
+        assert!((velocity_after(0.0) - Vec2::new(1.0, 0.0)).length() < EPSILON);
+        // The friction impulse is limited by the normal impulse
+        assert!((velocity_after(0.5) - Vec2::new(0.5, 0.0)).length() < EPSILON);
+        assert!(velocity_after(2.0).length() < EPSILON);
+    }
+
+    #[test]
+    fn test_body_comes_to_rest_on_static_body() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(SystemConfig::parallel("movement", MovementSystem));
+        scene.register_system(
+            SystemConfig::new("transform_propagation", TransformPropagationSystem)
+                .after("movement"),
+        );
+        scene.register_system(
+            SystemConfig::parallel("collision", CollisionSystem).after("transform_propagation"),
+        );
+        let world = scene.get_world_mut();
+        spawn(
+            world,
+            Vec2::zero(),
+            Vec2::zero(),
+            Some(RigidBody::new(BodyType::Static)),
+        );
+        let body = spawn(
+            world,
+            Vec2::new(0.0, 2.0),
+            Vec2::zero(),
+            Some(RigidBody::default().with_restitution(0.5)),
+        );
+        world
+            .get_component_mut::<Force>(body)
+            .unwrap()
+            .apply_force(AppliedForce {
+                id: "gravity".to_string(),
+                effect: ForceEffect::Linear(Vec3::new(0.0, -10.0, 0.0)),
+                mode: ForceMode::Continuous,
+            });
+        for _ in 0..600 {
+            scene.update(1.0 / 60.0);
+        }
+        let world = scene.get_world();
+        assert!((position(world, body).y - 1.0).abs() < 0.02);
+        assert!(velocity(world, body).length() < 0.2);
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod schedule_test {
+    use lumina_engine::logic::ecs::system::schedule::{Schedule, Stage, SystemConfig};
+    use lumina_engine::logic::ecs::system::system::System;
+    use lumina_engine::logic::scene::debug_config::DebugConfig;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::shared::engine_error::EngineError;
+    use std::sync::{Arc, Mutex};
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Record(&'static str, Log);
+
+    impl System for Record {
+        fn run(&mut self, _: &mut World, _: f32) {
+            self.1.lock().unwrap().push(self.0);
+        }
+    }
+
+    fn record(name: &'static str, log: &Log) -> SystemConfig {
+        SystemConfig::new(name, Record(name, log.clone()))
+    }
+
+    #[test]
+    fn test_unconstrained_systems_keep_registration_order() {
+        let log = Log::default();
+        let mut schedule = Schedule::new();
+        for name in ["c", "a", "b"] {
+            schedule.add_system(record(name, &log));
+        }
+        assert_eq!(
+            schedule.system_names(Stage::Update).unwrap(),
+            ["c", "a", "b"]
+        );
+    }
+
+    #[test]
+    fn test_before_and_after_constraints() {
+        let log = Log::default();
+        let mut schedule = Schedule::new();
+        schedule.add_system(record("collision", &log).after("movement"));
+        schedule.add_system(record("terrain_collision", &log).after("collision"));
+        schedule.add_system(record("input", &log).before("movement"));
+        schedule.add_system(record("movement", &log));
+
+        let mut world = World::load();
+        schedule.run_stage(Stage::Update, &mut world, 0.016);
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["input", "movement", "collision", "terrain_collision"]
+        );
+    }
+
+    #[test]
+    fn test_cycle_is_an_error() {
+        let log = Log::default();
+        let mut schedule = Schedule::new();
+        schedule.add_system(record("a", &log).after("c"));
+        schedule.add_system(record("b", &log).after("a"));
+        schedule.add_system(record("c", &log).after("b"));
+        schedule.add_system(record("d", &log));
+
+        let err = schedule.build().unwrap_err();
+        assert!(
+            matches!(&err, EngineError::SystemCycle(cycle) if cycle == &["a", "b", "c", "a"]),
+            "{}",
+            err
+        );
+        assert_eq!(err.to_string(), "System ordering cycle: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn test_self_dependency_is_a_cycle() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(record("a", &Log::default()).before("a"));
+        assert!(matches!(
+            schedule.build(),
+            Err(EngineError::SystemCycle(cycle)) if cycle == ["a", "a"]
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "System ordering cycle")]
+    fn test_running_cyclic_schedule_panics() {
+        let log = Log::default();
+        let mut schedule = Schedule::new();
+        schedule.add_system(record("a", &log).after("b"));
+        schedule.add_system(record("b", &log).after("a"));
+        schedule.run_stage(Stage::Update, &mut World::load(), 0.016);
+    }
+
+    #[test]
+    fn test_constraints_only_resolve_within_a_stage() {
+        let log = Log::default();
+        let mut schedule = Schedule::new();
+        schedule.add_system(record("input", &log).in_stage(Stage::PreUpdate));
+        schedule.add_system(record("movement", &log).after("input"));
+        assert!(matches!(
+            schedule.build(),
+            Err(EngineError::UnknownSystem(name, other)) if name == "movement" && other == "input"
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_names_are_an_error() {
+        let log = Log::default();
+        let mut schedule = Schedule::new();
+        schedule.add_system(record("a", &log));
+        schedule.add_system(record("a", &log));
+        assert!(matches!(
+            schedule.build(),
+            Err(EngineError::DuplicateSystem(name)) if name == "a"
+        ));
+        assert!(schedule.remove_system("a"));
+        assert!(schedule.build().is_ok());
+    }
+
+    #[test]
+    fn test_stages_run_in_order() {
+        let log = Log::default();
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(record("extract", &log).in_stage(Stage::Extract));
+        scene.register_system(record("post", &log).in_stage(Stage::PostUpdate));
+        scene.register_system(record("update", &log));
+        scene.register_system(record("pre", &log).in_stage(Stage::PreUpdate));
+
+        scene.update(0.016);
+        assert_eq!(*log.lock().unwrap(), ["pre", "update", "post"]);
+        scene.extract();
+        assert_eq!(*log.lock().unwrap(), ["pre", "update", "post", "extract"]);
+    }
+
+    #[test]
+    fn test_run_condition() {
+        let log = Log::default();
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(record("debug", &log).run_if(|world| {
+            world
+                .get_resource::<DebugConfig>()
+                .is_some_and(|config| config.enabled)
+        }));
+
+        scene.update(0.016);
+        assert!(log.lock().unwrap().is_empty());
+        scene
+            .get_world_mut()
+            .expect_resource_mut::<DebugConfig>()
+            .enabled = true;
+        scene.update(0.016);
+        assert_eq!(*log.lock().unwrap(), ["debug"]);
+    }
+
+    #[test]
+    fn test_default_engine_schedule_builds() {
+        let mut scene = Scene::new();
+        assert!(scene.schedule.build().is_ok());
+        assert_eq!(
+            scene.schedule.system_names(Stage::FixedUpdate).unwrap(),
+            [
+                "previous_transform",
+                "movement",
+                "fixed_transform_propagation",
+                "collision"
+            ]
+        );
+        assert_eq!(
+            scene.schedule.system_names(Stage::Update).unwrap(),
+            ["particle", "emitter"]
+        );
+    }
+}
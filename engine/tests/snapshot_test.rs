@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod snapshot_test {
+    use lumina_engine::logic::ecs::component::collider::{Collider, ColliderShape};
+    use lumina_engine::logic::ecs::component::component::Component;
+    use lumina_engine::logic::ecs::component::force::{
+        AppliedForce, Force, ForceEffect, ForceMode,
+    };
+    use lumina_engine::logic::ecs::component::movement::Movement;
+    use lumina_engine::logic::ecs::component::parent::Parent;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::entity::entity_map::EntityMap;
+    use lumina_engine::logic::scene::snapshot::{
+        SerializeResource, SnapshotFormat, SnapshotRegistry,
+    };
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::{vec2::Vec2, vec3::Vec3};
+    use lumina_engine::shared::engine_error::EngineError;
+    use lumina_engine::spawn_entity;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Component, Clone, Serialize, Deserialize)]
+    #[component(serialize)]
+    struct Target(Entity);
+
+    #[derive(Component)]
+    struct NotSaved;
+
+    #[derive(Serialize, Deserialize)]
+    struct Highscore {
+        holder: Entity,
+        score: u32,
+    }
+
+    impl SerializeResource for Highscore {
+        const NAME: &'static str = "Highscore";
+
+        fn map_entities(&mut self, entity_map: &EntityMap) {
+            self.holder = entity_map.map(self.holder);
+        }
+    }
+
+    fn registered_world() -> World {
+        let mut world = World::load();
+        let mut registry = SnapshotRegistry::default();
+        registry.register_component::<Target>();
+        registry.register_resource::<Highscore>();
+        world.insert_resource(registry);
+        world
+    }
+
+    fn transform(x: f32) -> Transform {
+        Transform {
+            position: Vec3::new(x, 2.0, 3.0),
+            rotation: 0.5,
+            scale: Vec2::new(1.0, 2.0),
+            is_flipped: true,
+        }
+    }
+
+    fn round_trip(world: &World, format: SnapshotFormat) -> (World, EntityMap) {
+        let data = world.save(format).unwrap();
+        let mut loaded = registered_world();
+        // Ids in the loaded world differ from the saved ones
+        for _ in 0..3 {
+            loaded.create_entity();
+        }
+        let entity_map = loaded.load_snapshot(&data, format).unwrap();
+        (loaded, entity_map)
+    }
+
+    #[test]
+    fn test_round_trip_remaps_parents() {
+        for format in [SnapshotFormat::Ron, SnapshotFormat::Binary] {
+            let mut world = registered_world();
+            let parent = spawn_entity!(world, transform(1.0));
+            let child = spawn_entity!(world, transform(2.0), Parent::from(parent));
+            let unsaved = spawn_entity!(world, NotSaved);
+
+            let (loaded, entity_map) = round_trip(&world, format);
+            assert_eq!(entity_map.len(), 2);
+            assert_eq!(entity_map.get(unsaved), None);
+            let (new_parent, new_child) = (entity_map.map(parent), entity_map.map(child));
+            assert_ne!(new_parent, parent);
+            assert_eq!(
+                loaded.get_component::<Parent>(new_child).unwrap().parent,
+                new_parent
+            );
+            let loaded_transform = loaded.get_component::<Transform>(new_child).unwrap();
+            assert_eq!(loaded_transform.position.x, 2.0);
+            assert_eq!(loaded_transform.scale.y, 2.0);
+            assert!(loaded_transform.is_flipped);
+            assert_eq!(loaded.query::<&NotSaved>().iter().count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_components_and_resources_round_trip() {
+        for format in [SnapshotFormat::Ron, SnapshotFormat::Binary] {
+            let mut world = registered_world();
+            let mut force = Force::new(2.0);
+            force.apply_force(AppliedForce {
+                id: "current".to_string(),
+                effect: ForceEffect::Linear(Vec3::new(0.5, 0.0, 0.0)),
+                mode: ForceMode::Continuous,
+            });
+            let fish = spawn_entity!(
+                world,
+                Movement {
+                    velocity: Vec3::new(1.0, -1.0, 0.0),
+                    ..Default::default()
+                },
+                force,
+                Collider::new(ColliderShape::Capsule2D {
+                    width: 0.1,
+                    height: 0.3,
+                })
+                .with_offset(Vec2::new(0.0, 0.05)),
+            );
+            let gone = spawn_entity!(world, NotSaved);
+            let hunter = spawn_entity!(world, Target(fish));
+            let lost = spawn_entity!(world, Target(gone));
+            world.insert_resource(Highscore {
+                holder: hunter,
+                score: 42,
+            });
+
+            let (loaded, entity_map) = round_trip(&world, format);
+            let fish = entity_map.map(fish);
+            assert_eq!(
+                loaded.get_component::<Movement>(fish).unwrap().velocity.y,
+                -1.0
+            );
+            let force = loaded.get_component::<Force>(fish).unwrap();
+            assert_eq!(force.mass, 2.0);
+            assert_eq!(force.get_linear_forces().next().unwrap().x, 0.5);
+            let collider = loaded.get_component::<Collider>(fish).unwrap();
+            assert!(
+                collider.shape
+                    == ColliderShape::Capsule2D {
+                        width: 0.1,
+                        height: 0.3
+                    }
+            );
+            assert_eq!(collider.offset.y, 0.05);
+            assert_eq!(
+                loaded
+                    .get_component::<Target>(entity_map.map(hunter))
+                    .unwrap()
+                    .0,
+                fish
+            );
+            // References to entities that weren't saved become null
+            assert!(loaded
+                .get_component::<Target>(entity_map.map(lost))
+                .unwrap()
+                .0
+                .is_null());
+            let highscore = loaded.expect_resource::<Highscore>();
+            assert_eq!(highscore.score, 42);
+            assert_eq!(highscore.holder, entity_map.map(hunter));
+        }
+    }
+
+    #[test]
+    fn test_ron_snapshot_is_readable() {
+        let mut world = registered_world();
+        spawn_entity!(world, transform(1.0));
+        let data = String::from_utf8(world.save(SnapshotFormat::Ron).unwrap()).unwrap();
+        assert!(data.contains("\"Transform\": ("), "{}", data);
+        assert!(!data.contains("NotSaved"));
+    }
+
+    #[test]
+    fn test_unknown_component_is_an_error() {
+        let mut world = registered_world();
+        spawn_entity!(world, transform(1.0));
+        let data = world.save(SnapshotFormat::Ron).unwrap();
+
+        let mut loaded = World::load();
+        loaded.insert_resource(SnapshotRegistry::empty());
+        let err = loaded
+            .load_snapshot(&data, SnapshotFormat::Ron)
+            .unwrap_err();
+        assert!(
+            matches!(&err, EngineError::Parse(_, message) if message.contains("unknown component 'Transform'")),
+            "{}",
+            err
+        );
+        assert!(loaded.entities.len() == 1);
+    }
+
+    #[test]
+    fn test_snapshot_needs_registry() {
+        let world = World::load();
+        assert!(matches!(
+            world.save(SnapshotFormat::Binary),
+            Err(EngineError::Generic(_))
+        ));
+    }
+}
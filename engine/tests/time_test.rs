@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod time_test {
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::time::Time;
+    use lumina_engine::render::resource::texture::texture::{AnimatedTexture, StaticTexture};
+    use std::time::Duration;
+
+    #[test]
+    fn test_step_advances_time() {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        let frame = scene.step(0.25);
+        assert_eq!(frame.elapsed, Duration::from_millis(250));
+        scene.step(0.5);
+
+        let time = scene.get_world().expect_resource::<Time>();
+        assert_eq!(time.frame(), 2);
+        assert_eq!(time.delta_time(), 0.5);
+        assert_eq!(time.elapsed_secs(), 0.75);
+    }
+
+    #[test]
+    fn test_animated_texture_follows_scene_time() {
+        let textures = (1..=4).map(|id| StaticTexture::new(id, 1, 1)).collect();
+        let texture = AnimatedTexture::new(textures, 400);
+        let id_at = |millis| {
+            texture
+                .current_texture(Duration::from_millis(millis))
+                .get_id()
+        };
+        assert_eq!(id_at(0), 1);
+        assert_eq!(id_at(150), 2);
+        assert_eq!(id_at(399), 4);
+        assert_eq!(id_at(400), 1);
+    }
+}
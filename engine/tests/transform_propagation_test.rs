@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod transform_propagation_test {
+    use lumina_engine::logic::ecs::component::global_transform::GlobalTransform;
+    use lumina_engine::logic::ecs::component::parent::Parent;
+    use lumina_engine::logic::ecs::component::previous_transform::PreviousTransform;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::logic::ecs::system::schedule::{Stage, SystemConfig};
+    use lumina_engine::logic::ecs::system::transform_propagation_system::TransformPropagationSystem;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::world::World;
+    use lumina_engine::math::transformation::calc_inherited_transform;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::math::vec3::Vec3;
+
+    fn scene() -> Scene {
+        let mut scene = Scene::new();
+        scene.schedule.clear();
+        scene.register_system(
+            SystemConfig::new("transform_propagation", TransformPropagationSystem)
+                .in_stage(Stage::PostUpdate),
+        );
+        scene
+    }
+
+    fn transform(x: f32, y: f32, rotation: f32, scale: f32, is_flipped: bool) -> Transform {
+        Transform {
+            position: Vec3::new(x, y, 0.1),
+            rotation,
+            scale: Vec2::new(scale, scale * 2.0),
+            is_flipped,
+        }
+    }
+
+    fn global(world: &World, entity: Entity) -> GlobalTransform {
+        world
+            .get_component::<GlobalTransform>(entity)
+            .as_deref()
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_matches_inherited_transforms() {
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let [root, flipped, child, grandchild] = [(); 4].map(|_| world.create_entity());
+        let transforms = [
+            transform(1.0, 2.0, 0.5, 2.0, false),
+            transform(-0.5, 0.25, 0.3, 0.5, true),
+            transform(0.75, -1.0, -0.2, 1.5, false),
+            transform(0.2, 0.4, 1.1, 0.8, true),
+        ];
+        for (entity, transform) in [root, flipped, child, grandchild].iter().zip(&transforms) {
+            world.add_component(*entity, transform.clone());
+        }
+        world.add_component(flipped, Parent::from(root));
+        world.add_component(child, Parent::from(flipped));
+        world.add_component(grandchild, Parent::from(child));
+        scene.update(0.0);
+
+        let mut expected = transforms[0].clone();
+        let world = scene.get_world();
+        assert_eq!(global(world, root).transform, expected);
+        for (entity, transform) in [flipped, child, grandchild].iter().zip(&transforms[1..]) {
+            expected = calc_inherited_transform(transform, Some(&expected));
+            assert_eq!(global(world, *entity).transform, expected);
+        }
+        // Flipping twice on the way down cancels out
+        assert!(global(world, flipped).transform.is_flipped);
+        assert!(global(world, child).transform.is_flipped);
+        assert!(!global(world, grandchild).transform.is_flipped);
+    }
+
+    #[test]
+    fn test_previous_uses_ancestor_previous_transforms() {
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let [root, child] = [(); 2].map(|_| world.create_entity());
+        let before = transform(0.0, 0.0, 0.0, 1.0, true);
+        world.add_component(root, transform(1.0, 0.0, 0.4, 1.0, false));
+        world.add_component(root, PreviousTransform(Some(before.clone())));
+        world.add_component(child, transform(0.5, 0.5, 0.1, 1.0, false));
+        world.add_component(child, Parent::from(root));
+        scene.update(0.0);
+
+        // The child has no previous transform of its own, so only the root's is used
+        let world = scene.get_world();
+        let child_transform = world.get_component::<Transform>(child).unwrap().clone();
+        assert_eq!(
+            global(world, child).previous,
+            calc_inherited_transform(&child_transform, Some(&before))
+        );
+    }
+
+    #[test]
+    fn test_follows_reparenting_and_null_parents() {
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let [a, b, child] = [(); 3].map(|_| world.create_entity());
+        world.add_component(a, transform(1.0, 0.0, 0.0, 1.0, false));
+        world.add_component(b, transform(0.0, 3.0, 0.0, 1.0, false));
+        world.add_component(child, transform(0.5, 0.0, 0.0, 1.0, false));
+        world.add_component(child, Parent::from(a));
+        scene.update(0.0);
+        assert_eq!(global(scene.get_world(), child).transform.position.x, 1.5);
+
+        scene.get_world_mut().set_parent(child, b).unwrap();
+        scene.update(0.0);
+        let position = global(scene.get_world(), child).transform.position;
+        assert_eq!((position.x, position.y), (0.5, 3.0));
+
+        // A hidden entity is placed as if it had no parent
+        scene
+            .get_world_mut()
+            .set_parent(child, Entity::NULL)
+            .unwrap();
+        scene.update(0.0);
+        assert_eq!(global(scene.get_world(), child).transform.position.y, 0.0);
+
+        scene.get_world_mut().remove_component::<Transform>(child);
+        scene.update(0.0);
+        assert!(scene
+            .get_world()
+            .get_component::<GlobalTransform>(child)
+            .is_none());
+    }
+
+    #[test]
+    fn test_cycles_are_skipped() {
+        let mut scene = scene();
+        let world = scene.get_world_mut();
+        let [a, b, root] = [(); 3].map(|_| world.create_entity());
+        for entity in [a, b, root] {
+            world.add_component(entity, transform(1.0, 0.0, 0.0, 1.0, false));
+        }
+        world.add_component(a, Parent::from(b));
+        world.add_component(b, Parent::from(a));
+        scene.update(0.0);
+        let world = scene.get_world();
+        assert_eq!(global(world, a), GlobalTransform::default());
+        assert_eq!(global(world, root).transform.position.x, 1.0);
+    }
+}
@@ -53,14 +53,14 @@ mod world_test {
         let world = create_world();
         let null_entity_opt = world.entities.get(0);
         assert!(null_entity_opt.is_some());
-        assert_eq!(Entity(0), *null_entity_opt.unwrap());
+        assert_eq!(Entity::NULL, *null_entity_opt.unwrap());
         assert_eq!(0, world.components.len());
     }
 
     #[test]
     fn test_create_entity() {
         let mut world = create_world();
-        assert_eq!(Entity(1), world.create_entity());
+        assert_eq!(Entity::new(1, 0), world.create_entity());
         assert_eq!(2, world.entities.len());
     }
 
@@ -77,7 +77,7 @@ mod world_test {
         assert!(world.get_component_mut::<MockComponent>(entity).is_none());
 
         let new_entity = world.create_entity();
-        assert_eq!(Entity(1), new_entity);
+        assert_eq!(Entity::new(1, 1), new_entity);
     }
 
     #[test]
@@ -120,10 +120,10 @@ mod world_test {
         let mut world = create_world();
         let entity = world.create_entity();
         assert!(world.get_storage::<MockComponent>().is_none());
-        assert!(world.get_storage_ptr::<MockComponent>().is_none());
+        assert!(world.get_storage_mut::<MockComponent>().is_none());
         world.add_component::<MockComponent>(entity, MockComponent {});
         assert!(world.get_storage::<MockComponent>().is_some());
-        assert!(world.get_storage_ptr::<MockComponent>().is_some());
+        assert!(world.get_storage_mut::<MockComponent>().is_some());
     }
 
     #[test]
@@ -142,9 +142,9 @@ mod world_test {
         let e1 = world.create_entity();
         let e2 = world.create_entity();
         let e3 = world.create_entity();
-        assert_eq!(e1, Entity(1));
-        assert_eq!(e2, Entity(2));
-        assert_eq!(e3, Entity(3));
+        assert_eq!(e1, Entity::new(1, 0));
+        assert_eq!(e2, Entity::new(2, 0));
+        assert_eq!(e3, Entity::new(3, 0));
         assert_eq!(world.entities.len(), 4); // null entity + 3
     }
 
@@ -156,16 +156,16 @@ mod world_test {
         world.delete_entity(e1);
         world.delete_entity(e2);
         let e3 = world.create_entity();
-        assert_eq!(e3, Entity(1));
+        assert_eq!(e3, Entity::new(1, 1));
         let e4 = world.create_entity();
-        assert_eq!(e4, Entity(2));
+        assert_eq!(e4, Entity::new(2, 1));
     }
 
     #[test]
     fn test_delete_nonexistent_entity() {
         let mut world = create_world();
         // Should not panic
-        world.delete_entity(Entity(999));
+        world.delete_entity(Entity::new(999, 0));
         assert_eq!(world.entities.len(), 1);
     }
 
@@ -223,7 +223,7 @@ mod world_test {
         let mut world = create_world();
         world.insert_resource(42u32);
         {
-            let r = world.get_resource_mut::<u32>().unwrap();
+            let mut r = world.get_resource_mut::<u32>().unwrap();
             *r = 100;
         }
         assert_eq!(*world.get_resource::<u32>().unwrap(), 100);
@@ -242,7 +242,7 @@ mod world_test {
         let mut world = create_world();
         world.insert_resource(String::from("hello"));
         let r = world.expect_resource::<String>();
-        assert_eq!(r, "hello");
+        assert_eq!(*r, "hello");
     }
 
     #[test]
@@ -256,38 +256,57 @@ mod world_test {
     fn test_expect_resource_mut() {
         let mut world = create_world();
         world.insert_resource(42u32);
-        let r = world.expect_resource_mut::<u32>();
-        *r = 100;
+        *world.expect_resource_mut::<u32>() = 100;
         assert_eq!(*world.expect_resource::<u32>(), 100);
     }
 
     #[test]
     #[should_panic(expected = "not found")]
     fn test_expect_resource_mut_panics_when_missing() {
-        let mut world = create_world();
+        let world = create_world();
         world.expect_resource_mut::<String>();
     }
 
     #[test]
-    fn test_get_resource_ptr() {
+    fn test_resource_shared_borrows_coexist() {
         let mut world = create_world();
         world.insert_resource(42u32);
-        let ptr = world.get_resource_ptr::<u32>();
-        assert!(ptr.is_some());
+        let a = world.expect_resource::<u32>();
+        let b = world.expect_resource::<u32>();
+        assert_eq!(*a + *b, 84);
     }
 
     #[test]
-    fn test_get_resource_ptr_none() {
+    #[should_panic(expected = "already borrowed")]
+    fn test_resource_mutable_borrow_conflict_panics() {
         let mut world = create_world();
-        let ptr = world.get_resource_ptr::<u32>();
-        assert!(ptr.is_none());
+        world.insert_resource(42u32);
+        let _read = world.expect_resource::<u32>();
+        world.expect_resource_mut::<u32>();
     }
 
     #[test]
-    #[should_panic(expected = "not found")]
-    fn test_expect_resource_ptr_panics_when_missing() {
+    fn test_resource_borrow_released_on_drop() {
+        let mut world = create_world();
+        world.insert_resource(42u32);
+        {
+            let _read = world.expect_resource::<u32>();
+        }
+        *world.expect_resource_mut::<u32>() += 1;
+        assert_eq!(*world.expect_resource::<u32>(), 43);
+    }
+
+    #[test]
+    fn test_resource_scope() {
         let mut world = create_world();
-        world.expect_resource_ptr::<String>();
+        world.insert_resource(1u32);
+        let entity = world.resource_scope(|world, counter: &mut u32| {
+            *counter += 1;
+            assert!(world.get_resource::<u32>().is_none());
+            world.create_entity()
+        });
+        assert!(world.is_alive(entity));
+        assert_eq!(*world.expect_resource::<u32>(), 2);
     }
 
     #[test]
@@ -297,22 +316,22 @@ mod world_test {
         world.insert_resource(String::from("hello"));
         world.insert_resource(3.14f64);
         assert_eq!(*world.get_resource::<u32>().unwrap(), 42);
-        assert_eq!(world.get_resource::<String>().unwrap(), "hello");
+        assert_eq!(*world.get_resource::<String>().unwrap(), "hello");
         assert_eq!(*world.get_resource::<f64>().unwrap(), 3.14);
     }
 
     #[test]
-    fn test_get_storage_ptr_mut() {
+    fn test_get_storage_mut() {
         let mut world = create_world();
         let entity = world.create_entity();
         world.add_component::<MockComponent>(entity, MockComponent {});
-        assert!(world.get_storage_ptr_mut::<MockComponent>().is_some());
+        assert!(world.get_storage_mut::<MockComponent>().is_some());
     }
 
     #[test]
-    fn test_get_storage_ptr_mut_none() {
-        let mut world = create_world();
-        assert!(world.get_storage_ptr_mut::<MockComponent>().is_none());
+    fn test_get_storage_mut_none() {
+        let world = create_world();
+        assert!(world.get_storage_mut::<MockComponent>().is_none());
     }
 
     #[test]
@@ -321,7 +340,91 @@ mod world_test {
         // Create and delete entities, null entity (0) should remain
         let e1 = world.create_entity();
         world.delete_entity(e1);
-        assert!(world.entities.contains(&Entity(0)));
+        assert!(world.entities.contains(&Entity::NULL));
+    }
+
+    #[test]
+    fn test_is_alive() {
+        let mut world = create_world();
+        let entity = world.create_entity();
+        assert!(world.is_alive(Entity::NULL));
+        assert!(world.is_alive(entity));
+        assert!(!world.is_alive(Entity::new(999, 0)));
+
+        world.delete_entity(entity);
+        assert!(!world.is_alive(entity));
+    }
+
+    #[test]
+    fn test_delete_then_recreate_bumps_generation() {
+        let mut world = create_world();
+        let old = world.create_entity();
+        world.delete_entity(old);
+        let new = world.create_entity();
+
+        assert_eq!(old.id, new.id);
+        assert_ne!(old, new);
+        assert_eq!(old.generation + 1, new.generation);
+        assert!(!world.is_alive(old));
+        assert!(world.is_alive(new));
+    }
+
+    #[test]
+    fn test_stale_handle_does_not_alias_recycled_entity() {
+        let mut world = create_world();
+        let old = world.create_entity();
+        world.delete_entity(old);
+        let new = world.create_entity();
+        world.add_component(new, Movement::default());
+
+        assert!(world.get_component::<Movement>(old).is_none());
+        assert!(world.get_component_mut::<Movement>(old).is_none());
+        assert!(world.remove_component::<Movement>(old).is_none());
+        assert!(world.get_component::<Movement>(new).is_some());
+    }
+
+    #[test]
+    fn test_add_component_to_stale_handle_is_rejected() {
+        let mut world = create_world();
+        let old = world.create_entity();
+        world.delete_entity(old);
+        let new = world.create_entity();
+
+        world.add_component(old, MockComponent {});
+
+        assert!(world.get_component::<MockComponent>(old).is_none());
+        assert!(world.get_component::<MockComponent>(new).is_none());
+    }
+
+    #[test]
+    fn test_delete_stale_handle_keeps_recycled_entity() {
+        let mut world = create_world();
+        let old = world.create_entity();
+        world.delete_entity(old);
+        let new = world.create_entity();
+        world.add_component(new, MockComponent {});
+
+        world.delete_entity(old);
+
+        assert!(world.is_alive(new));
+        assert!(world.entities.contains(&new));
+        assert!(world.get_component::<MockComponent>(new).is_some());
+    }
+
+    #[test]
+    fn test_query_skips_deleted_entities() {
+        let mut world = create_world();
+        let e1 = world.create_entity();
+        let e2 = world.create_entity();
+        world.add_component(e1, MockComponent {});
+        world.add_component(e2, MockComponent {});
+        world.delete_entity(e1);
+        world.create_entity();
+
+        let mut query = world.query::<(&MockComponent,)>();
+        let results: Vec<_> = query.iter().collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, e2);
     }
 
     #[test]
@@ -348,7 +451,8 @@ mod world_test {
             },
         );
 
-        let results: Vec<_> = world.query::<(&Transform,)>().collect();
+        let mut query = world.query::<(&Transform,)>();
+        let results: Vec<_> = query.iter().collect();
         assert_eq!(results.len(), 2);
     }
 
@@ -359,7 +463,8 @@ mod world_test {
         let _e2 = world.create_entity(); // no transform
         world.add_component(e1, MockComponent {});
 
-        let results: Vec<_> = world.query::<(&MockComponent,)>().collect();
+        let mut query = world.query::<(&MockComponent,)>();
+        let results: Vec<_> = query.iter().collect();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].0, e1);
     }
@@ -367,7 +472,8 @@ mod world_test {
     #[test]
     fn test_query_empty_world() {
         let world = create_world();
-        let results: Vec<_> = world.query::<(&MockComponent,)>().collect();
+        let mut query = world.query::<(&MockComponent,)>();
+        let results: Vec<_> = query.iter().collect();
         assert_eq!(results.len(), 0);
     }
 
@@ -385,7 +491,7 @@ mod world_test {
             },
         );
 
-        for (_entity, (transform,)) in world.query_mut::<(&mut Transform,)>() {
+        for (_entity, (transform,)) in world.query::<(&mut Transform,)>().iter() {
             transform.position.x = 99.0;
         }
 
@@ -403,7 +509,8 @@ mod world_test {
         // e2 only has Transform, not Movement
         world.add_component(e2, MockComponent {});
 
-        let results: Vec<_> = world.query::<(&MockComponent, &Movement)>().collect();
+        let mut query = world.query::<(&MockComponent, &Movement)>();
+        let results: Vec<_> = query.iter().collect();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].0, e1);
     }
@@ -18,25 +18,25 @@ pub struct CameraSystem;
 
 impl System for CameraSystem {
     fn run(&mut self, world: &mut World, _: f32) {
-        for (_, (camera, follow_component)) in world.query_mut::<(&mut Camera, &mut Follow)>() {
+        for (_, (camera, follow_component)) in world.query::<(&Camera, &Follow)>().iter() {
             let target_transform_component = world
-                .get_component_mut::<Transform>(follow_component.target_entity)
+                .get_component::<Transform>(follow_component.target_entity)
                 .expect("Failed to get target transform component for camera follow component!");
             let focal_offset = target_transform_component.position.xy() - camera.position.xy();
             let window_size = world.expect_resource::<WindowSize>();
             let aspect = window_size.width as f32 / window_size.height as f32;
-            if let Some(matrix_uniformbuffer) =
+            if let Some(mut matrix_uniformbuffer) =
                 world.get_resource_mut::<UniformBufferSource<MatrixUniformBuffer>>()
             {
                 matrix_uniformbuffer.update(MatrixUniformBuffer {
-                    projection_matrix: camera.get_projection_matrix(aspect),
-                    view_matrix: camera.get_view_matrix(),
+                    projection_matrix: camera.get_projection_matrix(aspect).to_cols_array(),
+                    view_matrix: camera.get_view_matrix().to_cols_array(),
                 });
             }
-            if let Some(focus_point) = world.get_resource_mut::<FocusPoint>() {
+            if let Some(mut focus_point) = world.get_resource_mut::<FocusPoint>() {
                 focus_point.0 = camera.position;
             }
-            if let Some(postprocess_config) = world.get_resource_mut::<PostprocessConfig>() {
+            if let Some(mut postprocess_config) = world.get_resource_mut::<PostprocessConfig>() {
                 postprocess_config
                     .material
                     .set_param("uAspectRatio", aspect);
@@ -1,6 +1,8 @@
 use lumina_engine::logic::ecs::{component::component::Component, entity::entity::Entity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
+#[component(serialize)]
 pub struct Follow {
     pub max_distance: f32,
     pub target_entity: Entity,
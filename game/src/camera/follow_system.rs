@@ -15,13 +15,13 @@ pub struct FollowSystem;
 
 impl System for FollowSystem {
     fn run(&mut self, world: &mut World, delta_time: f32) {
-        for (_, (camera, follow_component)) in world.query_mut::<(&mut Camera, &mut Follow)>() {
+        for (_, (camera, follow_component)) in world.query::<(&mut Camera, &Follow)>().iter() {
             let target_transform_component =
-                world.get_component_mut::<Transform>(follow_component.target_entity);
+                world.get_component::<Transform>(follow_component.target_entity);
             let player_state_component =
                 world.get_component::<PlayerState>(follow_component.target_entity);
             if let Some(target_transform) = target_transform_component {
-                let player_position = &mut target_transform.position;
+                let player_position = &target_transform.position;
                 let z_dest = match player_state_component {
                     Some(player_state_component) => player_state_component.cam_zoom(),
                     None => 0.0,
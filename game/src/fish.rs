@@ -1,3 +1,2 @@
 pub mod fish;
 pub mod fish_movement_system;
-pub mod fish_prefab;
@@ -1,5 +1,5 @@
 use lumina_engine::logic::{
-    ecs::{component::transform::Transform, system::system::System},
+    ecs::{access::Access, component::transform::Transform, system::system::ParallelSystem},
     scene::world::World,
 };
 
@@ -7,9 +7,13 @@ use super::fish::Fish;
 
 pub struct FishMovementSystem;
 
-impl System for FishMovementSystem {
-    fn run(&mut self, world: &mut World, _delta_time: f32) {
-        for (_entity, (transform, _fish)) in world.query_mut::<(&mut Transform, &mut Fish)>() {
+impl ParallelSystem for FishMovementSystem {
+    fn access(&self) -> Access {
+        Access::new().query::<(&mut Transform, &Fish)>()
+    }
+
+    fn run(&mut self, world: &World, _delta_time: f32) {
+        for (_entity, (transform, _fish)) in world.query::<(&mut Transform, &Fish)>().iter() {
             if transform.is_flipped {
                 transform.position.x += _delta_time * _fish.speed;
             } else {
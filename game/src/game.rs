@@ -1,23 +1,16 @@
-use std::{f32::consts::PI, time::Duration, vec};
+use std::{time::Duration, vec};
 
 use include_assets::{include_dir, NamedArchive};
 use lumina_engine::{
     logic::{
         ecs::{
             component::{
-                camera::Camera,
-                collider::{Collider, ColliderShape},
-                emitter::Emitter,
-                force::{AppliedForce, Force, ForceEffect, ForceMode},
-                material::Material,
-                model::Model,
-                movement::Movement,
-                parent::Parent,
+                camera::Camera, emitter::Emitter, material::Material, model::Model,
                 transform::Transform,
             },
-            entity::entity::Entity,
+            system::schedule::{Stage, SystemConfig},
         },
-        scene::{scene::Scene, world::World},
+        scene::{scene::Scene, snapshot::SnapshotRegistry, world::World},
     },
     math::{vec2::Vec2, vec3::Vec3},
     render::{
@@ -37,7 +30,7 @@ use lumina_engine::{
 
 use crate::{
     camera::{camera_system::CameraSystem, follow::Follow, follow_system::FollowSystem},
-    fish::fish_movement_system::FishMovementSystem,
+    fish::{fish::Fish, fish_movement_system::FishMovementSystem},
     player::{
         animation_system::AnimationSystem,
         conditional_parent::{AnimationCondition, ConditionalParent},
@@ -46,7 +39,6 @@ use crate::{
         player_movement_system::PlayerMovementSystem,
         player_part::PlayerPart,
         player_state::PlayerState,
-        player_state_definition::PlayerStateDefinition,
     },
     postprocess::{
         foreground::Foreground, postprocess_buffer_extractor::PostprocessBufferExtractor,
@@ -55,8 +47,10 @@ use crate::{
     },
     scene::{
         batch_spawn_system::BatchSpawnSystem, current_system::CurrentSystem, particle::Particle,
-        terrain::Terrain, terrain_collision_system::TerrainCollisionSystem,
-        terrain_system::TerrainSystem, water::Water,
+        terrain::Terrain, terrain_system::TerrainSystem, water::Water,
+    },
+    sea_trash::{
+        collected_trash::CollectedTrash, sea_trash::SeaTrash, sea_trash_system::SeaTrashSystem,
     },
 };
 
@@ -69,21 +63,46 @@ pub fn initialize_android(app: winit::platform::android::activity::AndroidApp) {
     lumina_engine::start_with_android_app(app, initialize_scene);
 }
 
-fn initialize_scene(scene: &mut Scene, resource_manager: &mut ResourceManager) {
+pub fn initialize_scene(scene: &mut Scene, resource_manager: &mut ResourceManager) {
     load_resources(resource_manager);
     init_world(scene.get_world_mut(), resource_manager);
-    scene.register_system(Box::new(InputSystem));
-    scene.register_system(Box::new(PlayerMovementSystem));
-    scene.register_system(Box::new(BatchSpawnSystem::new()));
-    scene.register_system(Box::new(FishMovementSystem));
-    scene.register_system(Box::new(CurrentSystem));
-    scene.register_system(Box::new(TerrainSystem));
-    scene.register_system(Box::new(FollowSystem));
-    scene.register_system(Box::new(CameraSystem));
-    scene.register_system(Box::new(AnimationSystem));
-    scene.register_system(Box::new(TerrainCollisionSystem));
-    scene.register_system(Box::new(UpdateFocalRadiusSystem));
-    scene.register_system(Box::new(UpdateGodRaysSystem));
+    scene.register_system(
+        SystemConfig::new("input", InputSystem)
+            .in_stage(Stage::PreUpdate)
+            .after("action"),
+    );
+    scene.register_system(
+        SystemConfig::new("player_movement", PlayerMovementSystem)
+            .in_stage(Stage::PreUpdate)
+            .after("input"),
+    );
+    scene.register_system(
+        SystemConfig::new("batch_spawn", BatchSpawnSystem::new())
+            .in_stage(Stage::PreUpdate)
+            .after("action"),
+    );
+    scene.register_system(
+        SystemConfig::parallel("fish_movement", FishMovementSystem)
+            .in_stage(Stage::FixedUpdate)
+            .after("previous_transform"),
+    );
+    scene.register_system(SystemConfig::parallel("current", CurrentSystem));
+    scene.register_system(SystemConfig::new("terrain", TerrainSystem));
+    scene.register_system(SystemConfig::new("animation", AnimationSystem));
+    scene.register_system(SystemConfig::new("sea_trash", SeaTrashSystem));
+    scene.register_system(SystemConfig::new("follow", FollowSystem).in_stage(Stage::PostUpdate));
+    scene.register_system(
+        SystemConfig::new("camera", CameraSystem)
+            .in_stage(Stage::PostUpdate)
+            .after("follow"),
+    );
+    scene.register_system(
+        SystemConfig::parallel("update_focal_radius", UpdateFocalRadiusSystem)
+            .in_stage(Stage::Extract),
+    );
+    scene.register_system(
+        SystemConfig::parallel("update_god_rays", UpdateGodRaysSystem).in_stage(Stage::Extract),
+    );
     scene.register_extractor(Box::new(PostprocessBufferExtractor));
 }
 
@@ -117,13 +136,28 @@ fn load_resources(resource_manager: &mut ResourceManager) {
     }
 }
 
+// Game components and resources that `World::save` writes next to the engine's
+pub fn register_snapshot_types(registry: &mut SnapshotRegistry) {
+    registry.register_component::<ConditionalParent>();
+    registry.register_component::<Fish>();
+    registry.register_component::<Follow>();
+    registry.register_component::<MultiConditionalParent>();
+    registry.register_component::<PlayerPart>();
+    registry.register_component::<PlayerState>();
+    registry.register_component::<SeaTrash>();
+    registry.register_resource::<Terrain>();
+    registry.register_resource::<Water>();
+}
+
 fn init_world(world: &mut World, resource_manager: &mut ResourceManager) {
+    register_snapshot_types(&mut world.expect_resource_mut::<SnapshotRegistry>());
     init_background(world, resource_manager); // TODO: fix this hack where background is initialized after other entities, causing it to render "on top of them"
     const WORLD_SEED: u32 = 696969;
     let terrain = Terrain::generate(world, 6969, resource_manager);
     world.insert_resource(terrain);
     let water = Water::create((WORLD_SEED ^ 0x5EAF00D).wrapping_mul(69696969));
     world.insert_resource(water);
+    world.insert_resource(CollectedTrash::default());
     let shader = resource_manager.get_shader("model").clone();
     let foreground = Foreground::construct();
     world.insert_resource(UniformBufferSource::new(
@@ -158,15 +192,22 @@ fn init_world(world: &mut World, resource_manager: &mut ResourceManager) {
         material: Material::new(Texture::None, postprocess_shader),
     });
 
-    let model_scale = 0.15;
-    let initial_position = Vec3::new(0.0, 0.25, 0.0);
-
     // Create player
+    let player = world
+        .instantiate_prefab_with("player", resource_manager)
+        .expect("Failed to spawn player prefab");
+    world.apply_commands();
+    let head_model = player.named("head").expect("Player prefab has no head");
+    let moving_head_model = player
+        .named("moving_head")
+        .expect("Player prefab has no moving head");
+    let player = player.root;
 
-    let player = world.create_entity();
-    let (camera, _) = world
+    let camera = world
         .query::<(&Camera,)>()
+        .iter()
         .next()
+        .map(|(camera, _)| camera)
         .expect("No camera found in the scene");
 
     world.add_component(
@@ -177,213 +218,6 @@ fn init_world(world: &mut World, resource_manager: &mut ResourceManager) {
         },
     );
 
-    world.add_component::<Collider>(
-        player,
-        Collider::new(ColliderShape::Capsule2D {
-            width: 0.4,
-            height: 1.4,
-        }),
-    );
-    world.add_component(player, PlayerState::Idle);
-    world.add_component(player, Movement::default());
-    let mut force_component = Force::new(10.0);
-    force_component.apply_force(AppliedForce {
-        id: "water_resistance".to_string(),
-        effect: ForceEffect::Drag(world.expect_resource::<Water>().get_resistance()),
-        mode: ForceMode::Continuous,
-    });
-    world.add_component(player, force_component);
-    world.add_component(
-        player,
-        Transform {
-            position: initial_position,
-            scale: Vec2::new(model_scale, model_scale),
-            rotation: 0.0,
-            is_flipped: false,
-        },
-    );
-
-    let left_hand_model = world.create_entity();
-    let legs_model = world.create_entity();
-    let moving_legs_model = world.create_entity();
-    let torso_model = world.create_entity();
-    let right_hand_model = world.create_entity();
-    let tank_model = world.create_entity();
-    let head_model = world.create_entity();
-    let moving_head_model = world.create_entity();
-
-    let mut head_textures: Vec<&str> = vec!["player/head2.png", "player/head3.png"];
-
-    let count = 22;
-    for _ in 0..count {
-        head_textures.push("player/head0.png");
-        head_textures.push("player/head1.png");
-    }
-
-    let moving_legs_textures: &[&str] = &["player/legs0.png", "player/legs1.png"];
-    let left_hand_texture = resource_manager
-        .load_static_texture("player/left_hand.png")
-        .unwrap();
-    let legs_texture = resource_manager
-        .load_static_texture("player/legs0.png")
-        .unwrap();
-    let torso_texture = resource_manager
-        .load_static_texture("player/torso.png")
-        .unwrap();
-    let right_hand_texture = resource_manager
-        .load_static_texture("player/right_hand.png")
-        .unwrap();
-    let tank_texture = resource_manager
-        .load_static_texture("player/tank.png")
-        .unwrap();
-    let head_texture = resource_manager
-        .load_animated_texture(head_textures.as_slice(), 6000)
-        .unwrap();
-    let moving_legs_texture = resource_manager
-        .load_animated_texture(
-            moving_legs_textures,
-            PlayerStateDefinition::Swimming.legs_animation_time(),
-        )
-        .unwrap();
-    let moving_head_texture = head_texture.clone();
-
-    world.add_component(
-        left_hand_model,
-        Material::new(left_hand_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        legs_model,
-        Material::new(legs_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        torso_model,
-        Material::new(torso_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        right_hand_model,
-        Material::new(right_hand_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        tank_model,
-        Material::new(tank_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        head_model,
-        Material::new(head_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        moving_legs_model,
-        Material::new(moving_legs_texture.into(), shader.clone()),
-    );
-    world.add_component(
-        moving_head_model,
-        Material::new(moving_head_texture.into(), shader.clone()),
-    );
-
-    let initial_scales = vec![
-        0.31640625, 0.4375, 0.23828125, 0.32421875, 0.25, 0.23828125, 0.4375, 0.23828125,
-    ];
-
-    let initial_positions = vec![
-        Vec3::from_vec2(Vec2::new(0.08984375, -0.03515625) * model_scale, 0.0),
-        Vec3::from_vec2(Vec2::new(-0.03125, -0.3984375) * model_scale, 0.0),
-        Vec3::from_vec2(Vec2::new(-0.05078125, 0.09765625) * model_scale, 0.0),
-        Vec3::from_vec2(Vec2::new(-0.03515625, -0.05078125) * model_scale, 0.0),
-        Vec3::from_vec2(Vec2::new(-0.2265625, 0.125) * model_scale, 0.0),
-        Vec3::from_vec2(Vec2::new(-0.03515625, 0.54296875) * model_scale, 0.0),
-        Vec3::from_vec2(Vec2::new(-0.03125, -0.3984375) * model_scale, 0.0),
-        Vec3::from_vec2(
-            Vec2::new(-0.03515625 - 0.05, 0.54296875 - 0.075) * model_scale,
-            0.0,
-        ),
-    ];
-
-    let pattern_mesh = resource_manager.get_mesh("square");
-
-    let children = vec![
-        left_hand_model,
-        legs_model,
-        torso_model,
-        right_hand_model,
-        tank_model,
-        head_model,
-        moving_legs_model,
-        moving_head_model,
-    ];
-
-    world.add_component(left_hand_model, PlayerPart::LeftHand);
-
-    world.add_component(legs_model, PlayerPart::Legs);
-    world.add_component(torso_model, PlayerPart::Torso);
-    world.add_component(right_hand_model, PlayerPart::RightHand);
-    world.add_component(tank_model, PlayerPart::Tank);
-    world.add_component(head_model, PlayerPart::Head);
-    world.add_component(moving_legs_model, PlayerPart::Legs);
-    world.add_component(moving_head_model, PlayerPart::Head);
-
-    for (idx, child) in children.iter().enumerate() {
-        world.add_component(
-            *child,
-            Transform {
-                position: initial_positions[idx],
-                rotation: match idx {
-                    7 => PI / 2.0,
-                    _ => 0.0,
-                },
-                scale: Vec2::new(2.0, 2.0) * initial_scales[idx],
-                is_flipped: false,
-            },
-        );
-        world.add_component::<Model>(*child, pattern_mesh.clone().into());
-        match idx {
-            1 | 6 => {
-                let condition = if idx == 1 {
-                    AnimationCondition::PlayerIdle
-                } else {
-                    AnimationCondition::PlayerSwimming
-                };
-                world.add_component::<MultiConditionalParent>(
-                    *child,
-                    vec![
-                        ConditionalParent {
-                            parent: player,
-                            condition: condition,
-                        },
-                        ConditionalParent {
-                            parent: Entity(0).into(),
-                            condition: AnimationCondition::True,
-                        },
-                    ]
-                    .into(),
-                );
-                world.add_component::<Parent>(*child, Entity(0).into());
-            }
-            5 | 7 => {
-                let condition = if idx == 5 {
-                    AnimationCondition::PlayerIdle
-                } else {
-                    AnimationCondition::PlayerSwimming
-                };
-                world.add_component::<MultiConditionalParent>(
-                    *child,
-                    vec![
-                        ConditionalParent {
-                            parent: player,
-                            condition: condition,
-                        },
-                        ConditionalParent {
-                            parent: Entity(0).into(),
-                            condition: AnimationCondition::True,
-                        },
-                    ]
-                    .into(),
-                );
-                world.add_component::<Parent>(*child, Entity(0).into())
-            }
-            _ => world.add_component::<Parent>(*child, player.into()),
-        }
-    }
-
     let bubble_mesh = resource_manager.get_mesh("square");
     let bubble_emitter = world.create_entity();
     world.add_component::<Emitter>(bubble_emitter, Particle::bubble().into());
@@ -419,7 +253,9 @@ fn init_world(world: &mut World, resource_manager: &mut ResourceManager) {
         ]
         .into(),
     );
-    world.add_component::<Parent>(bubble_emitter, head_model.into());
+    world
+        .set_parent(bubble_emitter, head_model)
+        .expect("Failed to parent bubble emitter");
 
     // Deep-water mote haze
     let mote_mesh = resource_manager.get_mesh("square");
@@ -440,7 +276,9 @@ fn init_world(world: &mut World, resource_manager: &mut ResourceManager) {
     if let Some(mote_texture) = resource_manager.load_static_texture("mote.png") {
         world.add_component(mote_entity, Material::new(mote_texture, shader.clone()));
     }
-    world.add_component::<Parent>(mote_entity, player.into());
+    world
+        .set_parent(mote_entity, player)
+        .expect("Failed to parent mote emitter");
 }
 
 fn init_background(world: &mut World, resource_manager: &mut ResourceManager) {
@@ -23,7 +23,8 @@ pub struct AnimationSystem;
 impl System for AnimationSystem {
     fn run(&mut self, world: &mut World, delta_time: f32) {
         world
-            .query_mut::<(&mut PlayerState, &mut Transform)>()
+            .query::<(&PlayerState, &mut Transform)>()
+            .iter()
             .last()
             .map(|(_, (player_state, transform))| {
                 Self::animate_player(world, player_state, transform, delta_time);
@@ -33,8 +34,8 @@ impl System for AnimationSystem {
 
 impl AnimationSystem {
     fn animate_player(
-        world: &mut World,
-        player_state: &mut PlayerState,
+        world: &World,
+        player_state: &PlayerState,
         transform: &mut Transform,
         delta_time: f32,
     ) {
@@ -62,18 +63,19 @@ impl AnimationSystem {
         }
         transform.is_flipped = !(0.0 <= transform.rotation && transform.rotation <= PI);
 
-        for (_, (parent, multi_conditional_parent)) in
-            world.query_mut::<(&mut Parent, &mut MultiConditionalParent)>()
+        for (_, (parent, multi_conditional_parent)) in world
+            .query::<(&mut Parent, &mut MultiConditionalParent)>()
+            .iter()
         {
             for conditional_parent in multi_conditional_parent.components.iter_mut() {
-                if Self::bind_parent(parent, conditional_parent, &player_state) {
+                if Self::bind_parent(parent, conditional_parent, player_state) {
                     break;
                 }
             }
         }
 
         for (_, (player_part, material_component)) in
-            world.query_mut::<(&mut PlayerPart, &mut Material)>()
+            world.query::<(&PlayerPart, &mut Material)>().iter()
         {
             if let Texture::AnimatedTexture(texture) = &mut material_component.texture {
                 if let PlayerPart::Legs = player_part {
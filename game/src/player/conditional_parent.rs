@@ -1,6 +1,7 @@
 use lumina_engine::logic::ecs::{component::component::Component, entity::entity::Entity};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum AnimationCondition {
     None,
     PlayerIdle,
@@ -8,7 +9,8 @@ pub enum AnimationCondition {
     True,
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
+#[component(serialize)]
 pub struct ConditionalParent {
     pub parent: Entity,
     pub condition: AnimationCondition,
@@ -1,8 +1,12 @@
-use lumina_engine::logic::ecs::component::component::Component;
+use lumina_engine::logic::ecs::{
+    component::component::{Component, SerializeComponent},
+    entity::entity_map::EntityMap,
+};
+use serde::{Deserialize, Serialize};
 
 use super::conditional_parent::ConditionalParent;
 
-#[derive(Component, Debug)]
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct MultiConditionalParent {
     pub components: Vec<ConditionalParent>,
 }
@@ -12,3 +16,13 @@ impl From<Vec<ConditionalParent>> for MultiConditionalParent {
         Self { components }
     }
 }
+
+impl SerializeComponent for MultiConditionalParent {
+    const NAME: &'static str = "MultiConditionalParent";
+
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        for conditional_parent in &mut self.components {
+            conditional_parent.map_entities(entity_map);
+        }
+    }
+}
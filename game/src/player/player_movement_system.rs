@@ -12,7 +12,7 @@ pub struct PlayerMovementSystem;
 
 impl System for PlayerMovementSystem {
     fn run(&mut self, world: &mut World, _: f32) {
-        for (_, (player_state, force)) in world.query_mut::<(&mut PlayerState, &mut Force)>() {
+        for (_, (player_state, force)) in world.query::<(&PlayerState, &mut Force)>().iter() {
             let direction = player_state.direction();
             let magnitude = player_state.acceleration() * force.mass;
             if magnitude > 0.0 {
@@ -1,6 +1,8 @@
 use lumina_engine::logic::ecs::component::component::Component;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
+#[component(serialize)]
 pub enum PlayerPart {
     LeftHand,
     Legs,
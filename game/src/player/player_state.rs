@@ -1,6 +1,8 @@
 use lumina_engine::{logic::ecs::component::component::Component, math::vec3::Vec3};
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Clone)]
+#[derive(Component, Clone, Serialize, Deserialize)]
+#[component(serialize)]
 pub enum PlayerState {
     Idle,
     Swimming { direction: Vec3 },
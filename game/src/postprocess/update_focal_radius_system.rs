@@ -1,5 +1,6 @@
+use lumina_engine::logic::ecs::access::Access;
 use lumina_engine::logic::ecs::component::transform::Transform;
-use lumina_engine::logic::ecs::system::system::System;
+use lumina_engine::logic::ecs::system::system::ParallelSystem;
 use lumina_engine::logic::scene::world::World;
 use lumina_engine::math::vec3::Vec3;
 use lumina_engine::render::uniform_buffer_source::UniformBufferSource;
@@ -10,24 +11,30 @@ use crate::postprocess::postprocess_uniform_buffer::PostProcessUniformBuffer;
 
 pub struct UpdateFocalRadiusSystem;
 
-impl System for UpdateFocalRadiusSystem {
-    fn run(&mut self, world: &mut World, delta_time: f32) {
+impl ParallelSystem for UpdateFocalRadiusSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .query::<(&PlayerState, &Transform)>()
+            .write_resource::<Foreground>()
+            .write_resource::<UniformBufferSource<PostProcessUniformBuffer>>()
+    }
+
+    fn run(&mut self, world: &World, delta_time: f32) {
         let mut player_position: Option<Vec3> = None;
         let mut light_level: Option<f32> = None;
 
         for (_, (player_state_component, transform)) in
-            world.query_mut::<(&mut PlayerState, &mut Transform)>()
+            world.query::<(&PlayerState, &Transform)>().iter()
         {
             player_position = transform.position.clone().into();
             light_level = player_state_component.light_level().into();
         }
         if let Some(_player_position) = player_position {
-            if let Some(foreground) = world.get_resource_ptr::<Foreground>() {
-                let foreground = unsafe { &mut *foreground };
+            if let Some(mut foreground) = world.get_resource_mut::<Foreground>() {
                 let focal_dest = light_level.unwrap_or(0.5);
                 let difference = focal_dest - foreground.focal_radius;
                 if 0.0 < difference.abs() {
-                    if let Some(postprocess_uniformbuffer) =
+                    if let Some(mut postprocess_uniformbuffer) =
                         world.get_resource_mut::<UniformBufferSource<PostProcessUniformBuffer>>()
                     {
                         postprocess_uniformbuffer.data.focal_radius = foreground.focal_radius;
@@ -1,6 +1,6 @@
 use lumina_engine::{
     logic::{
-        ecs::{component::camera::Camera, system::system::System},
+        ecs::{access::Access, component::camera::Camera, system::system::ParallelSystem},
         scene::world::World,
     },
     math::vec3::Vec3,
@@ -11,16 +11,23 @@ use crate::postprocess::foreground::Foreground;
 
 pub struct UpdateGodRaysSystem;
 
-impl System for UpdateGodRaysSystem {
-    fn run(&mut self, world: &mut World, _: f32) {
+impl ParallelSystem for UpdateGodRaysSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .read::<Camera>()
+            .write_resource::<Foreground>()
+            .write_resource::<PostprocessConfig>()
+    }
+
+    fn run(&mut self, world: &World, _: f32) {
         let camera_position: Option<Vec3> = world
-            .query_mut::<(&mut Camera,)>()
+            .query::<(&Camera,)>()
+            .iter()
             .last()
             .map(|(_, (camera,))| camera.position.clone());
 
         if let Some(camera_position) = camera_position {
-            if let Some(foreground) = world.get_resource_ptr::<Foreground>() {
-                let foreground = unsafe { &mut *foreground };
+            if let Some(mut foreground) = world.get_resource_mut::<Foreground>() {
                 let noise_index = (camera_position.x / foreground.god_rays_min_distance) as i32;
                 let difference = foreground.loaded_noise_index - noise_index;
                 if difference != 0 {
@@ -37,7 +44,8 @@ impl System for UpdateGodRaysSystem {
                         foreground.god_rays_noise.pop_front();
                     }
                 }
-                if let Some(postprocess_config) = world.get_resource_mut::<PostprocessConfig>() {
+                if let Some(mut postprocess_config) = world.get_resource_mut::<PostprocessConfig>()
+                {
                     let light_positions = foreground.get_light_positions();
                     postprocess_config
                         .material
@@ -3,8 +3,8 @@ pub mod current;
 pub mod current_system;
 pub mod object_type;
 pub mod particle;
+pub mod perlin_seed;
 pub mod terrain;
-pub mod terrain_collision_system;
 pub mod terrain_system;
 pub mod tile;
 pub mod water;
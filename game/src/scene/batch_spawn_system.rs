@@ -2,23 +2,19 @@ use std::{collections::VecDeque, f32::consts::PI};
 
 use lumina_engine::{
     logic::{
-        ecs::{component::transform::Transform, entity::entity::Entity, system::system::System},
+        ecs::{
+            component::{collider::Collider, transform::Transform},
+            entity::entity::Entity,
+            system::system::System,
+        },
         scene::world::World,
     },
-    math::vec3::Vec3,
-    shared::input::input_state::InputState,
+    math::{rect::Rect, vec3::Vec3},
+    shared::input::action_map::ActionMap,
 };
 use rand::{rngs::StdRng, Rng};
-use winit::keyboard::Key;
 
-use crate::{
-    fish::{fish::Fish, fish_prefab::FishPrefab},
-    player::player_state::PlayerState,
-    sea_trash::{
-        bottle_trash_prefab::BottleTrashPrefab, flipflop_trash_prefab::FlipflopTrashPrefab,
-        tuna_can_trash_prefab::TunaCanTrashPrefab,
-    },
-};
+use crate::{fish::fish::Fish, player::player_state::PlayerState};
 
 pub struct BatchSpawnSystem {
     pub spawned_fish: VecDeque<Entity>,
@@ -36,14 +32,29 @@ impl BatchSpawnSystem {
 
 impl System for BatchSpawnSystem {
     fn run(&mut self, world: &mut World, _: f32) {
-        for (_, (transform, _)) in world.query_mut::<(&mut Transform, &mut PlayerState)>() {
-            let rng = world.expect_resource_ptr::<StdRng>();
-            if let Some(input_state) = world.get_resource::<InputState>() {
-                if input_state.is_pressed(Key::Character("f".into())) {
-                    self.spawn_fish(world, transform, rng);
-                } else if input_state.is_pressed(Key::Character("t".into())) {
-                    self.spawn_sea_trash(world, transform, rng);
-                }
+        let (spawn_fish, spawn_sea_trash) = match world.get_resource::<ActionMap>() {
+            Some(actions) => (
+                actions.pressed("spawn_fish"),
+                actions.pressed("spawn_sea_trash"),
+            ),
+            None => return,
+        };
+        let mut rng = world.expect_resource_mut::<StdRng>();
+        for (_, (transform, _, collider)) in world
+            .query::<(&Transform, &PlayerState, Option<&Collider>)>()
+            .iter()
+        {
+            if spawn_fish {
+                self.spawn_fish(world, transform.position, &mut rng);
+            } else if spawn_sea_trash {
+                let diver_bounds = collider.map(|collider| {
+                    collider.bounds(
+                        transform.position.xy() + collider.offset,
+                        transform.scale,
+                        transform.rotation,
+                    )
+                });
+                self.spawn_sea_trash(world, transform.position, diver_bounds, &mut rng);
             }
         }
     }
@@ -54,64 +65,94 @@ const FISH_MAX_COUNT: usize = 500;
 
 const SEA_TRASH_BATCH_SIZE: usize = 5;
 const SEA_TRASH_MAX_COUNT: usize = 15;
+// Kept between new sea trash and the diver, so the diver doesn't collect it right away
+const SEA_TRASH_CLEARANCE: f32 = 0.1;
 
 impl BatchSpawnSystem {
-    fn spawn_fish(&mut self, world: &mut World, transform: &mut Transform, rng: *mut StdRng) {
+    fn spawn_fish(&mut self, world: &World, player_position: Vec3, rng: &mut StdRng) {
+        let mut commands = world.commands();
         for _ in 0..FISH_BATCH_SIZE {
             let random_offset = Vec3::new(
-                (unsafe { (*rng).gen::<f32>() } - 0.5) * 2.0,
-                (unsafe { (*rng).gen::<f32>() } - 0.5) * 2.0,
-                (unsafe { (*rng).gen::<f32>() } - 0.5) * 1.5,
+                (rng.gen::<f32>() - 0.5) * 2.0,
+                (rng.gen::<f32>() - 0.5) * 2.0,
+                (rng.gen::<f32>() - 0.5) * 1.5,
             );
-            let is_flipped = unsafe { (*rng).gen::<bool>() };
-            let speed_bias = (unsafe { (*rng).gen::<f32>() } - 0.5) * 0.03;
-            let spawn_position = transform.position + random_offset;
-            let fish_entity = FishPrefab::spawn(world);
-            world
-                .get_component_mut::<Transform>(fish_entity)
-                .map(|fish_transform| {
-                    fish_transform.position = spawn_position;
-                    fish_transform.is_flipped = is_flipped;
-                });
-            world.get_component_mut::<Fish>(fish_entity).map(|fish| {
-                fish.speed += speed_bias;
+            let is_flipped = rng.gen::<bool>();
+            let speed_bias = (rng.gen::<f32>() - 0.5) * 0.03;
+            let spawn_position = player_position + random_offset;
+            let fish_entity = world
+                .spawn_prefab("fish")
+                .expect("Failed to spawn fish prefab");
+            // The prefab is spawned when commands are applied, so adjust it after that
+            commands.add(move |world| {
+                world
+                    .get_component_mut::<Transform>(fish_entity)
+                    .map(|mut fish_transform| {
+                        fish_transform.position = spawn_position;
+                        fish_transform.is_flipped = is_flipped;
+                    });
+                world
+                    .get_component_mut::<Fish>(fish_entity)
+                    .map(|mut fish| {
+                        fish.speed += speed_bias;
+                    });
             });
             self.spawned_fish.push_back(fish_entity);
         }
         while self.spawned_fish.len() > FISH_MAX_COUNT {
             if let Some(old_entity) = self.spawned_fish.pop_front() {
-                world.delete_entity(old_entity);
+                commands.delete_entity(old_entity);
             }
         }
     }
 
-    fn spawn_sea_trash(&mut self, world: &mut World, transform: &mut Transform, rng: *mut StdRng) {
+    fn spawn_sea_trash(
+        &mut self,
+        world: &World,
+        player_position: Vec3,
+        diver_bounds: Option<Rect>,
+        rng: &mut StdRng,
+    ) {
+        let mut commands = world.commands();
+        let diver_bounds = diver_bounds.map(|bounds| bounds.grown(SEA_TRASH_CLEARANCE));
         for _ in 0..SEA_TRASH_BATCH_SIZE {
-            let random_offset = Vec3::new(
-                (unsafe { (*rng).gen::<f32>() } - 0.5) * 1.0,
-                (unsafe { (*rng).gen::<f32>() } - 0.5) * 1.0,
-                0.0,
-            );
-            let random_rotation = (unsafe { (*rng).gen::<f32>() } - 0.5) * PI;
-            let is_flipped = unsafe { (*rng).gen::<bool>() };
-            let spawn_position = transform.position + random_offset;
-            let sea_trash_entity = match unsafe { (*rng).gen_range(0..3) } {
-                0 => FlipflopTrashPrefab::spawn(world),
-                1 => TunaCanTrashPrefab::spawn(world),
-                _ => BottleTrashPrefab::spawn(world),
+            let spawn_position = loop {
+                let random_offset = Vec3::new(
+                    (rng.gen::<f32>() - 0.5) * 1.0,
+                    (rng.gen::<f32>() - 0.5) * 1.0,
+                    0.0,
+                );
+                let position = player_position + random_offset;
+                if !diver_bounds
+                    .is_some_and(|bounds| bounds.overlaps(&Rect::from_points(&[position.xy()])))
+                {
+                    break position;
+                }
             };
-            world
-                .get_component_mut::<Transform>(sea_trash_entity)
-                .map(|sea_trash_transform| {
-                    sea_trash_transform.position = spawn_position;
-                    sea_trash_transform.rotation = random_rotation;
-                    sea_trash_transform.is_flipped = is_flipped;
-                });
+            let random_rotation = (rng.gen::<f32>() - 0.5) * PI;
+            let is_flipped = rng.gen::<bool>();
+            let prefab = match rng.gen_range(0..3) {
+                0 => "flipflop_trash",
+                1 => "tuna_can_trash",
+                _ => "bottle_trash",
+            };
+            let sea_trash_entity = world
+                .spawn_prefab(prefab)
+                .expect("Failed to spawn sea trash prefab");
+            commands.add(move |world| {
+                world.get_component_mut::<Transform>(sea_trash_entity).map(
+                    |mut sea_trash_transform| {
+                        sea_trash_transform.position = spawn_position;
+                        sea_trash_transform.rotation = random_rotation;
+                        sea_trash_transform.is_flipped = is_flipped;
+                    },
+                );
+            });
             self.spawned_sea_trash.push_back(sea_trash_entity);
         }
         while self.spawned_sea_trash.len() > SEA_TRASH_MAX_COUNT {
             if let Some(old_entity) = self.spawned_sea_trash.pop_front() {
-                world.delete_entity(old_entity);
+                commands.delete_entity(old_entity);
             }
         }
     }
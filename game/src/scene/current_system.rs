@@ -1,18 +1,35 @@
 use lumina_engine::logic::{
     ecs::{
+        access::Access,
         component::{material::Material, movement::Movement, transform::Transform},
-        system::system::System,
+        system::system::ParallelSystem,
     },
-    scene::world::World,
+    scene::{time::Time, world::World},
 };
 
 use crate::{player::player_state::PlayerState, scene::water::Water};
 pub struct CurrentSystem;
 
-impl System for CurrentSystem {
-    fn run(&mut self, world: &mut World, _: f32) {
+impl ParallelSystem for CurrentSystem {
+    fn access(&self) -> Access {
+        Access::new()
+            .query::<(&Movement, &Transform, &PlayerState)>()
+            .write::<Material>()
+            .read_resource::<Water>()
+            .read_resource::<Time>()
+    }
+
+    fn run(&mut self, world: &World, _: f32) {
+        let time = world.expect_resource::<Time>().elapsed_secs();
+        let player = world
+            .query::<(&Movement, &Transform, &PlayerState)>()
+            .iter()
+            .last()
+            .map(|(_, (movement_component, transform, _))| {
+                (movement_component.velocity, transform.position)
+            });
         for (_, (material_component, transform_component)) in
-            world.query_mut::<(&mut Material, &mut Transform)>()
+            world.query::<(&mut Material, &Transform)>().iter()
         {
             if material_component.parameters.is_empty() {
                 continue;
@@ -21,19 +38,16 @@ impl System for CurrentSystem {
                 let object_position = transform_component.position;
                 let mut water_current = world
                     .expect_resource::<Water>()
-                    .get_current(&object_position);
-                world
-                    .query_mut::<(&mut Movement, &mut Transform, &mut PlayerState)>()
-                    .last()
-                    .map(|(_, (movement_component, transform, _))| {
-                        let player_distance = (object_position - transform.position).length();
-                        if player_distance != 0.0 {
-                            let mut influence = 1.0 / (player_distance.powf(1.5) * 10.0);
-                            let influence_treshold = 5.5;
-                            influence = f32::min(influence_treshold, influence);
-                            water_current += influence * movement_component.velocity.x;
-                        }
-                    });
+                    .get_current(&object_position, time);
+                if let Some((player_velocity, player_position)) = player {
+                    let player_distance = (object_position - player_position).length();
+                    if player_distance != 0.0 {
+                        let mut influence = 1.0 / (player_distance.powf(1.5) * 10.0);
+                        let influence_treshold = 5.5;
+                        influence = f32::min(influence_treshold, influence);
+                        water_current += influence * player_velocity.x;
+                    }
+                }
                 material_component.set_param("uCurrent", water_current);
             }
         }
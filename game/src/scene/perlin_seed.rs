@@ -0,0 +1,11 @@
+use noise::{Perlin, Seedable};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Saves noise generators by their seed, for `#[serde(with = "perlin_seed")]`
+pub fn serialize<S: Serializer>(noise: &Perlin, serializer: S) -> Result<S::Ok, S::Error> {
+    noise.seed().serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Perlin, D::Error> {
+    Ok(Perlin::new(u32::deserialize(deserializer)?))
+}
@@ -1,7 +1,10 @@
 use std::{collections::VecDeque, f32::consts::PI};
 
 use lumina_engine::{
-    logic::scene::world::World,
+    logic::{
+        ecs::{commands::Commands, entity::entity_map::EntityMap},
+        scene::{snapshot::SerializeResource, world::World},
+    },
     math::vec3::Vec3,
     render::resource::{
         resource_manager::ResourceManager,
@@ -9,15 +12,19 @@ use lumina_engine::{
     },
 };
 use noise::{NoiseFn, Perlin};
+use serde::{Deserialize, Serialize};
 
-use crate::scene::tile::Tile;
+use crate::scene::{perlin_seed, tile::Tile};
 
+#[derive(Serialize, Deserialize)]
 pub struct Terrain {
+    #[serde(with = "perlin_seed")]
     pub noise: Perlin,
     tile_size: f32,
     pub tiles: VecDeque<Tile>,
     pub loaded_tile_index: i32,
     default_tile_count: i32,
+    #[serde(skip, default = "Terrain::default_tile_texture")]
     tile_texture: Texture,
 }
 
@@ -34,26 +41,34 @@ impl Terrain {
             tile_size,
             loaded_tile_index,
             default_tile_count, // Even
-            tile_texture: StaticColor::new((0.8235, 0.7059, 0.5490).into()).into(),
+            tile_texture: Self::default_tile_texture(),
         };
-        terrain
-            .tiles
-            .push_back(terrain.generate_tile(world, loaded_tile_index, resource_manager));
+        let mut commands = world.commands();
+        terrain.tiles.push_back(terrain.generate_tile(
+            &mut commands,
+            loaded_tile_index,
+            resource_manager,
+        ));
         for i in 1..default_tile_count / 2 + 1 as i32 {
             terrain.tiles.push_back(terrain.generate_tile(
-                world,
+                &mut commands,
                 loaded_tile_index + i,
                 resource_manager,
             ));
             terrain.tiles.push_front(terrain.generate_tile(
-                world,
+                &mut commands,
                 loaded_tile_index - i,
                 resource_manager,
             ));
         }
+        world.apply_commands();
         terrain
     }
 
+    fn default_tile_texture() -> Texture {
+        StaticColor::new((0.8235, 0.7059, 0.5490).into()).into()
+    }
+
     pub fn get_tile_size(&self) -> f32 {
         self.tile_size
     }
@@ -81,10 +96,7 @@ impl Terrain {
 
     pub fn get_height(&self, x_f: f32) -> f32 {
         let x = (x_f / self.tile_size + 0.5).floor() as i32;
-        let mut x_fract = (x_f / self.tile_size + 0.5).fract();
-        if x_fract < 0.0 {
-            x_fract = 1.0 - x_fract;
-        }
+        let x_fract = (x_f / self.tile_size + 0.5).rem_euclid(1.0);
 
         let previous_y: f32 = Terrain::get_height_noise(x - 1, &self.noise);
         let current_y: f32 = Terrain::get_height_noise(x, &self.noise);
@@ -138,12 +150,12 @@ impl Terrain {
 
     fn generate_tile(
         &self,
-        world: &mut World,
+        commands: &mut Commands,
         x: i32,
         resource_manager: &mut ResourceManager,
     ) -> Tile {
         Tile::generate(
-            world,
+            commands,
             self.tile_size,
             x,
             &self.noise,
@@ -152,3 +164,13 @@ impl Terrain {
         )
     }
 }
+
+impl SerializeResource for Terrain {
+    const NAME: &'static str = "Terrain";
+
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        for tile in &mut self.tiles {
+            tile.map_entities(entity_map);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use lumina_engine::{
     logic::{
-        ecs::{component::transform::Transform, system::system::System},
+        ecs::{commands::Commands, component::transform::Transform, system::system::System},
         scene::world::World,
     },
     render::resource::resource_manager::ResourceManager,
@@ -15,45 +15,52 @@ pub struct TerrainSystem;
 
 impl System for TerrainSystem {
     fn run(&mut self, world: &mut World, _: f32) {
-        let terrain = world.expect_resource_ptr::<Terrain>().clone();
-        let resource_manager = world.expect_resource_ptr::<ResourceManager>();
-        for (_, (_, transform)) in world.query_mut::<(&mut PlayerState, &mut Transform)>() {
-            let tile_index =
-                (transform.position.x / unsafe { &mut *terrain }.get_tile_size()).round() as i32;
-            Self::update_tile_index(
-                world,
-                unsafe { &mut *terrain },
-                unsafe { &mut *resource_manager },
+        let mut terrain = world.expect_resource_mut::<Terrain>();
+        let mut resource_manager = world.expect_resource_mut::<ResourceManager>();
+        let mut commands = world.commands();
+        for (_, (_, transform)) in world.query::<(&PlayerState, &Transform)>().iter() {
+            let tile_index = (transform.position.x / terrain.get_tile_size()).round() as i32;
+            let removed_tile = Self::update_tile_index(
+                &mut commands,
+                &mut terrain,
+                &mut resource_manager,
                 tile_index,
             );
+            if let Some(tile) = removed_tile {
+                commands.delete_entity(*tile.get_entity());
+                tile.get_objects()
+                    .iter()
+                    .for_each(|e| commands.delete_entity(*e));
+            }
         }
     }
 }
 
 impl TerrainSystem {
     fn update_tile_index(
-        world: &mut World,
+        commands: &mut Commands,
         terrain: &mut Terrain,
         resource_manager: &mut ResourceManager,
         tile_index: i32,
-    ) {
+    ) -> Option<Tile> {
         let difference = terrain.loaded_tile_index - tile_index;
         if difference != 0 {
             terrain.loaded_tile_index = tile_index;
-            match difference > 0 {
-                true => Self::sweep_left(world, terrain, resource_manager),
-                false => Self::sweep_right(world, terrain, resource_manager),
-            }
+            return match difference > 0 {
+                true => Self::sweep_left(commands, terrain, resource_manager),
+                false => Self::sweep_right(commands, terrain, resource_manager),
+            };
         }
+        None
     }
 
     fn sweep_left(
-        world: &mut World,
+        commands: &mut Commands,
         terrain: &mut Terrain,
         resource_manager: &mut ResourceManager,
-    ) {
+    ) -> Option<Tile> {
         let new_tile = Tile::generate(
-            world,
+            commands,
             terrain.get_tile_size(),
             (terrain.loaded_tile_index - terrain.get_default_tile_count() / 2) as i32,
             &terrain.noise,
@@ -61,21 +68,16 @@ impl TerrainSystem {
             resource_manager,
         );
         terrain.tiles.push_front(new_tile);
-        terrain.tiles.pop_back().map(|tile: Tile| {
-            world.delete_entity(*tile.get_entity());
-            tile.get_objects()
-                .iter()
-                .for_each(|e| world.delete_entity(*e))
-        });
+        terrain.tiles.pop_back()
     }
 
     fn sweep_right(
-        world: &mut World,
+        commands: &mut Commands,
         terrain: &mut Terrain,
         resource_manager: &mut ResourceManager,
-    ) {
+    ) -> Option<Tile> {
         let new_tile = Tile::generate(
-            world,
+            commands,
             terrain.get_tile_size(),
             (terrain.loaded_tile_index + terrain.get_default_tile_count() / 2) as i32,
             &terrain.noise,
@@ -83,11 +85,6 @@ impl TerrainSystem {
             resource_manager,
         );
         terrain.tiles.push_back(new_tile);
-        terrain.tiles.pop_front().map(|tile| {
-            world.delete_entity(*tile.get_entity());
-            tile.get_objects()
-                .iter()
-                .for_each(|e| world.delete_entity(*e))
-        });
+        terrain.tiles.pop_front()
     }
 }
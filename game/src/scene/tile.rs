@@ -1,14 +1,14 @@
 use lumina_engine::{
-    logic::{
-        ecs::{
-            component::{
-                material::{DrawMode, Material},
-                model::Model,
-                transform::Transform,
-            },
-            entity::entity::Entity,
+    logic::ecs::{
+        commands::Commands,
+        component::{
+            collider::{Collider, ColliderShape},
+            material::{DrawMode, Material},
+            model::Model,
+            rigid_body::{BodyType, RigidBody},
+            transform::Transform,
         },
-        scene::world::World,
+        entity::{entity::Entity, entity_map::EntityMap},
     },
     math::{vec2::Vec2, vec3::Vec3},
     render::{
@@ -20,11 +20,16 @@ use lumina_engine::{
     },
 };
 use noise::Perlin;
+use serde::{Deserialize, Serialize};
 
 use crate::scene::{current::Current, object_type::ObjectType};
 
 use super::terrain::Terrain;
 
+// Segments of the slope the tile collides with
+const COLLIDER_SEGMENTS: usize = 8;
+
+#[derive(Serialize, Deserialize)]
 pub struct Tile {
     entity: Entity,
     uphill: bool,
@@ -34,7 +39,7 @@ pub struct Tile {
 
 impl Tile {
     pub fn generate(
-        world: &mut World,
+        commands: &mut Commands,
         size: f32,
         x: i32,
         noise: &Perlin,
@@ -99,7 +104,7 @@ impl Tile {
                 );
                 position.z = tile_z;
                 // TODO: use prefab
-                let seaweed = world.create_entity();
+                let seaweed = commands.create_entity();
                 let seaweed_mesh = resource_manager.get_mesh("seagrass");
                 let shader = resource_manager.get_shader("model_with_tesselation");
                 let use_tesselation = shader.get_handle().has_tesselation;
@@ -116,12 +121,12 @@ impl Tile {
                     if use_tesselation {
                         material.set_param("uCurrent", 0f32);
                     }
-                    world.add_component(seaweed, material);
+                    commands.add_component(seaweed, material);
                 }
-                world.add_component::<Current>(seaweed, Current::default());
-                world.add_component::<Model>(seaweed, Model { mesh: seaweed_mesh });
+                commands.add_component::<Current>(seaweed, Current::default());
+                commands.add_component::<Model>(seaweed, Model { mesh: seaweed_mesh });
 
-                world.add_component(
+                commands.add_component(
                     seaweed,
                     Transform {
                         position: position,
@@ -133,7 +138,7 @@ impl Tile {
                 objects.push(seaweed);
             }
         }
-        let tile = world.create_entity();
+        let tile = commands.create_entity();
         let mesh = resource_manager
             .load_mesh(
                 vertices.to_vec(),
@@ -141,7 +146,7 @@ impl Tile {
                 sprite::UVS.to_vec(),
             )
             .unwrap();
-        world.add_component::<Model>(tile, Model { mesh: mesh.into() });
+        commands.add_component::<Model>(tile, Model { mesh: mesh.into() });
         let shader = resource_manager.get_shader("model");
         let material = Material::new(texture.clone(), shader.clone())
             .with_param("uObjectType", ObjectType::Terrain as i32)
@@ -152,8 +157,8 @@ impl Tile {
                 false => DrawMode::Triangles,
             });
 
-        world.add_component(tile, material);
-        world.add_component(
+        commands.add_component(tile, material);
+        commands.add_component(
             tile,
             Transform {
                 position: tile_position,
@@ -162,6 +167,21 @@ impl Tile {
                 is_flipped: false,
             },
         );
+        // Follows the slope drawn by the shader. Without friction bodies slide along the seabed
+        let heights = (0..=COLLIDER_SEGMENTS)
+            .map(|i| {
+                let ratio = i as f32 / COLLIDER_SEGMENTS as f32;
+                bot + height * Terrain::interpolate(f32::from(!uphill), f32::from(uphill), ratio)
+            })
+            .collect();
+        commands.add_component(
+            tile,
+            Collider::new(ColliderShape::Heightfield {
+                width: size,
+                heights,
+            }),
+        );
+        commands.add_component(tile, RigidBody::new(BodyType::Static).with_friction(0.0));
         Tile {
             entity: tile,
             uphill,
@@ -185,4 +205,11 @@ impl Tile {
     pub fn get_objects(&self) -> &Vec<Entity> {
         &self.objects
     }
+
+    pub fn map_entities(&mut self, entity_map: &EntityMap) {
+        self.entity = entity_map.map(self.entity);
+        for object in &mut self.objects {
+            *object = entity_map.map(*object);
+        }
+    }
 }
@@ -1,12 +1,14 @@
-use std::time::Instant;
-
-use lumina_engine::math::vec3::Vec3;
+use lumina_engine::{logic::scene::snapshot::SerializeResource, math::vec3::Vec3};
 use noise::{NoiseFn, Perlin};
+use serde::{Deserialize, Serialize};
+
+use crate::scene::perlin_seed;
 
+#[derive(Serialize, Deserialize)]
 pub struct Water {
     resistance: f32,
+    #[serde(with = "perlin_seed")]
     current_noise: Perlin,
-    current_start: Instant,
 }
 
 impl Water {
@@ -14,7 +16,6 @@ impl Water {
         Water {
             resistance: 0.9,
             current_noise: Perlin::new(seed),
-            current_start: Instant::now(),
         }
     }
 
@@ -22,12 +23,14 @@ impl Water {
         self.resistance
     }
 
-    pub fn get_current(&self, pos: &Vec3) -> f32 {
+    // `time` is the scene time in seconds
+    pub fn get_current(&self, pos: &Vec3, time: f32) -> f32 {
         let speed = 0.5;
-        let time = Instant::now()
-            .duration_since(self.current_start)
-            .as_secs_f32();
         self.current_noise
             .get([pos.x as f64, pos.y as f64, speed * time as f64]) as f32
     }
 }
+
+impl SerializeResource for Water {
+    const NAME: &'static str = "Water";
+}
@@ -1,5 +1,3 @@
-pub mod bottle_trash_prefab;
-pub mod flipflop_trash_prefab;
+pub mod collected_trash;
 pub mod sea_trash;
 pub mod sea_trash_system;
-pub mod tuna_can_trash_prefab;
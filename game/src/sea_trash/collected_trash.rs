@@ -0,0 +1,5 @@
+// Number of sea trash pieces the player has picked up so far
+#[derive(Default)]
+pub struct CollectedTrash {
+    pub count: u32,
+}
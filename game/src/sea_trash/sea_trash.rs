@@ -1,4 +1,6 @@
 use lumina_engine::logic::ecs::component::component::Component;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
+#[component(serialize)]
 pub struct SeaTrash;
@@ -1,14 +1,71 @@
-use lumina_engine::logic::{
-    ecs::{component::collider::Collider, system::system::System},
-    scene::world::World,
+use lumina_engine::{
+    logic::{
+        ecs::{collision_event::CollisionEvents, entity::entity::Entity, system::system::System},
+        scene::world::World,
+    },
+    shared::input::{action_map::ActionMap, input_state::InputState},
 };
 
-use crate::sea_trash::sea_trash::SeaTrash;
+use crate::{
+    player::player_state::PlayerState,
+    sea_trash::{collected_trash::CollectedTrash, sea_trash::SeaTrash},
+};
 
+// Collects the sea trash under the cursor on the "collect" action and the sea trash the diver
+// swims into
 pub struct SeaTrashSystem;
 
+impl SeaTrashSystem {
+    fn clicked_trash(world: &World) -> Option<Entity> {
+        let collect = world
+            .get_resource::<ActionMap>()
+            .is_some_and(|actions| actions.just_pressed("collect"));
+        let mouse_position = match world.get_resource::<InputState>() {
+            Some(input_state) if collect => input_state.get_mouse_position(),
+            _ => return None,
+        };
+        world
+            .pick(mouse_position)
+            .into_iter()
+            .find(|entity| world.get_component::<SeaTrash>(*entity).is_some())
+    }
+
+    fn touched_trash(world: &World) -> Vec<Entity> {
+        let Some(events) = world.get_resource::<CollisionEvents>() else {
+            return Vec::new();
+        };
+        events
+            .started()
+            .filter_map(|event| {
+                [
+                    (event.entity_a, event.entity_b),
+                    (event.entity_b, event.entity_a),
+                ]
+                .into_iter()
+                .find(|(trash, diver)| {
+                    world.get_component::<SeaTrash>(*trash).is_some()
+                        && world.get_component::<PlayerState>(*diver).is_some()
+                })
+                .map(|(trash, _)| trash)
+            })
+            .collect()
+    }
+}
+
 impl System for SeaTrashSystem {
     fn run(&mut self, world: &mut World, _: f32) {
-        for (_, (_sea_trash, _collider)) in world.query_mut::<(&mut SeaTrash, &mut Collider)>() {}
+        let mut trash: Vec<Entity> = Self::touched_trash(world);
+        trash.extend(Self::clicked_trash(world));
+        trash.sort_by_key(|entity| entity.id);
+        trash.dedup();
+        for trash in trash {
+            if !world.is_alive(trash) {
+                continue;
+            }
+            world.despawn_recursive(trash);
+            if let Some(mut collected) = world.get_resource_mut::<CollectedTrash>() {
+                collected.count += 1;
+            }
+        }
     }
 }
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod determinism_test {
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::ecs::entity::entity::Entity;
+    use lumina_engine::shared::input::input_event::InputEvent;
+    use lumina_engine::shared::input::input_recording::{InputRecorder, InputRecording};
+    use lumina_engine::HeadlessRunner;
+    use lumina_game::game::initialize_scene;
+    use winit::keyboard::Key;
+
+    type Snapshot = Vec<(Entity, [u32; 6], bool)>;
+
+    fn snapshot(runner: &HeadlessRunner) -> Snapshot {
+        let world = runner.scene().get_world();
+        let mut query = world.query::<&Transform>();
+        let mut transforms: Snapshot = query
+            .iter()
+            .map(|(entity, transform)| {
+                let values = [
+                    transform.position.x,
+                    transform.position.y,
+                    transform.position.z,
+                    transform.rotation,
+                    transform.scale.x,
+                    transform.scale.y,
+                ];
+                (entity, values.map(f32::to_bits), transform.is_flipped)
+            })
+            .collect();
+        transforms.sort_by_key(|(entity, ..)| entity.id);
+        transforms
+    }
+
+    fn press(runner: &mut HeadlessRunner, key: &str, frames: u64) {
+        runner.send_input(InputEvent::KeyDown(Key::Character(key.into())));
+        runner.run(frames);
+        runner.send_input(InputEvent::KeyUp(Key::Character(key.into())));
+    }
+
+    // Spawns fish and trash, then swims around; 1000 frames in total
+    // Runs in the default parallel executor mode, so systems of a batch race each other
+    fn play(seed: u64) -> Snapshot {
+        let mut runner = HeadlessRunner::seeded(seed, initialize_scene);
+        press(&mut runner, "f", 1);
+        press(&mut runner, "t", 1);
+        press(&mut runner, "d", 300);
+        runner.run(100);
+        press(&mut runner, "w", 200);
+        press(&mut runner, "a", 150);
+        runner.run(248);
+        assert_eq!(runner.frame(), 1000);
+        snapshot(&runner)
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_transforms() {
+        let first = play(42);
+        assert!(first.len() > 25);
+        assert_eq!(first, play(42));
+    }
+
+    #[test]
+    fn test_recorded_run_replays_exactly() {
+        let path = std::env::temp_dir().join(format!("lumina_replay_{}.ron", std::process::id()));
+        let path = path.to_str().unwrap();
+        let mut runner = HeadlessRunner::from_entropy(initialize_scene);
+        runner
+            .start_recording(InputRecorder::create(path).unwrap())
+            .unwrap();
+        press(&mut runner, "f", 1);
+        press(&mut runner, "d", 200);
+        // Uneven frame times must be replayed as they were
+        runner = runner.with_delta_time(1.0 / 45.0);
+        press(&mut runner, "s", 120);
+        runner.run(30);
+        let recorded = snapshot(&runner);
+
+        let recording = InputRecording::load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let mut replayed = HeadlessRunner::for_replay(&recording, initialize_scene);
+        replayed.replay(recording);
+        assert_eq!(replayed.frame(), 351);
+        assert_eq!(snapshot(&replayed), recorded);
+    }
+
+    #[test]
+    fn test_seed_changes_the_run() {
+        assert_ne!(play(1), play(2));
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod headless_test {
+    use lumina_engine::logic::ecs::component::camera::Camera;
+    use lumina_engine::logic::ecs::component::collider::Collider;
+    use lumina_engine::logic::ecs::component::global_transform::GlobalTransform;
+    use lumina_engine::logic::ecs::component::movement::Movement;
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::scene::matrix_uniform_buffer::MatrixUniformBuffer;
+    use lumina_engine::math::vec2::Vec2;
+    use lumina_engine::render::uniform_buffer_source::UniformBufferSource;
+    use lumina_engine::shared::input::gamepad::GamepadAxis;
+    use lumina_engine::shared::input::input_event::InputEvent;
+    use lumina_engine::shared::window_size::WindowSize;
+    use lumina_engine::HeadlessRunner;
+    use lumina_game::fish::fish::Fish;
+    use lumina_game::game::initialize_scene;
+    use lumina_game::player::{player_part::PlayerPart, player_state::PlayerState};
+    use lumina_game::scene::terrain::Terrain;
+    use lumina_game::sea_trash::{collected_trash::CollectedTrash, sea_trash::SeaTrash};
+    use winit::event::MouseButton;
+    use winit::keyboard::Key;
+
+    const SEED: u64 = 42;
+
+    fn player_x(runner: &HeadlessRunner) -> f32 {
+        let world = runner.scene().get_world();
+        let mut query = world.query::<(&Transform, &PlayerState)>();
+        let (_, (transform, _)) = query.iter().next().expect("No player");
+        transform.position.x
+    }
+
+    #[test]
+    fn test_game_runs_without_window() {
+        let mut runner = HeadlessRunner::seeded(SEED, initialize_scene);
+        for key in ["f", "t"] {
+            runner.send_input(InputEvent::KeyDown(Key::Character(key.into())));
+            runner.tick();
+            runner.send_input(InputEvent::KeyUp(Key::Character(key.into())));
+        }
+        for _ in 0..300 {
+            let frame = runner.tick();
+            assert!(!frame.entities.is_empty());
+            assert!(frame.postprocess_pass.is_some());
+        }
+        assert_eq!(runner.frame(), 302);
+        let world = runner.scene().get_world();
+        assert_eq!(world.query::<&Fish>().iter().count(), 25);
+        let collected = world.expect_resource::<CollectedTrash>().count as usize;
+        assert_eq!(world.query::<&SeaTrash>().iter().count() + collected, 5);
+        assert_eq!(world.query::<&PlayerPart>().iter().count(), 8);
+    }
+
+    #[test]
+    fn test_input_moves_player() {
+        let mut runner = HeadlessRunner::new(initialize_scene);
+        runner.run(10);
+        let start = player_x(&runner);
+        runner.send_input(InputEvent::KeyDown(Key::Character("d".into())));
+        runner.run(120);
+        assert!(player_x(&runner) > start + 0.1);
+
+        runner.send_input(InputEvent::KeyUp(Key::Character("d".into())));
+        runner.run(240);
+        let stopped = player_x(&runner);
+        runner.run(60);
+        assert!((player_x(&runner) - stopped).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_gamepad_stick_steers_player() {
+        let mut runner = HeadlessRunner::new(initialize_scene);
+        runner.run(10);
+        let start = player_x(&runner);
+        runner.send_input(InputEvent::GamepadConnected(0));
+        runner.send_input(InputEvent::GamepadAxis {
+            id: 0,
+            axis: GamepadAxis::LeftStickX,
+            value: 0.5,
+        });
+        runner.run(120);
+        assert!(player_x(&runner) > start + 0.05);
+        let world = runner.scene().get_world();
+        let mut query = world.query::<&PlayerState>();
+        let (_, state) = query.iter().next().expect("No player");
+        assert!(matches!(
+            state,
+            PlayerState::Swimming { direction } if direction.x == 0.5 && direction.y == 0.0
+        ));
+    }
+
+    #[test]
+    fn test_clicking_sea_trash_collects_it() {
+        let mut runner = HeadlessRunner::seeded(SEED, initialize_scene);
+        let window_size = WindowSize {
+            width: 800,
+            height: 600,
+        };
+        runner
+            .scene_mut()
+            .get_world_mut()
+            .insert_resource(window_size.clone());
+        runner.send_input(InputEvent::KeyDown(Key::Character("t".into())));
+        runner.tick();
+        runner.send_input(InputEvent::KeyUp(Key::Character("t".into())));
+        runner.run(10);
+
+        let world = runner.scene().get_world();
+        let trash_count = world.query::<&SeaTrash>().iter().count();
+        let collected = world.expect_resource::<CollectedTrash>().count;
+        let position = world
+            .query::<(&SeaTrash, &GlobalTransform)>()
+            .iter()
+            .map(|(_, (_, global))| global.transform.position)
+            .next()
+            .expect("No sea trash");
+        let matrices = world
+            .get_resource::<UniformBufferSource<MatrixUniformBuffer>>()
+            .unwrap()
+            .data;
+        let screen = Camera::world_to_screen(&matrices, &window_size, position);
+        runner.send_input(InputEvent::MouseMove(screen));
+        runner.send_input(InputEvent::MouseEvent {
+            button: MouseButton::Left,
+            pressed: true,
+        });
+        runner.tick();
+
+        let world = runner.scene().get_world();
+        assert_eq!(world.query::<&SeaTrash>().iter().count(), trash_count - 1);
+        assert_eq!(
+            world.expect_resource::<CollectedTrash>().count,
+            collected + 1
+        );
+    }
+
+    #[test]
+    fn test_diver_touching_sea_trash_collects_it() {
+        let mut runner = HeadlessRunner::seeded(SEED, initialize_scene);
+        runner.send_input(InputEvent::KeyDown(Key::Character("t".into())));
+        runner.tick();
+        runner.send_input(InputEvent::KeyUp(Key::Character("t".into())));
+        runner.run(10);
+
+        let world = runner.scene().get_world();
+        let trash_count = world.query::<&SeaTrash>().iter().count();
+        let collected = world.expect_resource::<CollectedTrash>().count;
+        let player_position = world
+            .query::<(&PlayerState, &Transform)>()
+            .iter()
+            .map(|(_, (_, transform))| transform.position)
+            .next()
+            .expect("No player");
+        let trash = world
+            .query::<&SeaTrash>()
+            .iter()
+            .map(|(entity, _)| entity)
+            .next()
+            .expect("No sea trash");
+        runner
+            .scene_mut()
+            .get_world_mut()
+            .get_component_mut::<Transform>(trash)
+            .unwrap()
+            .position = player_position;
+        runner.run(2);
+
+        let world = runner.scene().get_world();
+        assert!(!world.is_alive(trash));
+        assert_eq!(world.query::<&SeaTrash>().iter().count(), trash_count - 1);
+        assert_eq!(
+            world.expect_resource::<CollectedTrash>().count,
+            collected + 1
+        );
+    }
+
+    #[test]
+    fn test_diver_slides_along_seabed() {
+        let mut runner = HeadlessRunner::new(initialize_scene);
+        runner.run(10);
+        let start = player_x(&runner);
+        for key in ["s", "d"] {
+            runner.send_input(InputEvent::KeyDown(Key::Character(key.into())));
+        }
+        let mut touched = 0;
+        for _ in 0..300 {
+            runner.tick();
+            let world = runner.scene().get_world();
+            let terrain = world.expect_resource::<Terrain>();
+            let mut query = world.query::<(&PlayerState, &Collider, &Movement)>();
+            let (_, (_, collider, movement)) = query.iter().next().expect("No player");
+            for point in collider.boundary_points() {
+                let height = terrain.get_height(point.x);
+                // The diver never sinks into the seabed
+                assert!(point.y > height - 0.01);
+                if point.y < height + 0.005 {
+                    let slope = terrain.get_height(point.x + 0.01) - height;
+                    let normal = Vec2::new(-slope, 0.01).normalized();
+                    // Nor keeps pushing into it
+                    assert!(Vec2::dot(&movement.velocity.xy(), &normal) > -0.05);
+                    touched += 1;
+                }
+            }
+        }
+        // And swimming down into it moves the diver along it
+        assert!(touched > 100);
+        assert!(player_x(&runner) > start + 0.5);
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod snapshot_test {
+    use lumina_engine::logic::ecs::component::transform::Transform;
+    use lumina_engine::logic::scene::scene::Scene;
+    use lumina_engine::logic::scene::snapshot::{SnapshotFormat, SnapshotRegistry};
+    use lumina_engine::HeadlessRunner;
+    use lumina_game::camera::follow::Follow;
+    use lumina_game::game::{initialize_scene, register_snapshot_types};
+    use lumina_game::player::player_state::PlayerState;
+    use lumina_game::scene::{terrain::Terrain, water::Water};
+
+    #[test]
+    fn test_game_world_round_trip() {
+        let mut runner = HeadlessRunner::new(initialize_scene);
+        runner.run(30);
+        let world = runner.scene().get_world();
+        let data = world.save(SnapshotFormat::Binary).unwrap();
+        let (player, player_x) = {
+            let mut query = world.query::<(&Transform, &PlayerState)>();
+            let (player, (transform, _)) = query.iter().next().unwrap();
+            (player, transform.position.x)
+        };
+        let tile_count = world.expect_resource::<Terrain>().get_tiles().len();
+
+        let mut scene = Scene::new();
+        let world = scene.get_world_mut();
+        register_snapshot_types(&mut world.expect_resource_mut::<SnapshotRegistry>());
+        let entity_map = world.load_snapshot(&data, SnapshotFormat::Binary).unwrap();
+
+        let player = entity_map.map(player);
+        assert_eq!(
+            world.get_component::<Transform>(player).unwrap().position.x,
+            player_x
+        );
+        assert!(world.get_component::<PlayerState>(player).is_some());
+        let mut follows = world.query::<&Follow>();
+        let (_, follow) = follows.iter().next().expect("Camera follow wasn't loaded");
+        assert_eq!(follow.target_entity, player);
+
+        let terrain = world.expect_resource::<Terrain>();
+        assert_eq!(terrain.get_tiles().len(), tile_count);
+        for tile in terrain.get_tiles() {
+            assert!(world
+                .get_component::<Transform>(*tile.get_entity())
+                .is_some());
+        }
+        assert_eq!(world.expect_resource::<Water>().get_resistance(), 0.9);
+    }
+}
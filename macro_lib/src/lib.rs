@@ -1,12 +1,86 @@
 use proc_macro::TokenStream;
-use syn::{parse_macro_input, DeriveInput};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index, Type};
 
-#[proc_macro_derive(Component)]
+// `#[component(serialize)]` also implements `SerializeComponent`, which needs `Clone` and serde's
+// `Serialize` and `Deserialize`. Fields of type `Entity` are remapped when a snapshot is loaded
+#[proc_macro_derive(Component, attributes(component))]
 pub fn derive_component(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let name = input.ident;
-    let expanded = quote::quote! {
+    let name = &input.ident;
+    let mut serialize = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("component"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("serialize") {
+                serialize = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `serialize`"))
+            }
+        });
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+    }
+
+    let mut expanded = quote! {
         impl Component for #name {}
     };
+    if serialize {
+        let name_str = name.to_string();
+        let mapped = entity_fields(&input.data);
+        expanded.extend(quote! {
+            impl ::lumina_engine::logic::ecs::component::component::SerializeComponent for #name {
+                const NAME: &'static str = #name_str;
+
+                fn map_entities(
+                    &mut self,
+                    entity_map: &::lumina_engine::logic::ecs::entity::entity_map::EntityMap,
+                ) {
+                    #(self.#mapped = entity_map.map(self.#mapped);)*
+                }
+            }
+        });
+    }
     TokenStream::from(expanded)
 }
+
+fn entity_fields(data: &Data) -> Vec<proc_macro2::TokenStream> {
+    let Data::Struct(data) = data else {
+        return Vec::new();
+    };
+    let is_entity = |ty: &Type| match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Entity"),
+        _ => false,
+    };
+    match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter(|field| is_entity(&field.ty))
+            .map(|field| {
+                let ident = &field.ident;
+                quote!(#ident)
+            })
+            .collect(),
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| is_entity(&field.ty))
+            .map(|(index, _)| {
+                let index = Index::from(index);
+                quote!(#index)
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+    }
+}